@@ -0,0 +1,149 @@
+use solver::{
+    checker::verifier::check,
+    repl::Repl,
+    system::{solve, solve_auto, System},
+};
+use std::{
+    env, fs,
+    io::{self, BufRead, Read, Write},
+    process,
+};
+
+const USAGE: &str = "usage: copl [--system <system>] [--file <path> | [--] <judgment>]
+       copl --check [--system <system>] [--file <path> | [--] <derivation>]
+       copl --repl";
+
+struct Args {
+    system: Option<System>,
+    check: bool,
+    judgment: String,
+}
+
+fn main() {
+    if env::args().nth(1).as_deref() == Some("--repl") {
+        repl();
+        return;
+    }
+    let args = match parse_args(env::args().skip(1).collect()) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("copl: {}\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+    if args.check {
+        check_derivation(args);
+        return;
+    }
+    // 体系の指定がなければ判断の形から選ぶ
+    let result = match args.system {
+        Some(system) => solve(system, &args.judgment),
+        None => solve_auto(&args.judgment).map(|(system, rule)| {
+            eprintln!("copl: using {}", system);
+            rule
+        }),
+    };
+    match result {
+        // パイプが閉じられても panic しない
+        Ok(rule) => {
+            let _ = writeln!(io::stdout(), "{}", rule);
+        }
+        Err(e) => {
+            eprintln!("copl: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+// 手で書いた導出を検査する (体系の指定がなければ環境の有無で EvalML1 か EvalML2 を選ぶ)
+fn check_derivation(args: Args) {
+    let system = args.system.unwrap_or(if args.judgment.contains("|-") {
+        System::EvalML2
+    } else {
+        System::EvalML1
+    });
+    match check(system, &args.judgment) {
+        Ok(()) => println!("valid {} derivation", system),
+        Err(e) => {
+            eprintln!("copl: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn repl() {
+    let mut repl = Repl::new();
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("copl> ");
+        let _ = io::stdout().flush();
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            _ => break,
+        };
+        match repl.eval(&line) {
+            Some(output) if output.is_empty() => {}
+            Some(output) => println!("{}", output),
+            None => break,
+        }
+    }
+}
+
+// 判断 (--check では導出) は引数, --file, 標準入力の順に探す
+// 知らないフラグは判断とみなすので -(3 + 4) evalto -7 もそのまま渡せる
+fn parse_args(args: Vec<String>) -> Result<Args, String> {
+    let mut system = None;
+    let mut check = false;
+    let mut file = None;
+    let mut judgment = None;
+    let mut options = true;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            _ if !options => {
+                if judgment.is_some() {
+                    return Err(format!("unexpected argument `{}`", arg));
+                }
+                judgment = Some(arg);
+            }
+            "--" => options = false,
+            "-s" | "--system" => {
+                let name = args.next().ok_or("missing value for --system")?;
+                system = Some(name.parse::<System>()?);
+            }
+            "-c" | "--check" => check = true,
+            "-f" | "--file" => {
+                file = Some(args.next().ok_or("missing value for --file")?);
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            _ if judgment.is_none() => judgment = Some(arg),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+    let judgment = match (judgment, file) {
+        (Some(_), Some(_)) => return Err("give either a judgment or --file".to_string()),
+        (Some(judgment), None) => judgment,
+        (None, Some(path)) => {
+            fs::read_to_string(&path).map_err(|e| format!("cannot read `{}`: {}", path, e))?
+        }
+        (None, None) => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("cannot read stdin: {}", e))?;
+            input
+        }
+    };
+    if judgment.trim().is_empty() {
+        return Err("empty judgment".to_string());
+    }
+    Ok(Args {
+        system,
+        check,
+        judgment,
+    })
+}
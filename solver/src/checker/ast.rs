@@ -0,0 +1,37 @@
+use crate::eval_ml2::ast::{Env, Expr};
+use std::fmt;
+
+// 規則の適用 (判断 by 規則名 { 前提; ... })
+#[derive(Clone, Debug, PartialEq)]
+pub struct Derivation {
+    pub judgment: String,
+    pub rule: String,
+    pub premises: Vec<Derivation>,
+    pub line: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Judgment {
+    EvalTo(Env, Expr, Expr),
+    Plus(i64, i64, i64),
+    Minus(i64, i64, i64),
+    Times(i64, i64, i64),
+    Lt(i64, i64, bool),
+}
+
+impl fmt::Display for Judgment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::checker::ast::Judgment::*;
+
+        match self {
+            EvalTo(env, expr1, expr2) if env == &Env::new() => {
+                write!(f, "{} evalto {}", expr1, expr2)
+            }
+            EvalTo(env, expr1, expr2) => write!(f, "{} |- {} evalto {}", env, expr1, expr2),
+            Plus(i1, i2, i3) => write!(f, "{} plus {} is {}", i1, i2, i3),
+            Minus(i1, i2, i3) => write!(f, "{} minus {} is {}", i1, i2, i3),
+            Times(i1, i2, i3) => write!(f, "{} times {} is {}", i1, i2, i3),
+            Lt(i1, i2, b) => write!(f, "{} less than {} is {}", i1, i2, b),
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use crate::{
+    checker::ast::{Derivation, Judgment},
+    eval_ml2::parser::{parse_env_expr, parse_expr},
+    syntax::error::{Error, IResult},
+    util::ws,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, digit1, multispace0},
+    combinator::{all_consuming, opt, recognize},
+    multi::separated_list0,
+    sequence::tuple,
+};
+
+// <derivation> ::= <judgment> 'by' <rule> '{' [ <derivation> [ ';' <derivation> ]* ] '}'
+// <rule> ::= 文字列 (英数字と '-')
+// <judgment> ::= [ <env> '|-' ] <expr> 'evalto' <value>
+//              | <int> 'plus' <int> 'is' <int>
+//              | <int> 'minus' <int> 'is' <int>
+//              | <int> 'times' <int> 'is' <int>
+//              | <int> 'less' 'than' <int> 'is' <bool>
+//              | <int> 'is' 'less' 'than' <int>
+
+pub fn parse(input: &str) -> IResult<&str, Derivation> {
+    let (rest, mut derivation) = parse_derivation(input)?;
+    set_line(&mut derivation, input);
+    Ok((rest, derivation))
+}
+
+fn parse_derivation(input: &str) -> IResult<&str, Derivation> {
+    let (input, _) = multispace0(input)?;
+    // 行番号は後で求めるので、ひとまず残りの長さを入れておく
+    let line = input.len();
+    let (input, (judgment, _, rule, _, premises, _)) = tuple((
+        parse_judgment_text,
+        ws(tag("by")),
+        take_while1(|c: char| c.is_alphanumeric() || c == '-'),
+        ws(char('{')),
+        separated_list0(ws(char(';')), parse_derivation),
+        ws(char('}')),
+    ))(input)?;
+    let derivation = Derivation {
+        judgment: judgment.trim().to_string(),
+        rule: rule.to_string(),
+        premises,
+        line,
+    };
+    Ok((input, derivation))
+}
+
+// 判断の中に '{' が現れることもある (継続) ので, 行内で最初の "by 規則名 {" を探す
+// (同じ行に続く前提まで見ないよう, 括弧の外の ';' か閉じていない '}' で止める)
+fn parse_judgment_text(input: &str) -> IResult<&str, &str> {
+    let mut depth = 0;
+    let end = input
+        .char_indices()
+        .find(|&(_, c)| match c {
+            '\n' => true,
+            '{' => {
+                depth += 1;
+                false
+            }
+            '}' if depth == 0 => true,
+            '}' => {
+                depth -= 1;
+                false
+            }
+            ';' => depth == 0,
+            _ => false,
+        })
+        .map_or(input.len(), |(i, _)| i);
+    let is_rule = |rest: &str| {
+        let rest = rest.trim_start_matches(|c: char| c.is_alphanumeric() || c == '-');
+        rest.trim_start().starts_with('{')
+    };
+    match input[..end]
+        .match_indices(" by ")
+        .map(|(i, _)| i)
+        .find(|&i| is_rule(&input[i + 4..end]))
+    {
+        Some(i) => Ok((&input[i..], &input[..i])),
+        None => Err(nom::Err::Error(Error::new(input, "`by`".to_string()))),
+    }
+}
+
+fn set_line(derivation: &mut Derivation, input: &str) {
+    let offset = input.len() - derivation.line;
+    derivation.line = input[..offset].matches('\n').count() + 1;
+    for premise in derivation.premises.iter_mut() {
+        set_line(premise, input);
+    }
+}
+
+pub fn parse_judgment(input: &str) -> IResult<&str, Judgment> {
+    all_consuming(alt((
+        parse_eval_to,
+        parse_plus,
+        parse_minus,
+        parse_times,
+        parse_less_than,
+        parse_is_less_than,
+    )))(input)
+}
+
+fn parse_eval_to(input: &str) -> IResult<&str, Judgment> {
+    let (input, (env, expr1)) = parse_env_expr(input)?;
+    let (input, (_, expr2)) = tuple((ws(tag("evalto")), parse_expr))(input)?;
+    Ok((input, Judgment::EvalTo(env, expr1, expr2)))
+}
+
+fn parse_plus(input: &str) -> IResult<&str, Judgment> {
+    let (input, (i1, _, i2, _, i3)) = tuple((
+        parse_int,
+        ws(tag("plus")),
+        parse_int,
+        ws(tag("is")),
+        parse_int,
+    ))(input)?;
+    Ok((input, Judgment::Plus(i1, i2, i3)))
+}
+
+fn parse_minus(input: &str) -> IResult<&str, Judgment> {
+    let (input, (i1, _, i2, _, i3)) = tuple((
+        parse_int,
+        ws(tag("minus")),
+        parse_int,
+        ws(tag("is")),
+        parse_int,
+    ))(input)?;
+    Ok((input, Judgment::Minus(i1, i2, i3)))
+}
+
+fn parse_times(input: &str) -> IResult<&str, Judgment> {
+    let (input, (i1, _, i2, _, i3)) = tuple((
+        parse_int,
+        ws(tag("times")),
+        parse_int,
+        ws(tag("is")),
+        parse_int,
+    ))(input)?;
+    Ok((input, Judgment::Times(i1, i2, i3)))
+}
+
+fn parse_less_than(input: &str) -> IResult<&str, Judgment> {
+    let (input, (i1, _, _, i2, _, b)) = tuple((
+        parse_int,
+        ws(tag("less")),
+        ws(tag("than")),
+        parse_int,
+        ws(tag("is")),
+        parse_bool,
+    ))(input)?;
+    Ok((input, Judgment::Lt(i1, i2, b)))
+}
+
+// solver の B-Lt が出力する形 (真のときだけ書ける)
+fn parse_is_less_than(input: &str) -> IResult<&str, Judgment> {
+    let (input, (i1, _, _, _, i2)) = tuple((
+        parse_int,
+        ws(tag("is")),
+        ws(tag("less")),
+        ws(tag("than")),
+        parse_int,
+    ))(input)?;
+    Ok((input, Judgment::Lt(i1, i2, true)))
+}
+
+fn parse_int(input: &str) -> IResult<&str, i64> {
+    let (rest, i) = ws(parse_number)(input)?;
+    match i.parse::<i64>() {
+        Ok(i) => Ok((rest, i)),
+        // i64 に収まらない整数
+        Err(_) => Err(nom::Err::Error(Error::new(input, "integer".to_string()))),
+    }
+}
+
+fn parse_number(input: &str) -> IResult<&str, &str> {
+    recognize(tuple((opt(char('-')), digit1)))(input)
+}
+
+fn parse_bool(input: &str) -> IResult<&str, bool> {
+    let (input, b) = alt((ws(tag("true")), ws(tag("false"))))(input)?;
+    Ok((input, b == "true"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        checker::{
+            ast::{Derivation, Judgment},
+            parser::{parse, parse_judgment},
+        },
+        eval_ml2::ast::{Env, Expr::*},
+    };
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(
+            parse("3 + 5 evalto 8 by E-Plus {\n    3 evalto 3 by E-Int {};\n    5 evalto 5 by E-Int {};\n    3 plus 5 is 8  by B-Plus {}\n}")
+                .unwrap()
+                .1,
+            Derivation {
+                judgment: "3 + 5 evalto 8".to_string(),
+                rule: "E-Plus".to_string(),
+                premises: vec![
+                    Derivation {
+                        judgment: "3 evalto 3".to_string(),
+                        rule: "E-Int".to_string(),
+                        premises: vec![],
+                        line: 2,
+                    },
+                    Derivation {
+                        judgment: "5 evalto 5".to_string(),
+                        rule: "E-Int".to_string(),
+                        premises: vec![],
+                        line: 3,
+                    },
+                    Derivation {
+                        judgment: "3 plus 5 is 8".to_string(),
+                        rule: "B-Plus".to_string(),
+                        premises: vec![],
+                        line: 4,
+                    },
+                ],
+                line: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse_judgment("x = 3, y = 2 |- x evalto 3").unwrap().1,
+            Judgment::EvalTo(
+                Env::from(vec![
+                    (Var("x".to_string()), Int(3)),
+                    (Var("y".to_string()), Int(2)),
+                ]),
+                Var("x".to_string()),
+                Int(3)
+            )
+        );
+        assert_eq!(
+            parse_judgment("-23 is less than -16").unwrap().1,
+            Judgment::Lt(-23, -16, true)
+        );
+        assert_eq!(
+            parse_judgment("5 less than 3 is false").unwrap().1,
+            Judgment::Lt(5, 3, false)
+        );
+        assert!(parse_judgment("3 plus 5 is 8 9").is_err());
+    }
+}
@@ -0,0 +1,419 @@
+use crate::{
+    checker::{
+        ast::{Derivation, Judgment},
+        parser::{parse, parse_judgment},
+    },
+    eval_ml2::ast::{Env, Expr, Expr::*},
+    system::System,
+};
+use std::fmt;
+
+const EVAL_ML1_RULES: [&str; 12] = [
+    "E-Int", "E-Bool", "E-IfT", "E-IfF", "E-Plus", "E-Minus", "E-Times", "E-Lt", "B-Plus",
+    "B-Minus", "B-Times", "B-Lt",
+];
+
+const EVAL_ML2_RULES: [&str; 3] = ["E-Var1", "E-Var2", "E-Let"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CheckError {
+    Unsupported(System),
+    Syntax(usize),
+    Invalid(usize, String, String, String),
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckError::Unsupported(system) => write!(f, "{} is not supported", system),
+            CheckError::Syntax(line) => write!(f, "line {}: syntax error", line),
+            CheckError::Invalid(line, judgment, rule, reason) => {
+                write!(f, "line {}: {} by {}: {}", line, judgment, rule, reason)
+            }
+        }
+    }
+}
+
+pub fn check(system: System, input: &str) -> Result<(), CheckError> {
+    if !matches!(system, System::EvalML1 | System::EvalML2) {
+        return Err(CheckError::Unsupported(system));
+    }
+    match parse(input) {
+        Ok((rest, derivation)) if rest.trim().is_empty() => verify(system, &derivation),
+        Ok((rest, _)) => Err(CheckError::Syntax(line_of(input, rest))),
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+            Err(CheckError::Syntax(line_of(input, e.input)))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(CheckError::Syntax(1)),
+    }
+}
+
+fn line_of(input: &str, rest: &str) -> usize {
+    let offset = input.len() - rest.len();
+    input[..offset].matches('\n').count() + 1
+}
+
+// 各ノードの前提と付帯条件を調べる (最初に見つかった誤りを返す)
+pub fn verify(system: System, derivation: &Derivation) -> Result<(), CheckError> {
+    let invalid = |derivation: &Derivation, reason: String| {
+        CheckError::Invalid(
+            derivation.line,
+            derivation.judgment.clone(),
+            derivation.rule.clone(),
+            reason,
+        )
+    };
+    let judgment = judgment_of(system, derivation).map_err(|e| invalid(derivation, e))?;
+    let premises = derivation
+        .premises
+        .iter()
+        .map(|premise| judgment_of(system, premise).map_err(|e| invalid(premise, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+    check_rule(system, &derivation.rule, &judgment, &premises)
+        .map_err(|e| invalid(derivation, e))?;
+    derivation
+        .premises
+        .iter()
+        .try_for_each(|premise| verify(system, premise))
+}
+
+fn judgment_of(system: System, derivation: &Derivation) -> Result<Judgment, String> {
+    if system == System::EvalML1 && derivation.judgment.contains("|-") {
+        return Err("EvalML1 judgments have no environment".to_string());
+    }
+    match parse_judgment(&derivation.judgment) {
+        Ok((_, judgment)) => Ok(judgment),
+        Err(_) => Err("cannot parse judgment".to_string()),
+    }
+}
+
+fn check_rule(
+    system: System,
+    rule: &str,
+    judgment: &Judgment,
+    premises: &[Judgment],
+) -> Result<(), String> {
+    let known = EVAL_ML1_RULES.contains(&rule)
+        || (system == System::EvalML2 && EVAL_ML2_RULES.contains(&rule));
+    if !known {
+        return Err(format!("unknown rule for {}", system));
+    }
+    match (rule, judgment) {
+        ("E-Int", Judgment::EvalTo(_, Int(i1), Int(i2))) => {
+            arity(premises, 0)?;
+            expect(i1 == i2, || format!("{} does not evaluate to {}", i1, i2))
+        }
+        ("E-Bool", Judgment::EvalTo(_, Bool(b1), Bool(b2))) => {
+            arity(premises, 0)?;
+            expect(b1 == b2, || format!("{} does not evaluate to {}", b1, b2))
+        }
+        ("E-Var1", Judgment::EvalTo(env, Var(_), expr)) => {
+            arity(premises, 0)?;
+            expect(env != &Env::new(), || "environment is empty".to_string())?;
+            let (expr1, expr2) = env.last();
+            expect(&expr1 == judgment_expr(judgment), || {
+                format!("last variable is {}", expr1)
+            })?;
+            expect(&expr2 == expr, || {
+                format!("{} is bound to {}", expr1, expr2)
+            })
+        }
+        ("E-Var2", Judgment::EvalTo(env, Var(_), expr)) => {
+            arity(premises, 1)?;
+            expect(env != &Env::new(), || "environment is empty".to_string())?;
+            let (expr1, _) = env.last();
+            expect(&expr1 != judgment_expr(judgment), || {
+                format!("last variable is {}; use E-Var1", expr1)
+            })?;
+            let expected =
+                Judgment::EvalTo(env.butlast(), judgment_expr(judgment).clone(), expr.clone());
+            premise(premises, 0, &expected)
+        }
+        ("E-IfT", Judgment::EvalTo(env, If(expr1, expr2, _), expr)) => {
+            arity(premises, 2)?;
+            premise(
+                premises,
+                0,
+                &Judgment::EvalTo(env.clone(), *expr1.clone(), Bool(true)),
+            )?;
+            premise(
+                premises,
+                1,
+                &Judgment::EvalTo(env.clone(), *expr2.clone(), expr.clone()),
+            )
+        }
+        ("E-IfF", Judgment::EvalTo(env, If(expr1, _, expr3), expr)) => {
+            arity(premises, 2)?;
+            premise(
+                premises,
+                0,
+                &Judgment::EvalTo(env.clone(), *expr1.clone(), Bool(false)),
+            )?;
+            premise(
+                premises,
+                1,
+                &Judgment::EvalTo(env.clone(), *expr3.clone(), expr.clone()),
+            )
+        }
+        ("E-Plus", Judgment::EvalTo(env, Plus(expr1, expr2), Int(i3))) => {
+            arity(premises, 3)?;
+            let i1 = evaluated_int(premises, 0, env, expr1)?;
+            let i2 = evaluated_int(premises, 1, env, expr2)?;
+            premise(premises, 2, &Judgment::Plus(i1, i2, *i3))
+        }
+        ("E-Minus", Judgment::EvalTo(env, Minus(expr1, expr2), Int(i3))) => {
+            arity(premises, 3)?;
+            let i1 = evaluated_int(premises, 0, env, expr1)?;
+            let i2 = evaluated_int(premises, 1, env, expr2)?;
+            premise(premises, 2, &Judgment::Minus(i1, i2, *i3))
+        }
+        ("E-Times", Judgment::EvalTo(env, Times(expr1, expr2), Int(i3))) => {
+            arity(premises, 3)?;
+            let i1 = evaluated_int(premises, 0, env, expr1)?;
+            let i2 = evaluated_int(premises, 1, env, expr2)?;
+            premise(premises, 2, &Judgment::Times(i1, i2, *i3))
+        }
+        ("E-Lt", Judgment::EvalTo(env, Lt(expr1, expr2), Bool(b))) => {
+            arity(premises, 3)?;
+            let i1 = evaluated_int(premises, 0, env, expr1)?;
+            let i2 = evaluated_int(premises, 1, env, expr2)?;
+            premise(premises, 2, &Judgment::Lt(i1, i2, *b))
+        }
+        ("E-Let", Judgment::EvalTo(env, Let(expr1, expr2, expr3), expr)) => {
+            arity(premises, 2)?;
+            let expr4 = evaluated(premises, 0, env, expr2)?;
+            let mut new_env = env.clone();
+            new_env.put(*expr1.clone(), expr4);
+            premise(
+                premises,
+                1,
+                &Judgment::EvalTo(new_env, *expr3.clone(), expr.clone()),
+            )
+        }
+        ("B-Plus", Judgment::Plus(i1, i2, i3)) => {
+            arity(premises, 0)?;
+            arith(i1.checked_add(*i2), *i3, || format!("{} + {}", i1, i2))
+        }
+        ("B-Minus", Judgment::Minus(i1, i2, i3)) => {
+            arity(premises, 0)?;
+            arith(i1.checked_sub(*i2), *i3, || format!("{} - {}", i1, i2))
+        }
+        ("B-Times", Judgment::Times(i1, i2, i3)) => {
+            arity(premises, 0)?;
+            arith(i1.checked_mul(*i2), *i3, || format!("{} * {}", i1, i2))
+        }
+        ("B-Lt", Judgment::Lt(i1, i2, b)) => {
+            arity(premises, 0)?;
+            expect((i1 < i2) == *b, || format!("{} < {} is not {}", i1, i2, b))
+        }
+        _ => Err(format!("conclusion does not match {}", rule)),
+    }
+}
+
+fn judgment_expr(judgment: &Judgment) -> &Expr {
+    match judgment {
+        Judgment::EvalTo(_, expr, _) => expr,
+        _ => unreachable!(),
+    }
+}
+
+fn expect<F: Fn() -> String>(b: bool, reason: F) -> Result<(), String> {
+    if b {
+        Ok(())
+    } else {
+        Err(reason())
+    }
+}
+
+// i64 からあふれる計算は正しい導出にならない
+fn arith<F: Fn() -> String>(i: Option<i64>, expected: i64, expr: F) -> Result<(), String> {
+    match i {
+        Some(i) => expect(i == expected, || format!("{} is not {}", expr(), expected)),
+        None => Err(format!("{} overflows", expr())),
+    }
+}
+
+fn arity(premises: &[Judgment], n: usize) -> Result<(), String> {
+    expect(premises.len() == n, || {
+        format!("expected {} premises, found {}", n, premises.len())
+    })
+}
+
+fn premise(premises: &[Judgment], i: usize, expected: &Judgment) -> Result<(), String> {
+    expect(&premises[i] == expected, || {
+        format!("premise {} should be `{}`", i + 1, expected)
+    })
+}
+
+// 前提 i が env |- expr evalto v の形なら v を返す
+fn evaluated(premises: &[Judgment], i: usize, env: &Env, expr: &Expr) -> Result<Expr, String> {
+    match &premises[i] {
+        Judgment::EvalTo(env1, expr1, expr2) if env1 == env && expr1 == expr => Ok(expr2.clone()),
+        _ => {
+            let expected = Judgment::EvalTo(env.clone(), expr.clone(), Var("_".to_string()));
+            Err(format!("premise {} should be `{}`", i + 1, expected))
+        }
+    }
+}
+
+fn evaluated_int(premises: &[Judgment], i: usize, env: &Env, expr: &Expr) -> Result<i64, String> {
+    match evaluated(premises, i, env, expr)? {
+        Int(i1) => Ok(i1),
+        expr1 => Err(format!(
+            "premise {} evaluates to {}, not an integer",
+            i + 1,
+            expr1
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        checker::verifier::{check, CheckError},
+        system::System,
+    };
+
+    #[test]
+    fn test_check1() {
+        for input in [
+            include_str!("../../../derivation/Q025"),
+            include_str!("../../../derivation/Q026"),
+            include_str!("../../../derivation/Q027"),
+            include_str!("../../../derivation/Q028"),
+            include_str!("../../../derivation/Q029"),
+            include_str!("../../../derivation/Q030"),
+        ] {
+            assert_eq!(check(System::EvalML1, input), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_check2() {
+        for input in [
+            include_str!("../../../derivation/Q034"),
+            include_str!("../../../derivation/Q035"),
+            include_str!("../../../derivation/Q036"),
+            include_str!("../../../derivation/Q037"),
+            include_str!("../../../derivation/Q038"),
+            include_str!("../../../derivation/Q039"),
+        ] {
+            assert_eq!(check(System::EvalML2, input), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_check3() {
+        let input = "3 + 5 evalto 9 by E-Plus {
+    3 evalto 3 by E-Int {};
+    5 evalto 5 by E-Int {};
+    3 plus 5 is 9 by B-Plus {}
+}";
+        assert_eq!(
+            check(System::EvalML1, input).unwrap_err().to_string(),
+            "line 4: 3 plus 5 is 9 by B-Plus: 3 + 5 is not 9"
+        );
+    }
+
+    #[test]
+    fn test_check_overflow() {
+        assert_eq!(
+            check(
+                System::EvalML1,
+                "9223372036854775807 plus 1 is 0 by B-Plus {}"
+            )
+            .unwrap_err()
+            .to_string(),
+            "line 1: 9223372036854775807 plus 1 is 0 by B-Plus: 9223372036854775807 + 1 overflows"
+        );
+        assert_eq!(
+            check(
+                System::EvalML1,
+                "4611686018427387904 times 2 is -9223372036854775808 by B-Times {}"
+            )
+            .unwrap_err()
+            .to_string(),
+            "line 1: 4611686018427387904 times 2 is -9223372036854775808 by B-Times: 4611686018427387904 * 2 overflows"
+        );
+    }
+
+    #[test]
+    fn test_check_one_line() {
+        assert_eq!(
+            check(
+                System::EvalML1,
+                "1 + 2 evalto 3 by E-Plus { 1 evalto 1 by E-Int {}; 2 evalto 2 by E-Int {}; 1 plus 2 is 3 by B-Plus {} }"
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_check_large_int() {
+        assert_eq!(
+            check(
+                System::EvalML1,
+                "99999999999999999999 plus 1 is 0 by B-Plus {}"
+            )
+            .unwrap_err()
+            .to_string(),
+            "line 1: 99999999999999999999 plus 1 is 0 by B-Plus: cannot parse judgment"
+        );
+    }
+
+    #[test]
+    fn test_check4() {
+        let input = "if 4 < 5 then 2 else 3 evalto 2 by E-IfT {
+    4 < 5 evalto true by E-Lt {
+        4 evalto 4 by E-Int {};
+        5 evalto 5 by E-Int {};
+        4 is less than 5 by B-Lt {}
+    };
+    3 evalto 3 by E-Int {}
+}";
+        assert_eq!(
+            check(System::EvalML1, input).unwrap_err().to_string(),
+            "line 1: if 4 < 5 then 2 else 3 evalto 2 by E-IfT: premise 2 should be `2 evalto 2`"
+        );
+    }
+
+    #[test]
+    fn test_check5() {
+        assert_eq!(
+            check(System::EvalML1, "|- let x = 1 in x evalto 1 by E-Let {}"),
+            Err(CheckError::Invalid(
+                1,
+                "|- let x = 1 in x evalto 1".to_string(),
+                "E-Let".to_string(),
+                "EvalML1 judgments have no environment".to_string()
+            ))
+        );
+        assert_eq!(
+            check(System::EvalML2, "x = 1 |- x evalto 1 by E-Var {}")
+                .unwrap_err()
+                .to_string(),
+            "line 1: x = 1 |- x evalto 1 by E-Var: unknown rule for EvalML2"
+        );
+        assert_eq!(
+            check(System::EvalML2, "x = 1, y = 2 |- x evalto 1 by E-Var1 {}")
+                .unwrap_err()
+                .to_string(),
+            "line 1: x = 1, y = 2 |- x evalto 1 by E-Var1: last variable is y"
+        );
+    }
+
+    #[test]
+    fn test_check6() {
+        assert_eq!(
+            check(
+                System::EvalML1,
+                "3 evalto 3 by E-Int {\n    3 evalto 3 by E-Int {}\n"
+            ),
+            Err(CheckError::Syntax(3))
+        );
+        assert_eq!(
+            check(System::EvalML3, "|- 3 evalto 3 by E-Int {}"),
+            Err(CheckError::Unsupported(System::EvalML3))
+        );
+    }
+}
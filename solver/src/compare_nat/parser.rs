@@ -0,0 +1,39 @@
+use crate::{
+    nat::{ast::Nat, parser::parse_nat},
+    util::ws,
+};
+use nom::{bytes::complete::tag, sequence::tuple, IResult};
+
+// <judgement> ::= <nat> 'is' 'less' 'than' <nat>
+
+pub fn parse(input: &str) -> IResult<&str, (Nat, Nat)> {
+    let (input, (nat1, _, _, _, nat2)) = tuple((
+        parse_nat,
+        ws(tag("is")),
+        ws(tag("less")),
+        ws(tag("than")),
+        parse_nat,
+    ))(input)?;
+    Ok((input, (nat1, nat2)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{compare_nat::parser::parse, nat::ast::Nat::*};
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(parse("Z is less than S(Z)").unwrap().1, (Z, S(Box::new(Z))));
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse("S(S(Z)) is less than S(S(S(Z)))").unwrap().1,
+            (
+                S(Box::new(S(Box::new(Z)))),
+                S(Box::new(S(Box::new(S(Box::new(Z))))))
+            )
+        );
+    }
+}
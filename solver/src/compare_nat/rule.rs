@@ -0,0 +1,73 @@
+use crate::{nat::ast::Nat, util::ident};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum System {
+    CompareNat1,
+    CompareNat2,
+    CompareNat3,
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    LSucc(Nat, usize),
+    LTrans(Nat, Nat, Box<Rule>, Box<Rule>, usize),
+    LZero(Nat, usize),
+    LSuccSucc(Nat, Nat, Box<Rule>, usize),
+    LSuccR(Nat, Nat, Box<Rule>, usize),
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::compare_nat::rule::Rule::*;
+
+        match self {
+            LSucc(nat, depth) => {
+                write!(
+                    f,
+                    "{}{} is less than S({}) by L-Succ {{}}",
+                    ident(*depth),
+                    nat,
+                    nat
+                )
+            }
+            LTrans(nat1, nat2, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{} is less than {} by L-Trans {{",
+                    ident(*depth),
+                    nat1,
+                    nat2
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            LZero(nat, depth) => {
+                write!(f, "{}Z is less than {} by L-Zero {{}}", ident(*depth), nat)
+            }
+            LSuccSucc(nat1, nat2, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} is less than {} by L-SuccSucc {{",
+                    ident(*depth),
+                    nat1,
+                    nat2
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            LSuccR(nat1, nat2, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} is less than {} by L-SuccR {{",
+                    ident(*depth),
+                    nat1,
+                    nat2
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+        }
+    }
+}
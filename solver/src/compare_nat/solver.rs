@@ -0,0 +1,170 @@
+use crate::{
+    compare_nat::rule::{Rule, Rule::*, System, System::*},
+    nat::ast::{Nat, Nat::*},
+};
+
+pub fn solve(system: System, nat1: &Nat, nat2: &Nat, depth: usize) -> Option<Rule> {
+    match system {
+        CompareNat1 => solve1(nat1, nat2, depth),
+        CompareNat2 => solve2(nat1, nat2, depth),
+        CompareNat3 => solve3(nat1, nat2, depth),
+    }
+}
+
+fn solve1(nat1: &Nat, nat2: &Nat, depth: usize) -> Option<Rule> {
+    match nat2 {
+        Z => None,
+        S(nat) if **nat == *nat1 => Some(LSucc(nat1.clone(), depth)),
+        S(nat) => {
+            let rule1 = solve1(nat1, nat, depth + 1)?;
+            let rule2 = LSucc(*nat.clone(), depth + 1);
+            Some(LTrans(
+                nat1.clone(),
+                nat2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            ))
+        }
+    }
+}
+
+fn solve2(nat1: &Nat, nat2: &Nat, depth: usize) -> Option<Rule> {
+    match (nat1, nat2) {
+        (Z, S(_)) => Some(LZero(nat2.clone(), depth)),
+        (S(n1), S(n2)) => {
+            let rule = solve2(n1, n2, depth + 1)?;
+            Some(LSuccSucc(nat1.clone(), nat2.clone(), Box::new(rule), depth))
+        }
+        _ => None,
+    }
+}
+
+fn solve3(nat1: &Nat, nat2: &Nat, depth: usize) -> Option<Rule> {
+    match nat2 {
+        Z => None,
+        S(nat) if **nat == *nat1 => Some(LSucc(nat1.clone(), depth)),
+        S(nat) => {
+            let rule = solve3(nat1, nat, depth + 1)?;
+            Some(LSuccR(nat1.clone(), nat2.clone(), Box::new(rule), depth))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        compare_nat::{
+            rule::{Rule::*, System::*},
+            solver::solve,
+        },
+        nat::ast::Nat::*,
+    };
+
+    #[test]
+    fn test_solve1() {
+        assert_eq!(
+            solve(
+                CompareNat1,
+                &S(Box::new(S(Box::new(Z)))),
+                &S(Box::new(S(Box::new(S(Box::new(Z)))))),
+                0
+            ),
+            Some(LSucc(S(Box::new(S(Box::new(Z)))), 0))
+        );
+    }
+
+    #[test]
+    fn test_solve2() {
+        assert_eq!(
+            solve(
+                CompareNat1,
+                &S(Box::new(Z)),
+                &S(Box::new(S(Box::new(S(Box::new(Z)))))),
+                0
+            ),
+            Some(LTrans(
+                S(Box::new(Z)),
+                S(Box::new(S(Box::new(S(Box::new(Z)))))),
+                Box::new(LSucc(S(Box::new(Z)), 1)),
+                Box::new(LSucc(S(Box::new(S(Box::new(Z)))), 1)),
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve3() {
+        assert_eq!(
+            solve(
+                CompareNat2,
+                &S(Box::new(Z)),
+                &S(Box::new(S(Box::new(Z)))),
+                0
+            ),
+            Some(LSuccSucc(
+                S(Box::new(Z)),
+                S(Box::new(S(Box::new(Z)))),
+                Box::new(LZero(S(Box::new(Z)), 1)),
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve4() {
+        assert_eq!(
+            solve(CompareNat3, &Z, &S(Box::new(S(Box::new(Z)))), 0),
+            Some(LSuccR(
+                Z,
+                S(Box::new(S(Box::new(Z)))),
+                Box::new(LSucc(Z, 1)),
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve5() {
+        assert_eq!(
+            solve(CompareNat1, &S(Box::new(Z)), &S(Box::new(Z)), 0),
+            None
+        );
+        assert_eq!(solve(CompareNat2, &S(Box::new(Z)), &Z, 0), None);
+        assert_eq!(solve(CompareNat3, &S(Box::new(Z)), &Z, 0), None);
+    }
+
+    #[test]
+    fn test_solve6() {
+        let nat1 = S(Box::new(S(Box::new(Z))));
+        let nat2 = S(Box::new(S(Box::new(S(Box::new(S(Box::new(S(
+            Box::new(Z),
+        )))))))));
+        assert_eq!(
+            solve(CompareNat1, &nat1, &nat2, 0).unwrap().to_string(),
+            "S(S(Z)) is less than S(S(S(S(S(Z))))) by L-Trans {
+    S(S(Z)) is less than S(S(S(S(Z)))) by L-Trans {
+        S(S(Z)) is less than S(S(S(Z))) by L-Succ {};
+        S(S(S(Z))) is less than S(S(S(S(Z)))) by L-Succ {}
+    };
+    S(S(S(S(Z)))) is less than S(S(S(S(S(Z))))) by L-Succ {}
+}"
+        );
+        assert_eq!(
+            solve(CompareNat2, &nat1, &nat2, 0).unwrap().to_string(),
+            "S(S(Z)) is less than S(S(S(S(S(Z))))) by L-SuccSucc {
+    S(Z) is less than S(S(S(S(Z)))) by L-SuccSucc {
+        Z is less than S(S(S(Z))) by L-Zero {}
+    }
+}"
+        );
+        assert_eq!(
+            solve(CompareNat3, &nat1, &nat2, 0).unwrap().to_string(),
+            "S(S(Z)) is less than S(S(S(S(S(Z))))) by L-SuccR {
+    S(S(Z)) is less than S(S(S(S(Z)))) by L-SuccR {
+        S(S(Z)) is less than S(S(S(Z))) by L-Succ {}
+    }
+}"
+        );
+    }
+}
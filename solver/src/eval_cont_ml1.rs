@@ -0,0 +1,4 @@
+pub mod ast;
+pub mod parser;
+pub mod rule;
+pub mod solver;
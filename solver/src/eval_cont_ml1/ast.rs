@@ -0,0 +1,119 @@
+use crate::eval_ml1::ast::Expr;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Op {
+    Plus,
+    Minus,
+    Times,
+    Lt,
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_cont_ml1::ast::Op::*;
+
+        match self {
+            Plus => write!(f, "+"),
+            Minus => write!(f, "-"),
+            Times => write!(f, "*"),
+            Lt => write!(f, "<"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Frame {
+    // {_ op e}
+    EvalR(Op, Expr),
+    // {v op _}
+    BinOp(Expr, Op),
+    // {if _ then e else e}
+    If(Expr, Expr),
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_cont_ml1::ast::Frame::*;
+
+        let precedence = |op: &Op| match op {
+            Op::Plus | Op::Minus => 3,
+            Op::Times => 4,
+            Op::Lt => 1,
+        };
+        match self {
+            EvalR(op, expr) => {
+                let paren = expr.precedence() <= precedence(op) && !expr.is_open();
+                write!(f, "{{_ {} {}}}", op, expr.paren(paren))
+            }
+            BinOp(expr, op) => write!(f, "{{{} {} _}}", expr, op),
+            If(expr1, expr2) => write!(f, "{{if _ then {} else {}}}", expr1, expr2),
+        }
+    }
+}
+
+// 末尾が先頭のフレーム
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Cont(Vec<Frame>);
+
+impl Cont {
+    pub fn new() -> Cont {
+        Cont(vec![])
+    }
+
+    pub fn from(frames: Vec<Frame>) -> Cont {
+        Cont(frames.into_iter().rev().collect())
+    }
+
+    pub fn push(&mut self, frame: Frame) {
+        self.0.push(frame)
+    }
+
+    pub fn pop(&mut self) -> Option<Frame> {
+        self.0.pop()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for Cont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "_");
+        }
+        let frames = self
+            .0
+            .iter()
+            .rev()
+            .map(|frame| frame.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}", frames.join(" >> "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eval_cont_ml1::ast::{Cont, Frame, Op},
+        eval_ml1::ast::Expr::*,
+    };
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Cont::new().to_string(), "_");
+        assert_eq!(
+            Cont::from(vec![
+                Frame::EvalR(Op::Plus, Plus(Box::new(Int(1)), Box::new(Int(2)))),
+                Frame::If(Int(3), Int(4)),
+            ])
+            .to_string(),
+            "{_ + (1 + 2)} >> {if _ then 3 else 4}"
+        );
+        assert_eq!(
+            Cont::from(vec![Frame::BinOp(Int(5), Op::Lt)]).to_string(),
+            "{5 < _}"
+        );
+    }
+}
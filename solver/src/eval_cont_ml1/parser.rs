@@ -0,0 +1,140 @@
+use crate::{
+    eval_cont_ml1::ast::{Cont, Frame, Op},
+    eval_ml1::{
+        ast::Expr,
+        parser::{parse_expr, parse_value},
+    },
+    syntax::error::IResult,
+    util::ws,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::opt,
+    multi::many0,
+    sequence::{delimited, tuple},
+};
+
+// <judgment> ::= <expr> [ '>>' <cont> ]
+// <cont> ::= '_' | <frame> [ '>>' <frame> ]* [ '>>' '_' ]
+// <frame> ::= '{' ( <eval_r> | <bin_op> | <if> ) '}'
+// <eval_r> ::= '_' <op> <expr>
+// <bin_op> ::= <value> <op> '_'
+// <if> ::= 'if' '_' 'then' <expr> 'else' <expr>
+// <op> ::= '+' | '-' | '*' | '<'
+// <expr> ::= EvalML1 の式
+// <value> ::= <int> | <bool>
+
+pub fn parse(input: &str) -> IResult<&str, (Expr, Cont)> {
+    let (input, (expr, cont)) = tuple((parse_expr, opt(parse_then_cont)))(input)?;
+    Ok((input, (expr, cont.unwrap_or_default())))
+}
+
+fn parse_then_cont(input: &str) -> IResult<&str, Cont> {
+    let (input, (_, cont)) = tuple((ws(tag(">>")), parse_cont))(input)?;
+    Ok((input, cont))
+}
+
+fn parse_cont(input: &str) -> IResult<&str, Cont> {
+    alt((parse_hole, parse_frames))(input)
+}
+
+fn parse_hole(input: &str) -> IResult<&str, Cont> {
+    let (input, _) = ws(char('_'))(input)?;
+    Ok((input, Cont::new()))
+}
+
+fn parse_frames(input: &str) -> IResult<&str, Cont> {
+    let (input, (frame, frames, _)) = tuple((
+        parse_frame,
+        many0(tuple((ws(tag(">>")), parse_frame))),
+        opt(tuple((ws(tag(">>")), ws(char('_'))))),
+    ))(input)?;
+    let mut cont = vec![frame];
+    cont.extend(frames.into_iter().map(|(_, frame)| frame));
+    Ok((input, Cont::from(cont)))
+}
+
+fn parse_frame(input: &str) -> IResult<&str, Frame> {
+    let parse_lbrace = ws(char('{'));
+    let parse_rbrace = ws(char('}'));
+    let parse_body = alt((parse_eval_r, parse_bin_op, parse_if));
+    let (input, frame) = delimited(parse_lbrace, parse_body, parse_rbrace)(input)?;
+    Ok((input, frame))
+}
+
+fn parse_eval_r(input: &str) -> IResult<&str, Frame> {
+    let (input, (_, op, expr)) = tuple((ws(char('_')), parse_op, parse_expr))(input)?;
+    Ok((input, Frame::EvalR(op, expr)))
+}
+
+fn parse_bin_op(input: &str) -> IResult<&str, Frame> {
+    let (input, (expr, op, _)) = tuple((parse_value, parse_op, ws(char('_'))))(input)?;
+    Ok((input, Frame::BinOp(expr, op)))
+}
+
+fn parse_if(input: &str) -> IResult<&str, Frame> {
+    let (input, (_, _, _, expr1, _, expr2)) = tuple((
+        ws(tag("if")),
+        ws(char('_')),
+        ws(tag("then")),
+        parse_expr,
+        ws(tag("else")),
+        parse_expr,
+    ))(input)?;
+    Ok((input, Frame::If(expr1, expr2)))
+}
+
+fn parse_op(input: &str) -> IResult<&str, Op> {
+    let (input, op) = alt((ws(char('+')), ws(char('-')), ws(char('*')), ws(char('<'))))(input)?;
+    let op = match op {
+        '+' => Op::Plus,
+        '-' => Op::Minus,
+        '*' => Op::Times,
+        '<' => Op::Lt,
+        _ => unreachable!(),
+    };
+    Ok((input, op))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eval_cont_ml1::{
+            ast::{Cont, Frame, Op},
+            parser::parse,
+        },
+        eval_ml1::ast::Expr::*,
+    };
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(
+            parse("3 + 5").unwrap().1,
+            (Plus(Box::new(Int(3)), Box::new(Int(5))), Cont::new())
+        );
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse("5 >> {3 + _} >> {_ * (1 + 2)} >> _").unwrap().1,
+            (
+                Int(5),
+                Cont::from(vec![
+                    Frame::BinOp(Int(3), Op::Plus),
+                    Frame::EvalR(Op::Times, Plus(Box::new(Int(1)), Box::new(Int(2)))),
+                ])
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse3() {
+        assert_eq!(
+            parse("true >> {if _ then 1 else -1}").unwrap().1,
+            (Bool(true), Cont::from(vec![Frame::If(Int(1), Int(-1))]))
+        );
+    }
+}
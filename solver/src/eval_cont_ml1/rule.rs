@@ -0,0 +1,256 @@
+use crate::{
+    eval_cont_ml1::ast::{Cont, Op},
+    eval_ml1::ast::Expr,
+    util::ident,
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    EInt(i64, Cont, Box<Rule>, usize),
+    EBool(bool, Cont, Box<Rule>, usize),
+    EBinOp(Expr, Op, Expr, Cont, Box<Rule>, usize),
+    EIf(Expr, Expr, Expr, Cont, Box<Rule>, usize),
+    CRet(Expr, usize),
+    CEvalR(Expr, Cont, Box<Rule>, usize),
+    CPlus(Expr, Cont, Box<Rule>, Box<Rule>, usize),
+    CMinus(Expr, Cont, Box<Rule>, Box<Rule>, usize),
+    CTimes(Expr, Cont, Box<Rule>, Box<Rule>, usize),
+    CLt(Expr, Cont, Box<Rule>, Box<Rule>, usize),
+    CIfT(Cont, Box<Rule>, usize),
+    CIfF(Cont, Box<Rule>, usize),
+    BPlus(Expr, Expr, Expr, usize),
+    BMinus(Expr, Expr, Expr, usize),
+    BTimes(Expr, Expr, Expr, usize),
+    BLt(Expr, Expr, Expr, usize),
+}
+
+impl Rule {
+    pub fn evaluated(&self) -> Expr {
+        use crate::eval_cont_ml1::rule::Rule::*;
+
+        match self {
+            EInt(_, _, rule, _) => rule.evaluated(),
+            EBool(_, _, rule, _) => rule.evaluated(),
+            EBinOp(_, _, _, _, rule, _) => rule.evaluated(),
+            EIf(_, _, _, _, rule, _) => rule.evaluated(),
+            CRet(expr, _) => expr.clone(),
+            CEvalR(_, _, rule, _) => rule.evaluated(),
+            CPlus(_, _, _, rule2, _) => rule2.evaluated(),
+            CMinus(_, _, _, rule2, _) => rule2.evaluated(),
+            CTimes(_, _, _, rule2, _) => rule2.evaluated(),
+            CLt(_, _, _, rule2, _) => rule2.evaluated(),
+            CIfT(_, rule, _) => rule.evaluated(),
+            CIfF(_, rule, _) => rule.evaluated(),
+            BPlus(_, _, expr3, _) => expr3.clone(),
+            BMinus(_, _, expr3, _) => expr3.clone(),
+            BTimes(_, _, expr3, _) => expr3.clone(),
+            BLt(_, _, expr3, _) => expr3.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_cont_ml1::rule::Rule::*;
+
+        match self {
+            EInt(i, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}{} evalto {} by E-Int {{",
+                    ident(*depth),
+                    i,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EBool(b, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}{} evalto {} by E-Bool {{",
+                    ident(*depth),
+                    b,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EBinOp(expr1, op, expr2, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} {} {}{} evalto {} by E-BinOp {{",
+                    ident(*depth),
+                    expr1,
+                    op,
+                    expr2,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EIf(expr1, expr2, expr3, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}if {} then {} else {}{} evalto {} by E-If {{",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CRet(expr, depth) => {
+                write!(
+                    f,
+                    "{}{} => _ evalto {} by C-Ret {{}}",
+                    ident(*depth),
+                    expr,
+                    expr
+                )
+            }
+            CEvalR(expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-EvalR {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CPlus(expr, cont, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-Plus {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CMinus(expr, cont, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-Minus {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CTimes(expr, cont, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-Times {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CLt(expr, cont, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-Lt {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CIfT(cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}true => {} evalto {} by C-IfT {{",
+                    ident(*depth),
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CIfF(cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}false => {} evalto {} by C-IfF {{",
+                    ident(*depth),
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            BPlus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} plus {} is {} by B-Plus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BMinus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} minus {} is {} by B-Minus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BTimes(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} times {} is {} by B-Times {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BLt(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} less than {} is {} by B-Lt {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+        }
+    }
+}
+
+// 継続が _ のときは省略する
+fn then(cont: &Cont) -> String {
+    if cont.is_empty() {
+        String::new()
+    } else {
+        format!(" >> {}", cont)
+    }
+}
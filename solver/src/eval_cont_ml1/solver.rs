@@ -0,0 +1,228 @@
+use crate::{
+    eval_cont_ml1::{
+        ast::{Cont, Frame, Op},
+        rule::{Rule, Rule::*},
+    },
+    eval_error::{check_depth, checked, EvalError},
+    eval_ml1::ast::{Expr, Expr::*},
+};
+
+// e >> k evalto v
+pub fn solve(expr: &Expr, cont: &Cont, depth: usize) -> Result<Rule, EvalError> {
+    check_depth(depth)?;
+    let rule = match expr {
+        Int(i) => {
+            let rule = solve_cont(expr, cont, depth + 1)?;
+            EInt(*i, cont.clone(), Box::new(rule), depth)
+        }
+        Bool(b) => {
+            let rule = solve_cont(expr, cont, depth + 1)?;
+            EBool(*b, cont.clone(), Box::new(rule), depth)
+        }
+        Plus(expr1, expr2) => solve_bin_op(expr1, Op::Plus, expr2, cont, depth)?,
+        Minus(expr1, expr2) => solve_bin_op(expr1, Op::Minus, expr2, cont, depth)?,
+        Times(expr1, expr2) => solve_bin_op(expr1, Op::Times, expr2, cont, depth)?,
+        Lt(expr1, expr2) => solve_bin_op(expr1, Op::Lt, expr2, cont, depth)?,
+        If(expr1, expr2, expr3) => {
+            let mut new_cont = cont.clone();
+            new_cont.push(Frame::If(*expr2.clone(), *expr3.clone()));
+            let rule = solve(expr1, &new_cont, depth + 1)?;
+            EIf(
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                cont.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+    };
+    Ok(rule)
+}
+
+fn solve_bin_op(
+    expr1: &Expr,
+    op: Op,
+    expr2: &Expr,
+    cont: &Cont,
+    depth: usize,
+) -> Result<Rule, EvalError> {
+    let mut new_cont = cont.clone();
+    new_cont.push(Frame::EvalR(op.clone(), expr2.clone()));
+    let rule = solve(expr1, &new_cont, depth + 1)?;
+    Ok(EBinOp(
+        expr1.clone(),
+        op,
+        expr2.clone(),
+        cont.clone(),
+        Box::new(rule),
+        depth,
+    ))
+}
+
+// v => k evalto v
+pub fn solve_cont(expr: &Expr, cont: &Cont, depth: usize) -> Result<Rule, EvalError> {
+    check_depth(depth)?;
+    let mut new_cont = cont.clone();
+    let rule = match new_cont.pop() {
+        None => CRet(expr.clone(), depth),
+        Some(Frame::EvalR(op, expr1)) => {
+            new_cont.push(Frame::BinOp(expr.clone(), op));
+            let rule = solve(&expr1, &new_cont, depth + 1)?;
+            CEvalR(expr.clone(), cont.clone(), Box::new(rule), depth)
+        }
+        Some(Frame::BinOp(expr1, op)) => {
+            let (i1, i2) = match (&expr1, expr) {
+                (Int(i1), Int(i2)) => (*i1, *i2),
+                _ => return Err(EvalError::Stuck(format!("{} => {}", expr, cont))),
+            };
+            let expr2 = format!("{} {} {}", expr1, op, expr);
+            match op {
+                Op::Plus => {
+                    let expr3 = Int(checked(&expr2, i1.checked_add(i2))?);
+                    let rule1 = BPlus(expr1, expr.clone(), expr3.clone(), depth + 1);
+                    let rule2 = solve_cont(&expr3, &new_cont, depth + 1)?;
+                    CPlus(
+                        expr.clone(),
+                        cont.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+                Op::Minus => {
+                    let expr3 = Int(checked(&expr2, i1.checked_sub(i2))?);
+                    let rule1 = BMinus(expr1, expr.clone(), expr3.clone(), depth + 1);
+                    let rule2 = solve_cont(&expr3, &new_cont, depth + 1)?;
+                    CMinus(
+                        expr.clone(),
+                        cont.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+                Op::Times => {
+                    let expr3 = Int(checked(&expr2, i1.checked_mul(i2))?);
+                    let rule1 = BTimes(expr1, expr.clone(), expr3.clone(), depth + 1);
+                    let rule2 = solve_cont(&expr3, &new_cont, depth + 1)?;
+                    CTimes(
+                        expr.clone(),
+                        cont.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+                Op::Lt => {
+                    let expr3 = Bool(i1 < i2);
+                    let rule1 = BLt(expr1, expr.clone(), expr3.clone(), depth + 1);
+                    let rule2 = solve_cont(&expr3, &new_cont, depth + 1)?;
+                    CLt(
+                        expr.clone(),
+                        cont.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+            }
+        }
+        Some(Frame::If(expr1, expr2)) => match expr {
+            Bool(true) => {
+                let rule = solve(&expr1, &new_cont, depth + 1)?;
+                CIfT(cont.clone(), Box::new(rule), depth)
+            }
+            Bool(false) => {
+                let rule = solve(&expr2, &new_cont, depth + 1)?;
+                CIfF(cont.clone(), Box::new(rule), depth)
+            }
+            _ => return Err(EvalError::Stuck(format!("{} => {}", expr, cont))),
+        },
+    };
+    Ok(rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eval_cont_ml1::{
+            ast::{Cont, Frame, Op},
+            parser::parse,
+            rule::Rule::*,
+            solver::solve,
+        },
+        eval_ml1::ast::Expr::*,
+    };
+
+    #[test]
+    fn test_solve1() {
+        assert_eq!(
+            solve(&Int(3), &Cont::new(), 0).unwrap(),
+            EInt(3, Cont::new(), Box::new(CRet(Int(3), 1)), 0)
+        );
+    }
+
+    #[test]
+    fn test_solve2() {
+        let cont = Cont::from(vec![Frame::EvalR(Op::Plus, Int(5))]);
+        assert_eq!(
+            solve(&Plus(Box::new(Int(3)), Box::new(Int(5))), &Cont::new(), 0).unwrap(),
+            EBinOp(
+                Int(3),
+                Op::Plus,
+                Int(5),
+                Cont::new(),
+                Box::new(EInt(
+                    3,
+                    cont.clone(),
+                    Box::new(CEvalR(
+                        Int(3),
+                        cont,
+                        Box::new(EInt(
+                            5,
+                            Cont::from(vec![Frame::BinOp(Int(3), Op::Plus)]),
+                            Box::new(CPlus(
+                                Int(5),
+                                Cont::from(vec![Frame::BinOp(Int(3), Op::Plus)]),
+                                Box::new(BPlus(Int(3), Int(5), Int(8), 5)),
+                                Box::new(CRet(Int(8), 5)),
+                                4
+                            )),
+                            3
+                        )),
+                        2
+                    )),
+                    1
+                )),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let (expr, cont) = parse("if 4 < 5 then 2 else 3").unwrap().1;
+        assert_eq!(
+            solve(&expr, &cont, 0).unwrap().to_string(),
+            "if 4 < 5 then 2 else 3 evalto 2 by E-If {
+    4 < 5 >> {if _ then 2 else 3} evalto 2 by E-BinOp {
+        4 >> {_ < 5} >> {if _ then 2 else 3} evalto 2 by E-Int {
+            4 => {_ < 5} >> {if _ then 2 else 3} evalto 2 by C-EvalR {
+                5 >> {4 < _} >> {if _ then 2 else 3} evalto 2 by E-Int {
+                    5 => {4 < _} >> {if _ then 2 else 3} evalto 2 by C-Lt {
+                        4 less than 5 is true by B-Lt {};
+                        true => {if _ then 2 else 3} evalto 2 by C-IfT {
+                            2 evalto 2 by E-Int {
+                                2 => _ evalto 2 by C-Ret {}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}"
+        );
+    }
+}
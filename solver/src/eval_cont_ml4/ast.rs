@@ -0,0 +1,417 @@
+use crate::eval_cont_ml1::ast::Op;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Expr {
+    Int(i64),
+    Bool(bool),
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+    Plus(Box<Expr>, Box<Expr>),
+    Minus(Box<Expr>, Box<Expr>),
+    Times(Box<Expr>, Box<Expr>),
+    Lt(Box<Expr>, Box<Expr>),
+    Let(Box<Expr>, Box<Expr>, Box<Expr>),
+    Var(String),
+    Fun(Box<Expr>, Box<Expr>),
+    App(Box<Expr>, Box<Expr>),
+    LetRec(Box<Expr>, Box<Expr>, Box<Expr>, Box<Expr>),
+    Closure(Env, Box<Expr>, Box<Expr>),
+    RecClosure(Env, Box<Expr>, Box<Expr>, Box<Expr>),
+    Nil,
+    Cons(Box<Expr>, Box<Expr>),
+    Match(Box<Expr>, Box<Expr>, Box<Expr>, Box<Expr>, Box<Expr>),
+    LetCc(Box<Expr>, Box<Expr>),
+    Continuation(Cont),
+}
+
+impl Expr {
+    // 結合の強さ (大きいほど強い)
+    fn precedence(&self) -> usize {
+        use crate::eval_cont_ml4::ast::Expr::*;
+
+        match self {
+            If(_, _, _)
+            | Let(_, _, _)
+            | Fun(_, _)
+            | LetRec(_, _, _, _)
+            | Match(_, _, _, _, _)
+            | LetCc(_, _) => 0,
+            Lt(_, _) => 1,
+            Cons(_, _) => 2,
+            Plus(_, _) | Minus(_, _) => 3,
+            Times(_, _) => 4,
+            App(_, _) => 5,
+            Int(i) if *i < 0 => 5,
+            _ => 6,
+        }
+    }
+
+    fn paren(&self, paren: bool) -> String {
+        if paren {
+            format!("({})", self)
+        } else {
+            self.to_string()
+        }
+    }
+
+    // 右にできるだけ伸びる式 (右端にあれば括弧がいらない)
+    fn is_open(&self) -> bool {
+        use crate::eval_cont_ml4::ast::Expr::*;
+
+        matches!(
+            self,
+            If(_, _, _)
+                | Let(_, _, _)
+                | Fun(_, _)
+                | LetRec(_, _, _, _)
+                | Match(_, _, _, _, _)
+                | LetCc(_, _)
+        )
+    }
+
+    fn ends_with_match(&self) -> bool {
+        use crate::eval_cont_ml4::ast::Expr::*;
+
+        match self {
+            Match(_, _, _, _, _) => true,
+            If(_, _, expr)
+            | Let(_, _, expr)
+            | Fun(_, expr)
+            | LetRec(_, _, _, expr)
+            | LetCc(_, expr) => expr.ends_with_match(),
+            _ => false,
+        }
+    }
+
+    // tail は後ろに何も続かないこと
+    fn show(&self, tail: bool) -> String {
+        use crate::eval_cont_ml4::ast::Expr::*;
+
+        let p = self.precedence();
+        let left = |expr: &Expr, paren: bool| {
+            if paren {
+                expr.paren(true)
+            } else {
+                expr.show(false)
+            }
+        };
+        // 右端なら if や let も括弧なしで書ける
+        let right = |expr: &Expr, paren: bool| {
+            if paren && !(tail && expr.is_open()) {
+                expr.paren(true)
+            } else {
+                expr.show(tail)
+            }
+        };
+        match self {
+            Int(i) => i.to_string(),
+            Bool(b) => b.to_string(),
+            If(expr1, expr2, expr3) => {
+                format!("if {} then {} else {}", expr1, expr2, expr3.show(tail))
+            }
+            Plus(expr1, expr2) => format!(
+                "{} + {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Minus(expr1, expr2) => format!(
+                "{} - {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Times(expr1, expr2) => format!(
+                "{} * {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Lt(expr1, expr2) => format!(
+                "{} < {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Let(expr1, expr2, expr3) => {
+                format!("let {} = {} in {}", expr1, expr2, expr3.show(tail))
+            }
+            Var(s) => s.to_string(),
+            Fun(expr1, expr2) => format!("fun {} -> {}", expr1, expr2.show(tail)),
+            App(expr1, expr2) => format!(
+                "{} {}",
+                left(expr1, expr1.precedence() < p),
+                expr2.paren(expr2.precedence() <= p)
+            ),
+            LetRec(expr1, expr2, expr3, expr4) => {
+                format!(
+                    "let rec {} = fun {} -> {} in {}",
+                    expr1,
+                    expr2,
+                    expr3,
+                    expr4.show(tail)
+                )
+            }
+            Closure(env, expr1, expr2) => format!("({})[fun {} -> {}]", env, expr1, expr2),
+            RecClosure(env, expr1, expr2, expr3) => {
+                format!("({})[rec {} = fun {} -> {}]", env, expr1, expr2, expr3)
+            }
+            Nil => "[]".to_string(),
+            Cons(expr1, expr2) => format!(
+                "{} :: {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() < p)
+            ),
+            Match(expr1, expr2, expr3, expr4, expr5) => {
+                // 最初の節の本体が match で終わると後続の節を取り込んでしまう
+                let expr2 = left(expr2, expr2.ends_with_match());
+                format!(
+                    "match {} with [] -> {} | {} :: {} -> {}",
+                    expr1,
+                    expr2,
+                    expr3,
+                    expr4,
+                    expr5.show(tail)
+                )
+            }
+            LetCc(expr1, expr2) => format!("letcc {} in {}", expr1, expr2.show(tail)),
+            Continuation(cont) => format!("[{}]", cont),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.show(true))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Env(Vec<(Expr, Expr)>);
+
+impl Env {
+    pub fn new() -> Env {
+        Env(vec![])
+    }
+
+    pub fn from(pairs: Vec<(Expr, Expr)>) -> Env {
+        Env(pairs)
+    }
+
+    pub fn put(&mut self, expr1: Expr, expr2: Expr) {
+        self.0.push((expr1, expr2))
+    }
+
+    pub fn get(&self, expr: &Expr) -> Expr {
+        self.0
+            .iter()
+            .rev()
+            .find(|(expr1, _)| expr1 == expr)
+            .unwrap()
+            .1
+            .clone()
+    }
+
+    pub fn contains(&self, expr: &Expr) -> bool {
+        self.0.iter().any(|(expr1, _)| expr1 == expr)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for Env {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs = self
+            .0
+            .iter()
+            .map(|(expr1, expr2)| format!("{} = {}", expr1, expr2))
+            .collect::<Vec<_>>();
+        write!(f, "{}", pairs.join(", "))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Frame {
+    // {E |- _ op e}
+    EvalR(Env, Op, Expr),
+    // {v op _}
+    BinOp(Expr, Op),
+    // {E |- if _ then e else e}
+    If(Env, Expr, Expr),
+    // {E |- let x = _ in e}
+    Let(Env, Expr, Expr),
+    // {E |- _ e}
+    EvalArg(Env, Expr),
+    // {v _}
+    EvalFun(Expr),
+    // {E |- _ :: e}
+    EvalConsR(Env, Expr),
+    // {v :: _}
+    Cons(Expr),
+    // {E |- match _ with [] -> e | x :: y -> e}
+    Match(Env, Expr, Expr, Expr, Expr),
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_cont_ml4::ast::Frame::*;
+
+        // 空の環境は "|-" だけを書く
+        let turnstile = |env: &Env| {
+            if env.is_empty() {
+                "|-".to_string()
+            } else {
+                format!("{} |-", env)
+            }
+        };
+        let precedence = |op: &Op| match op {
+            Op::Plus | Op::Minus => 3,
+            Op::Times => 4,
+            Op::Lt => 1,
+        };
+        match self {
+            EvalR(env, op, expr) => {
+                let expr = expr.paren(expr.precedence() <= precedence(op) && !expr.is_open());
+                write!(f, "{{{} _ {} {}}}", turnstile(env), op, expr)
+            }
+            BinOp(expr, op) => {
+                let expr = expr.paren(expr.precedence() < precedence(op));
+                write!(f, "{{{} {} _}}", expr, op)
+            }
+            If(env, expr1, expr2) => write!(
+                f,
+                "{{{} if _ then {} else {}}}",
+                turnstile(env),
+                expr1,
+                expr2
+            ),
+            Let(env, expr1, expr2) => {
+                write!(f, "{{{} let {} = _ in {}}}", turnstile(env), expr1, expr2)
+            }
+            EvalArg(env, expr) => write!(
+                f,
+                "{{{} _ {}}}",
+                turnstile(env),
+                expr.paren(expr.precedence() <= 5)
+            ),
+            EvalFun(expr) => write!(f, "{{{} _}}", expr.paren(expr.precedence() < 6)),
+            EvalConsR(env, expr) => write!(
+                f,
+                "{{{} _ :: {}}}",
+                turnstile(env),
+                expr.paren(expr.precedence() < 2 && !expr.is_open())
+            ),
+            Cons(expr) => write!(f, "{{{} :: _}}", expr.paren(expr.precedence() <= 2)),
+            Match(env, expr1, expr2, expr3, expr4) => {
+                let expr1 = expr1.paren(matches!(expr1, Expr::Match(_, _, _, _, _)));
+                write!(
+                    f,
+                    "{{{} match _ with [] -> {} | {} :: {} -> {}}}",
+                    turnstile(env),
+                    expr1,
+                    expr2,
+                    expr3,
+                    expr4
+                )
+            }
+        }
+    }
+}
+
+// 末尾が先頭のフレーム
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Cont(Vec<Frame>);
+
+impl Cont {
+    pub fn new() -> Cont {
+        Cont(vec![])
+    }
+
+    pub fn from(frames: Vec<Frame>) -> Cont {
+        Cont(frames.into_iter().rev().collect())
+    }
+
+    pub fn push(&mut self, frame: Frame) {
+        self.0.push(frame)
+    }
+
+    pub fn pop(&mut self) -> Option<Frame> {
+        self.0.pop()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for Cont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "_");
+        }
+        let frames = self
+            .0
+            .iter()
+            .rev()
+            .map(|frame| frame.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}", frames.join(" >> "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eval_cont_ml1::ast::Op,
+        eval_cont_ml4::ast::{Cont, Env, Expr::*, Frame},
+    };
+
+    #[test]
+    fn test_env() {
+        let mut env = Env::new();
+        env.put(Var("x".to_string()), Int(1));
+        env.put(Var("y".to_string()), Int(2));
+        env.put(Var("x".to_string()), Int(3));
+        assert_eq!("x = 1, y = 2, x = 3", env.to_string());
+        assert_eq!(Int(3), env.get(&Var("x".to_string())));
+        assert_eq!(Int(2), env.get(&Var("y".to_string())));
+    }
+
+    #[test]
+    fn test_cont() {
+        let env = Env::from(vec![(Var("x".to_string()), Int(1))]);
+        let cont = Cont::from(vec![
+            Frame::EvalR(
+                Env::new(),
+                Op::Plus,
+                Plus(Box::new(Int(1)), Box::new(Int(2))),
+            ),
+            Frame::EvalArg(env.clone(), Var("x".to_string())),
+            Frame::Cons(Cons(Box::new(Int(1)), Box::new(Nil))),
+        ]);
+        assert_eq!(
+            "{|- _ + (1 + 2)} >> {x = 1 |- _ x} >> {(1 :: []) :: _}",
+            cont.to_string()
+        );
+        let mut env = Env::new();
+        env.put(Var("k".to_string()), Continuation(cont));
+        env.put(Var("j".to_string()), Continuation(Cont::new()));
+        assert_eq!(
+            "k = [{|- _ + (1 + 2)} >> {x = 1 |- _ x} >> {(1 :: []) :: _}], j = [_]",
+            env.to_string()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "1 + letcc k in k 2",
+            Plus(
+                Box::new(Int(1)),
+                Box::new(LetCc(
+                    Box::new(Var("k".to_string())),
+                    Box::new(App(Box::new(Var("k".to_string())), Box::new(Int(2))))
+                ))
+            )
+            .to_string()
+        );
+    }
+}
@@ -0,0 +1,440 @@
+use crate::{
+    eval_cont_ml4::ast::{Env, Expr, Expr::*},
+    syntax::lexer::{keyword, name},
+    util::ws,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, digit1},
+    combinator::{map_res, opt, recognize, verify},
+    multi::many0,
+    sequence::{delimited, tuple},
+    IResult,
+};
+
+// <expr> ::= <cons> [ '<' <cons> ]
+// <cons> ::= <term1> [ '::' <cons> ]
+// <term1> ::= <term2> [ ( '+' | '-' ) <term2> ]*
+// <term2> ::= <app> [ '*' <app> ]*
+// <app> ::= <factor> [ <arg> ]*
+// <factor> ::= <value> | <nil> | <paren> | <if> | <let_rec> | <let> | <fun> | <match> | <letcc> | <var>
+// <arg> ::= <pos_int> | <bool> | <nil> | <paren> | <var>
+// <value> ::= <int> | <bool>
+// <int> ::= 数値
+// <bool> ::= 'true' | 'false'
+// <paren> ::= '(' <expr> ')'
+// <if> ::= 'if' <expr> 'then' <expr> 'else' <expr>
+// <let_rec> ::= 'let' 'rec' <var> '=' 'fun' <var> '->' <expr> 'in' <expr>
+// <let> ::= 'let' <var> '=' <expr> 'in' <expr>
+// <fun> ::= 'fun' <var> '->' <expr>
+// <match> ::= 'match' <expr> 'with' '[' ']' '->' <expr> '|' <var> '::' <var> '->' <expr>
+// <letcc> ::= 'letcc' <var> 'in' <expr>
+// <nil> ::= '[' ']'
+// <var> ::= 英字か '_' で始まる英数字と '_', '\'' の列 (予約語を除く)
+// <closure> ::= '(' <env> ')' '[' 'fun' <var> '->' <expr> ']'
+// <rec_closure> ::= '(' <env> ')' '[' 'rec' <var> '=' 'fun' <var> '->' <expr> ']'
+
+const KEYWORDS: [&str; 12] = [
+    "if", "then", "else", "let", "rec", "in", "fun", "match", "with", "letcc", "true", "false",
+];
+
+pub fn parse(input: &str) -> IResult<&str, (Env, Expr)> {
+    match input.find("|-") {
+        Some(i) => {
+            let (input1, input2) = input.split_at(i);
+            let (_, env) = parse_env(input1)?;
+            let (input, expr) = parse_expr(&input2[2..])?;
+            Ok((input, (env, expr)))
+        }
+        None => {
+            let (input, expr) = parse_expr(input)?;
+            Ok((input, (Env::new(), expr)))
+        }
+    }
+}
+
+// 評価結果 (クロージャを含む)
+pub fn parse_result(input: &str) -> IResult<&str, Expr> {
+    alt((parse_closure, parse_rec_closure, parse_expr))(input)
+}
+
+fn parse_env(input: &str) -> IResult<&str, Env> {
+    let mut env = Env::new();
+    let (input, pair) = opt(parse_pair)(input)?;
+    match pair {
+        Some((expr1, expr2)) => env.put(expr1, expr2),
+        None => return Ok((input, env)),
+    };
+    let (input, pairs) = opt(parse_pairs)(input)?;
+    if let Some(pairs) = pairs {
+        pairs
+            .into_iter()
+            .for_each(|(expr1, expr2)| env.put(expr1, expr2));
+    }
+    Ok((input, env))
+}
+
+fn parse_pair(input: &str) -> IResult<&str, (Expr, Expr)> {
+    let parse_value = alt((parse_closure, parse_rec_closure, parse_expr));
+    let (input, (expr1, _, expr2)) = tuple((parse_var, ws(char('=')), parse_value))(input)?;
+    let pair = (expr1, expr2);
+    Ok((input, pair))
+}
+
+fn parse_pairs(input: &str) -> IResult<&str, Vec<(Expr, Expr)>> {
+    let (input, pairs) = many0(tuple((ws(char(',')), parse_pair)))(input)?;
+    let pairs = pairs.into_iter().map(|(_, pair)| pair).collect();
+    Ok((input, pairs))
+}
+
+pub fn parse_expr(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr1, expr2)) = tuple((parse_cons, opt(parse_lt)))(input)?;
+    let expr = match expr2 {
+        Some(expr2) => Lt(Box::new(expr1), Box::new(expr2)),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+fn parse_lt(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr)) = tuple((ws(char('<')), parse_cons))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_cons(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr1, expr2)) =
+        tuple((parse_term1, opt(tuple((ws(tag("::")), parse_cons)))))(input)?;
+    let expr = match expr2 {
+        Some((_, expr2)) => Cons(Box::new(expr1), Box::new(expr2)),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+fn parse_term1(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_term2, parse_plus_minus))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
+        '+' => Plus(Box::new(expr1), Box::new(expr2.clone())),
+        '-' => Minus(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+fn parse_plus_minus(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
+    let parse_plus = ws(char('+'));
+    let parse_minus = ws(char('-'));
+    let parse_op = alt((parse_plus, parse_minus));
+    let (input, exprs) = many0(tuple((parse_op, parse_term2)))(input)?;
+    Ok((input, exprs))
+}
+
+fn parse_term2(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_app, parse_times))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
+        '*' => Times(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+fn parse_times(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
+    let parse_times = ws(char('*'));
+    let (input, exprs) = many0(tuple((parse_times, parse_app)))(input)?;
+    Ok((input, exprs))
+}
+
+fn parse_app(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_factor, many0(parse_arg)))(input)?;
+    let expr = exprs
+        .into_iter()
+        .fold(expr, |expr1, expr2| App(Box::new(expr1), Box::new(expr2)));
+    Ok((input, expr))
+}
+
+fn parse_factor(input: &str) -> IResult<&str, Expr> {
+    let (input, expr) = alt((
+        parse_value,
+        parse_nil,
+        parse_paren,
+        parse_if,
+        parse_let_rec,
+        parse_let,
+        parse_fun,
+        parse_match,
+        parse_letcc,
+        parse_var,
+    ))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_arg(input: &str) -> IResult<&str, Expr> {
+    let parse_pos_int = |input| {
+        let (input, i) = ws(parse_pos_number)(input)?;
+        Ok((input, Int(i)))
+    };
+    let (input, expr) = alt((parse_pos_int, parse_bool, parse_nil, parse_paren, parse_var))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_value(input: &str) -> IResult<&str, Expr> {
+    let (input, expr) = alt((parse_int, parse_bool))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_int(input: &str) -> IResult<&str, Expr> {
+    let (input, i) = alt((ws(parse_pos_number), ws(parse_neg_number)))(input)?;
+    let expr = Int(i);
+    Ok((input, expr))
+}
+
+fn parse_pos_number(input: &str) -> IResult<&str, i64> {
+    map_res(digit1, |i: &str| i.parse::<i64>())(input)
+}
+
+fn parse_neg_number(input: &str) -> IResult<&str, i64> {
+    map_res(recognize(tuple((char('-'), digit1))), |i: &str| {
+        i.parse::<i64>()
+    })(input)
+}
+
+fn parse_bool(input: &str) -> IResult<&str, Expr> {
+    let (input, b) = alt((parse_true, parse_false))(input)?;
+    let expr = Bool(b);
+    Ok((input, expr))
+}
+
+fn parse_true(input: &str) -> IResult<&str, bool> {
+    let (input, _) = ws(keyword("true"))(input)?;
+    Ok((input, true))
+}
+
+fn parse_false(input: &str) -> IResult<&str, bool> {
+    let (input, _) = ws(keyword("false"))(input)?;
+    Ok((input, false))
+}
+
+fn parse_nil(input: &str) -> IResult<&str, Expr> {
+    let (input, _) = tuple((ws(char('[')), ws(char(']'))))(input)?;
+    Ok((input, Nil))
+}
+
+fn parse_paren(input: &str) -> IResult<&str, Expr> {
+    let parse_lparen = ws(char('('));
+    let parse_rparen = ws(char(')'));
+    let (input, expr) = delimited(parse_lparen, parse_expr, parse_rparen)(input)?;
+    Ok((input, expr))
+}
+
+fn parse_if(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr1, _, expr2, _, expr3)) = tuple((
+        ws(keyword("if")),
+        parse_expr,
+        ws(keyword("then")),
+        parse_expr,
+        ws(keyword("else")),
+        parse_expr,
+    ))(input)?;
+    let expr = If(Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+fn parse_let(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr1, _, expr2, _, expr3)) = tuple((
+        ws(keyword("let")),
+        parse_var,
+        ws(char('=')),
+        parse_expr,
+        ws(keyword("in")),
+        parse_expr,
+    ))(input)?;
+    let expr = Let(Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+fn parse_let_rec(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, _, expr1, _, _, expr2, _, expr3, _, expr4)) = tuple((
+        ws(keyword("let")),
+        ws(keyword("rec")),
+        parse_var,
+        ws(char('=')),
+        ws(keyword("fun")),
+        parse_var,
+        ws(tag("->")),
+        parse_expr,
+        ws(keyword("in")),
+        parse_expr,
+    ))(input)?;
+    let expr = LetRec(
+        Box::new(expr1),
+        Box::new(expr2),
+        Box::new(expr3),
+        Box::new(expr4),
+    );
+    Ok((input, expr))
+}
+
+fn parse_fun(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr1, _, expr2)) =
+        tuple((ws(keyword("fun")), parse_var, ws(tag("->")), parse_expr))(input)?;
+    let expr = Fun(Box::new(expr1), Box::new(expr2));
+    Ok((input, expr))
+}
+
+fn parse_match(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr1, _, _, _, expr2, _, expr3, _, expr4, _, expr5)) = tuple((
+        ws(keyword("match")),
+        parse_expr,
+        ws(keyword("with")),
+        parse_nil,
+        ws(tag("->")),
+        parse_expr,
+        ws(char('|')),
+        parse_var,
+        ws(tag("::")),
+        parse_var,
+        ws(tag("->")),
+        parse_expr,
+    ))(input)?;
+    let expr = Match(
+        Box::new(expr1),
+        Box::new(expr2),
+        Box::new(expr3),
+        Box::new(expr4),
+        Box::new(expr5),
+    );
+    Ok((input, expr))
+}
+
+fn parse_letcc(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr1, _, expr2)) = tuple((
+        ws(keyword("letcc")),
+        parse_var,
+        ws(keyword("in")),
+        parse_expr,
+    ))(input)?;
+    let expr = LetCc(Box::new(expr1), Box::new(expr2));
+    Ok((input, expr))
+}
+
+fn parse_closure(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, env, _, _, _, expr1, _, expr2, _)) = tuple((
+        ws(char('(')),
+        parse_env,
+        ws(char(')')),
+        ws(char('[')),
+        ws(keyword("fun")),
+        parse_var,
+        ws(tag("->")),
+        parse_expr,
+        ws(char(']')),
+    ))(input)?;
+    let expr = Closure(env, Box::new(expr1), Box::new(expr2));
+    Ok((input, expr))
+}
+
+fn parse_rec_closure(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, env, _, _, _, expr1, _, _, expr2, _, expr3, _)) = tuple((
+        ws(char('(')),
+        parse_env,
+        ws(char(')')),
+        ws(char('[')),
+        ws(keyword("rec")),
+        parse_var,
+        ws(char('=')),
+        ws(keyword("fun")),
+        parse_var,
+        ws(tag("->")),
+        parse_expr,
+        ws(char(']')),
+    ))(input)?;
+    let expr = RecClosure(env, Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+pub fn parse_var(input: &str) -> IResult<&str, Expr> {
+    let (input, s) = ws(parse_ident)(input)?;
+    let expr = Var(s.to_string());
+    Ok((input, expr))
+}
+
+fn parse_ident(input: &str) -> IResult<&str, &str> {
+    verify(name, |s: &str| !KEYWORDS.contains(&s))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_cont_ml4::{
+        ast::{Env, Expr::*},
+        parser::{parse, parse_expr, parse_var},
+    };
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(
+            parse("|- 3 + 5").unwrap().1,
+            (Env::new(), Plus(Box::new(Int(3)), Box::new(Int(5))))
+        );
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse("|- 1 + letcc k in 2 + k 3").unwrap().1,
+            (
+                Env::new(),
+                Plus(
+                    Box::new(Int(1)),
+                    Box::new(LetCc(
+                        Box::new(Var("k".to_string())),
+                        Box::new(Plus(
+                            Box::new(Int(2)),
+                            Box::new(App(Box::new(Var("k".to_string())), Box::new(Int(3))))
+                        ))
+                    ))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse3() {
+        assert_eq!(
+            parse("x = 1, f = ()[fun y -> y] |- f x :: []").unwrap().1,
+            (
+                Env::from(vec![
+                    (Var("x".to_string()), Int(1)),
+                    (
+                        Var("f".to_string()),
+                        Closure(
+                            Env::new(),
+                            Box::new(Var("y".to_string())),
+                            Box::new(Var("y".to_string()))
+                        )
+                    ),
+                ]),
+                Cons(
+                    Box::new(App(
+                        Box::new(Var("f".to_string())),
+                        Box::new(Var("x".to_string()))
+                    )),
+                    Box::new(Nil)
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_ident() {
+        assert_eq!(
+            parse_expr("f truex").unwrap().1,
+            App(
+                Box::new(Var("f".to_string())),
+                Box::new(Var("truex".to_string()))
+            )
+        );
+        assert_eq!(parse_var("_x1").unwrap().1, Var("_x1".to_string()));
+        assert!(parse_var("123").is_err());
+    }
+}
@@ -0,0 +1,518 @@
+use crate::{
+    eval_cont_ml4::ast::{Cont, Env, Expr},
+    util::{env_prefix, ident},
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    EInt(Env, Expr, Cont, Box<Rule>, usize),
+    EBool(Env, Expr, Cont, Box<Rule>, usize),
+    EIf(Env, Expr, Cont, Box<Rule>, usize),
+    EBinOp(Env, Expr, Cont, Box<Rule>, usize),
+    EVar(Env, Expr, Cont, Box<Rule>, usize),
+    ELet(Env, Expr, Cont, Box<Rule>, usize),
+    EFun(Env, Expr, Cont, Box<Rule>, usize),
+    EApp(Env, Expr, Cont, Box<Rule>, usize),
+    ELetRec(Env, Expr, Cont, Box<Rule>, usize),
+    ENil(Env, Expr, Cont, Box<Rule>, usize),
+    ECons(Env, Expr, Cont, Box<Rule>, usize),
+    EMatch(Env, Expr, Cont, Box<Rule>, usize),
+    ELetCc(Env, Expr, Cont, Box<Rule>, usize),
+    CRet(Expr, usize),
+    CPlus(Expr, Cont, Box<Rule>, Box<Rule>, usize),
+    CMinus(Expr, Cont, Box<Rule>, Box<Rule>, usize),
+    CTimes(Expr, Cont, Box<Rule>, Box<Rule>, usize),
+    CLt(Expr, Cont, Box<Rule>, Box<Rule>, usize),
+    CEvalR(Expr, Cont, Box<Rule>, usize),
+    CIfT(Expr, Cont, Box<Rule>, usize),
+    CIfF(Expr, Cont, Box<Rule>, usize),
+    CLetBody(Expr, Cont, Box<Rule>, usize),
+    CEvalArg(Expr, Cont, Box<Rule>, usize),
+    CEvalFun(Expr, Cont, Box<Rule>, usize),
+    CEvalFunR(Expr, Cont, Box<Rule>, usize),
+    CEvalFunC(Expr, Cont, Box<Rule>, usize),
+    CEvalConsR(Expr, Cont, Box<Rule>, usize),
+    CCons(Expr, Cont, Box<Rule>, usize),
+    CMatchNil(Expr, Cont, Box<Rule>, usize),
+    CMatchCons(Expr, Cont, Box<Rule>, usize),
+    BPlus(Expr, Expr, Expr, usize),
+    BMinus(Expr, Expr, Expr, usize),
+    BTimes(Expr, Expr, Expr, usize),
+    BLt(Expr, Expr, Expr, usize),
+}
+
+impl Rule {
+    pub fn evaluated(&self) -> Expr {
+        use crate::eval_cont_ml4::rule::Rule::*;
+
+        match self {
+            EInt(_, _, _, rule, _) => rule.evaluated(),
+            EBool(_, _, _, rule, _) => rule.evaluated(),
+            EIf(_, _, _, rule, _) => rule.evaluated(),
+            EBinOp(_, _, _, rule, _) => rule.evaluated(),
+            EVar(_, _, _, rule, _) => rule.evaluated(),
+            ELet(_, _, _, rule, _) => rule.evaluated(),
+            EFun(_, _, _, rule, _) => rule.evaluated(),
+            EApp(_, _, _, rule, _) => rule.evaluated(),
+            ELetRec(_, _, _, rule, _) => rule.evaluated(),
+            ENil(_, _, _, rule, _) => rule.evaluated(),
+            ECons(_, _, _, rule, _) => rule.evaluated(),
+            EMatch(_, _, _, rule, _) => rule.evaluated(),
+            ELetCc(_, _, _, rule, _) => rule.evaluated(),
+            CRet(expr, _) => expr.clone(),
+            CPlus(_, _, _, rule2, _) => rule2.evaluated(),
+            CMinus(_, _, _, rule2, _) => rule2.evaluated(),
+            CTimes(_, _, _, rule2, _) => rule2.evaluated(),
+            CLt(_, _, _, rule2, _) => rule2.evaluated(),
+            CEvalR(_, _, rule, _) => rule.evaluated(),
+            CIfT(_, _, rule, _) => rule.evaluated(),
+            CIfF(_, _, rule, _) => rule.evaluated(),
+            CLetBody(_, _, rule, _) => rule.evaluated(),
+            CEvalArg(_, _, rule, _) => rule.evaluated(),
+            CEvalFun(_, _, rule, _) => rule.evaluated(),
+            CEvalFunR(_, _, rule, _) => rule.evaluated(),
+            CEvalFunC(_, _, rule, _) => rule.evaluated(),
+            CEvalConsR(_, _, rule, _) => rule.evaluated(),
+            CCons(_, _, rule, _) => rule.evaluated(),
+            CMatchNil(_, _, rule, _) => rule.evaluated(),
+            CMatchCons(_, _, rule, _) => rule.evaluated(),
+            BPlus(_, _, expr3, _) => expr3.clone(),
+            BMinus(_, _, expr3, _) => expr3.clone(),
+            BTimes(_, _, expr3, _) => expr3.clone(),
+            BLt(_, _, expr3, _) => expr3.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_cont_ml4::rule::Rule::*;
+
+        match self {
+            EInt(env, expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {}{} evalto {} by E-Int {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EBool(env, expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {}{} evalto {} by E-Bool {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EIf(env, expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {}{} evalto {} by E-If {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EBinOp(env, expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {}{} evalto {} by E-BinOp {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EVar(env, expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {}{} evalto {} by E-Var {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELet(env, expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {}{} evalto {} by E-Let {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EFun(env, expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {}{} evalto {} by E-Fun {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EApp(env, expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {}{} evalto {} by E-App {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELetRec(env, expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {}{} evalto {} by E-LetRec {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ENil(env, expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {}{} evalto {} by E-Nil {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ECons(env, expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {}{} evalto {} by E-Cons {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMatch(env, expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {}{} evalto {} by E-Match {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELetCc(env, expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {}{} evalto {} by E-LetCc {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    then(cont),
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CRet(expr, depth) => {
+                write!(
+                    f,
+                    "{}{} => _ evalto {} by C-Ret {{}}",
+                    ident(*depth),
+                    expr,
+                    expr
+                )
+            }
+            CPlus(expr, cont, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-Plus {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CMinus(expr, cont, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-Minus {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CTimes(expr, cont, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-Times {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CLt(expr, cont, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-Lt {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CEvalR(expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-EvalR {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CIfT(expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-IfT {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CIfF(expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-IfF {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CLetBody(expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-LetBody {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CEvalArg(expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-EvalArg {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CEvalFun(expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-EvalFun {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CEvalFunR(expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-EvalFunR {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CEvalFunC(expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-EvalFunC {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CEvalConsR(expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-EvalConsR {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CCons(expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-Cons {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CMatchNil(expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-MatchNil {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            CMatchCons(expr, cont, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} => {} evalto {} by C-MatchCons {{",
+                    ident(*depth),
+                    expr,
+                    cont,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            BPlus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} plus {} is {} by B-Plus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BMinus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} minus {} is {} by B-Minus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BTimes(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} times {} is {} by B-Times {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BLt(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} less than {} is {} by B-Lt {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+        }
+    }
+}
+
+// 継続が _ のときは省略する
+fn then(cont: &Cont) -> String {
+    if cont.is_empty() {
+        String::new()
+    } else {
+        format!(" >> {}", cont)
+    }
+}
@@ -0,0 +1,359 @@
+use crate::{
+    eval_cont_ml1::ast::Op,
+    eval_cont_ml4::{
+        ast::{Cont, Env, Expr, Expr::*, Frame},
+        rule::{Rule, Rule::*},
+    },
+    eval_error::{check_depth, checked, EvalError},
+};
+
+// E |- e >> k evalto v
+pub fn solve(env: &Env, expr: &Expr, cont: &Cont, depth: usize) -> Result<Rule, EvalError> {
+    check_depth(depth)?;
+    // 部分式を評価する継続にフレームを積む
+    let eval = |expr1: &Expr, frame: Frame| {
+        let mut new_cont = cont.clone();
+        new_cont.push(frame);
+        solve(env, expr1, &new_cont, depth + 1).map(Box::new)
+    };
+    let rule = match expr {
+        Int(_) => {
+            let rule = solve_cont(expr, cont, depth + 1)?;
+            EInt(
+                env.clone(),
+                expr.clone(),
+                cont.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+        Bool(_) => {
+            let rule = solve_cont(expr, cont, depth + 1)?;
+            EBool(
+                env.clone(),
+                expr.clone(),
+                cont.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+        If(expr1, expr2, expr3) => {
+            let rule = eval(
+                expr1,
+                Frame::If(env.clone(), *expr2.clone(), *expr3.clone()),
+            )?;
+            EIf(env.clone(), expr.clone(), cont.clone(), rule, depth)
+        }
+        Plus(expr1, expr2) => {
+            let rule = eval(expr1, Frame::EvalR(env.clone(), Op::Plus, *expr2.clone()))?;
+            EBinOp(env.clone(), expr.clone(), cont.clone(), rule, depth)
+        }
+        Minus(expr1, expr2) => {
+            let rule = eval(expr1, Frame::EvalR(env.clone(), Op::Minus, *expr2.clone()))?;
+            EBinOp(env.clone(), expr.clone(), cont.clone(), rule, depth)
+        }
+        Times(expr1, expr2) => {
+            let rule = eval(expr1, Frame::EvalR(env.clone(), Op::Times, *expr2.clone()))?;
+            EBinOp(env.clone(), expr.clone(), cont.clone(), rule, depth)
+        }
+        Lt(expr1, expr2) => {
+            let rule = eval(expr1, Frame::EvalR(env.clone(), Op::Lt, *expr2.clone()))?;
+            EBinOp(env.clone(), expr.clone(), cont.clone(), rule, depth)
+        }
+        Var(_) => {
+            if !env.contains(expr) {
+                return Err(EvalError::Unbound(expr.to_string()));
+            }
+            let rule = solve_cont(&env.get(expr), cont, depth + 1)?;
+            EVar(
+                env.clone(),
+                expr.clone(),
+                cont.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+        Let(expr1, expr2, expr3) => {
+            let rule = eval(
+                expr2,
+                Frame::Let(env.clone(), *expr1.clone(), *expr3.clone()),
+            )?;
+            ELet(env.clone(), expr.clone(), cont.clone(), rule, depth)
+        }
+        Fun(expr1, expr2) => {
+            let closure = Closure(env.clone(), expr1.clone(), expr2.clone());
+            let rule = solve_cont(&closure, cont, depth + 1)?;
+            EFun(
+                env.clone(),
+                expr.clone(),
+                cont.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+        App(expr1, expr2) => {
+            let rule = eval(expr1, Frame::EvalArg(env.clone(), *expr2.clone()))?;
+            EApp(env.clone(), expr.clone(), cont.clone(), rule, depth)
+        }
+        LetRec(expr1, expr2, expr3, expr4) => {
+            let closure = RecClosure(env.clone(), expr1.clone(), expr2.clone(), expr3.clone());
+            let mut new_env = env.clone();
+            new_env.put(*expr1.clone(), closure);
+            let rule = solve(&new_env, expr4, cont, depth + 1)?;
+            ELetRec(
+                env.clone(),
+                expr.clone(),
+                cont.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+        Nil => {
+            let rule = solve_cont(expr, cont, depth + 1)?;
+            ENil(
+                env.clone(),
+                expr.clone(),
+                cont.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+        Cons(expr1, expr2) => {
+            let rule = eval(expr1, Frame::EvalConsR(env.clone(), *expr2.clone()))?;
+            ECons(env.clone(), expr.clone(), cont.clone(), rule, depth)
+        }
+        Match(expr1, expr2, expr3, expr4, expr5) => {
+            let frame = Frame::Match(
+                env.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                *expr4.clone(),
+                *expr5.clone(),
+            );
+            let rule = eval(expr1, frame)?;
+            EMatch(env.clone(), expr.clone(), cont.clone(), rule, depth)
+        }
+        LetCc(expr1, expr2) => {
+            let mut new_env = env.clone();
+            new_env.put(*expr1.clone(), Continuation(cont.clone()));
+            let rule = solve(&new_env, expr2, cont, depth + 1)?;
+            ELetCc(
+                env.clone(),
+                expr.clone(),
+                cont.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+        _ => return Err(EvalError::Stuck(expr.to_string())),
+    };
+    Ok(rule)
+}
+
+// v => k evalto v
+pub fn solve_cont(expr: &Expr, cont: &Cont, depth: usize) -> Result<Rule, EvalError> {
+    check_depth(depth)?;
+    let mut new_cont = cont.clone();
+    let rule = match new_cont.pop() {
+        None => CRet(expr.clone(), depth),
+        Some(Frame::EvalR(env, op, expr1)) => {
+            new_cont.push(Frame::BinOp(expr.clone(), op));
+            let rule = solve(&env, &expr1, &new_cont, depth + 1)?;
+            CEvalR(expr.clone(), cont.clone(), Box::new(rule), depth)
+        }
+        Some(Frame::BinOp(expr1, op)) => {
+            let (i1, i2) = match (&expr1, expr) {
+                (Int(i1), Int(i2)) => (*i1, *i2),
+                _ => return Err(EvalError::Stuck(format!("{} => {}", expr, cont))),
+            };
+            let expr2 = format!("{} {} {}", expr1, op, expr);
+            match op {
+                Op::Plus => {
+                    let expr3 = Int(checked(&expr2, i1.checked_add(i2))?);
+                    let rule1 = BPlus(expr1, expr.clone(), expr3.clone(), depth + 1);
+                    let rule2 = solve_cont(&expr3, &new_cont, depth + 1)?;
+                    CPlus(
+                        expr.clone(),
+                        cont.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+                Op::Minus => {
+                    let expr3 = Int(checked(&expr2, i1.checked_sub(i2))?);
+                    let rule1 = BMinus(expr1, expr.clone(), expr3.clone(), depth + 1);
+                    let rule2 = solve_cont(&expr3, &new_cont, depth + 1)?;
+                    CMinus(
+                        expr.clone(),
+                        cont.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+                Op::Times => {
+                    let expr3 = Int(checked(&expr2, i1.checked_mul(i2))?);
+                    let rule1 = BTimes(expr1, expr.clone(), expr3.clone(), depth + 1);
+                    let rule2 = solve_cont(&expr3, &new_cont, depth + 1)?;
+                    CTimes(
+                        expr.clone(),
+                        cont.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+                Op::Lt => {
+                    let expr3 = Bool(i1 < i2);
+                    let rule1 = BLt(expr1, expr.clone(), expr3.clone(), depth + 1);
+                    let rule2 = solve_cont(&expr3, &new_cont, depth + 1)?;
+                    CLt(
+                        expr.clone(),
+                        cont.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+            }
+        }
+        Some(Frame::If(env, expr1, expr2)) => match expr {
+            Bool(true) => {
+                let rule = solve(&env, &expr1, &new_cont, depth + 1)?;
+                CIfT(expr.clone(), cont.clone(), Box::new(rule), depth)
+            }
+            Bool(false) => {
+                let rule = solve(&env, &expr2, &new_cont, depth + 1)?;
+                CIfF(expr.clone(), cont.clone(), Box::new(rule), depth)
+            }
+            _ => return Err(EvalError::Stuck(format!("{} => {}", expr, cont))),
+        },
+        Some(Frame::Let(env, expr1, expr2)) => {
+            let mut new_env = env.clone();
+            new_env.put(expr1, expr.clone());
+            let rule = solve(&new_env, &expr2, &new_cont, depth + 1)?;
+            CLetBody(expr.clone(), cont.clone(), Box::new(rule), depth)
+        }
+        Some(Frame::EvalArg(env, expr1)) => {
+            new_cont.push(Frame::EvalFun(expr.clone()));
+            let rule = solve(&env, &expr1, &new_cont, depth + 1)?;
+            CEvalArg(expr.clone(), cont.clone(), Box::new(rule), depth)
+        }
+        Some(Frame::EvalFun(expr1)) => match &expr1 {
+            Closure(env, expr2, expr3) => {
+                let mut new_env = env.clone();
+                new_env.put(*expr2.clone(), expr.clone());
+                let rule = solve(&new_env, expr3, &new_cont, depth + 1)?;
+                CEvalFun(expr.clone(), cont.clone(), Box::new(rule), depth)
+            }
+            RecClosure(env, expr2, expr3, expr4) => {
+                let mut new_env = env.clone();
+                new_env.put(*expr2.clone(), expr1.clone());
+                new_env.put(*expr3.clone(), expr.clone());
+                let rule = solve(&new_env, expr4, &new_cont, depth + 1)?;
+                CEvalFunR(expr.clone(), cont.clone(), Box::new(rule), depth)
+            }
+            Continuation(cont1) => {
+                // 現在の継続を捨てて取り出した継続に値を渡す
+                let rule = solve_cont(expr, cont1, depth + 1)?;
+                CEvalFunC(expr.clone(), cont.clone(), Box::new(rule), depth)
+            }
+            _ => return Err(EvalError::Stuck(format!("{} => {}", expr, cont))),
+        },
+        Some(Frame::EvalConsR(env, expr1)) => {
+            new_cont.push(Frame::Cons(expr.clone()));
+            let rule = solve(&env, &expr1, &new_cont, depth + 1)?;
+            CEvalConsR(expr.clone(), cont.clone(), Box::new(rule), depth)
+        }
+        Some(Frame::Cons(expr1)) => {
+            let expr2 = Cons(Box::new(expr1), Box::new(expr.clone()));
+            let rule = solve_cont(&expr2, &new_cont, depth + 1)?;
+            CCons(expr.clone(), cont.clone(), Box::new(rule), depth)
+        }
+        Some(Frame::Match(env, expr1, expr2, expr3, expr4)) => match expr {
+            Nil => {
+                let rule = solve(&env, &expr1, &new_cont, depth + 1)?;
+                CMatchNil(expr.clone(), cont.clone(), Box::new(rule), depth)
+            }
+            Cons(expr5, expr6) => {
+                let mut new_env = env.clone();
+                new_env.put(expr2, *expr5.clone());
+                new_env.put(expr3, *expr6.clone());
+                let rule = solve(&new_env, &expr4, &new_cont, depth + 1)?;
+                CMatchCons(expr.clone(), cont.clone(), Box::new(rule), depth)
+            }
+            _ => return Err(EvalError::Stuck(format!("{} => {}", expr, cont))),
+        },
+    };
+    Ok(rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_cont_ml4::{ast::Cont, parser::parse, solver::solve};
+
+    #[test]
+    fn test_solve1() {
+        let (env, expr) = parse("|- 1 + letcc k in 2 + k 3").unwrap().1;
+        assert_eq!(
+            solve(&env, &expr, &Cont::new(), 0)
+                .unwrap()
+                .evaluated()
+                .to_string(),
+            "4"
+        );
+    }
+
+    #[test]
+    fn test_solve2() {
+        let (env, expr) = parse(
+            "|- let rec sum = fun l -> match l with [] -> 0 | x :: y -> x + sum y in sum (1 :: 2 :: 3 :: [])",
+        )
+        .unwrap()
+        .1;
+        assert_eq!(
+            solve(&env, &expr, &Cont::new(), 0)
+                .unwrap()
+                .evaluated()
+                .to_string(),
+            "6"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let (env, expr) = parse("|- 1 + letcc k in 2 + k 3").unwrap().1;
+        assert_eq!(
+            solve(&env, &expr, &Cont::new(), 0).unwrap().to_string(),
+            "|- 1 + letcc k in 2 + k 3 evalto 4 by E-BinOp {
+    |- 1 >> {|- _ + letcc k in 2 + k 3} evalto 4 by E-Int {
+        1 => {|- _ + letcc k in 2 + k 3} evalto 4 by C-EvalR {
+            |- letcc k in 2 + k 3 >> {1 + _} evalto 4 by E-LetCc {
+                k = [{1 + _}] |- 2 + k 3 >> {1 + _} evalto 4 by E-BinOp {
+                    k = [{1 + _}] |- 2 >> {k = [{1 + _}] |- _ + k 3} >> {1 + _} evalto 4 by E-Int {
+                        2 => {k = [{1 + _}] |- _ + k 3} >> {1 + _} evalto 4 by C-EvalR {
+                            k = [{1 + _}] |- k 3 >> {2 + _} >> {1 + _} evalto 4 by E-App {
+                                k = [{1 + _}] |- k >> {k = [{1 + _}] |- _ 3} >> {2 + _} >> {1 + _} evalto 4 by E-Var {
+                                    [{1 + _}] => {k = [{1 + _}] |- _ 3} >> {2 + _} >> {1 + _} evalto 4 by C-EvalArg {
+                                        k = [{1 + _}] |- 3 >> {[{1 + _}] _} >> {2 + _} >> {1 + _} evalto 4 by E-Int {
+                                            3 => {[{1 + _}] _} >> {2 + _} >> {1 + _} evalto 4 by C-EvalFunC {
+                                                3 => {1 + _} evalto 4 by C-Plus {
+                                                    1 plus 3 is 4 by B-Plus {};
+                                                    4 => _ evalto 4 by C-Ret {}
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}"
+        );
+    }
+}
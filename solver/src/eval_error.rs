@@ -0,0 +1,41 @@
+use std::fmt;
+
+// これより深い導出は評価が止まらないものとみなす (system::STACK_SIZE に収まる深さ)
+pub const MAX_DEPTH: usize = 4000;
+
+// 評価の導出が作れない理由 (式は各体系の表示で持つ)
+#[derive(Clone, Debug, PartialEq)]
+pub enum EvalError {
+    Stuck(String),
+    Unbound(String),
+    DivisionByZero(String),
+    Overflow(String),
+    TooDeep,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_error::EvalError::*;
+
+        match self {
+            Stuck(expr) => write!(f, "cannot evaluate `{}`", expr),
+            Unbound(var) => write!(f, "unbound variable `{}`", var),
+            DivisionByZero(expr) => write!(f, "division by zero in `{}`", expr),
+            Overflow(expr) => write!(f, "integer overflow in `{}`", expr),
+            TooDeep => write!(f, "derivation deeper than {} levels", MAX_DEPTH),
+        }
+    }
+}
+
+pub fn check_depth(depth: usize) -> Result<(), EvalError> {
+    if depth > MAX_DEPTH {
+        Err(EvalError::TooDeep)
+    } else {
+        Ok(())
+    }
+}
+
+// checked_* の結果があふれていればエラーにする
+pub fn checked<T: fmt::Display>(expr: &T, result: Option<i64>) -> Result<i64, EvalError> {
+    result.ok_or_else(|| EvalError::Overflow(expr.to_string()))
+}
@@ -11,18 +11,86 @@ pub enum Expr {
     Lt(Box<Expr>, Box<Expr>),
 }
 
-impl fmt::Display for Expr {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl Expr {
+    // 結合の強さ (大きいほど強い)
+    pub(crate) fn precedence(&self) -> usize {
+        use crate::eval_ml1::ast::Expr::*;
+
+        match self {
+            If(_, _, _) => 0,
+            Lt(_, _) => 1,
+            Plus(_, _) | Minus(_, _) => 3,
+            Times(_, _) => 4,
+            _ => 6,
+        }
+    }
+
+    // 右にできるだけ伸びる式 (右端にあれば括弧がいらない)
+    pub(crate) fn is_open(&self) -> bool {
+        use crate::eval_ml1::ast::Expr::*;
+
+        matches!(self, If(_, _, _))
+    }
+
+    pub(crate) fn paren(&self, paren: bool) -> String {
+        if paren {
+            format!("({})", self)
+        } else {
+            self.to_string()
+        }
+    }
+
+    // tail は後ろに何も続かないこと
+    fn show(&self, tail: bool) -> String {
         use crate::eval_ml1::ast::Expr::*;
 
+        let p = self.precedence();
+        let left = |expr: &Expr, paren: bool| {
+            if paren {
+                expr.paren(true)
+            } else {
+                expr.show(false)
+            }
+        };
+        let right = |expr: &Expr, paren: bool| {
+            if paren && !(tail && expr.is_open()) {
+                expr.paren(true)
+            } else {
+                expr.show(tail)
+            }
+        };
         match self {
-            Int(i) => write!(f, "{}", i),
-            Bool(b) => write!(f, "{}", b),
-            If(expr1, expr2, expr3) => write!(f, "(if {} then {} else {})", expr1, expr2, expr3),
-            Plus(expr1, expr2) => write!(f, "({} + {})", expr1, expr2),
-            Minus(expr1, expr2) => write!(f, "({} - {})", expr1, expr2),
-            Times(expr1, expr2) => write!(f, "({} * {})", expr1, expr2),
-            Lt(expr1, expr2) => write!(f, "({} < {})", expr1, expr2),
+            Int(i) => i.to_string(),
+            Bool(b) => b.to_string(),
+            If(expr1, expr2, expr3) => {
+                format!("if {} then {} else {}", expr1, expr2, expr3.show(tail))
+            }
+            Plus(expr1, expr2) => format!(
+                "{} + {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Minus(expr1, expr2) => format!(
+                "{} - {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Times(expr1, expr2) => format!(
+                "{} * {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Lt(expr1, expr2) => format!(
+                "{} < {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
         }
     }
 }
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.show(true))
+    }
+}
@@ -2,8 +2,8 @@ use crate::eval_ml1::ast::{Expr, Expr::*};
 
 pub fn eval(expr: &Expr) -> Expr {
     match expr {
-        Int(i) => Int(i.clone()),
-        Bool(b) => Bool(b.clone()),
+        Int(i) => Int(*i),
+        Bool(b) => Bool(*b),
         If(expr1, expr2, expr3) => match eval(expr1) {
             Bool(true) => eval(expr2),
             Bool(false) => eval(expr3),
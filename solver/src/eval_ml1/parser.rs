@@ -1,17 +1,18 @@
 use crate::{
     eval_ml1::ast::{Expr, Expr::*},
-    util::ws,
+    syntax::{
+        error::{complete, expected, IResult, ParseError},
+        lexer::{int, token, tokens, Token},
+    },
 };
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{char, digit1},
-    combinator::{opt, recognize},
+    combinator::{cut, opt},
     multi::many0,
-    sequence::{delimited, tuple},
-    IResult,
+    sequence::{delimited, preceded, tuple},
 };
 
+// 字句は syntax::lexer で区切る
 // <expr> ::= <term1> [ <' <term1> ]
 // <term1> ::= <term2> [ ( '+' | '-' ) <term2> ]*
 // <term2> ::= <factor> [ '*' <factor> ]*
@@ -22,12 +23,20 @@ use nom::{
 // <paren> ::= '(' <expr> ')'
 // <if> ::= 'if' <expr> 'then' <expr> 'else' <expr>
 
-pub fn parse(input: &str) -> IResult<&str, Expr> {
-    parse_expr(input)
+pub fn parse(input: &str) -> Result<Expr, ParseError> {
+    complete(input, parse_expr)
 }
 
-fn parse_expr(input: &str) -> IResult<&str, Expr> {
-    let (input, (expr1, expr2)) = tuple((parse_term1, opt(parse_lt)))(input)?;
+pub fn parse_expr(input: &str) -> IResult<&str, Expr> {
+    tokens(expr)(input)
+}
+
+pub fn parse_value(input: &str) -> IResult<&str, Expr> {
+    tokens(value)(input)
+}
+
+fn expr(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (expr1, expr2)) = tuple((term1, opt(lt)))(input)?;
     let expr = match expr2 {
         Some(expr2) => Lt(Box::new(expr1), Box::new(expr2)),
         None => expr1,
@@ -35,103 +44,67 @@ fn parse_expr(input: &str) -> IResult<&str, Expr> {
     Ok((input, expr))
 }
 
-fn parse_lt(input: &str) -> IResult<&str, Expr> {
-    let (input, (_, expr)) = tuple((ws(char('<')), parse_term1))(input)?;
-    Ok((input, expr))
+// 演算子を読んだら右辺は省略できない
+fn lt(input: &[Token]) -> IResult<&[Token], Expr> {
+    preceded(token("<"), cut(term1))(input)
 }
 
-fn parse_term1(input: &str) -> IResult<&str, Expr> {
-    let (input, (expr, exprs)) = tuple((parse_term2, parse_plus_minus))(input)?;
-    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
-        '+' => Plus(Box::new(expr1), Box::new(expr2.clone())),
-        '-' => Minus(Box::new(expr1), Box::new(expr2.clone())),
+fn term1(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (expr, exprs)) = tuple((term2, plus_minus))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match *op {
+        "+" => Plus(Box::new(expr1), Box::new(expr2.clone())),
+        "-" => Minus(Box::new(expr1), Box::new(expr2.clone())),
         _ => unreachable!(),
     });
     Ok((input, expr))
 }
 
-fn parse_plus_minus(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
-    let parse_plus = ws(char('+'));
-    let parse_minus = ws(char('-'));
-    let parse_op = alt((parse_plus, parse_minus));
-    let (input, exprs) = many0(tuple((parse_op, parse_term2)))(input)?;
-    Ok((input, exprs))
+fn plus_minus(input: &[Token]) -> IResult<&[Token], Vec<(&str, Expr)>> {
+    let op = alt((token("+"), token("-")));
+    many0(tuple((op, cut(term2))))(input)
 }
 
-fn parse_term2(input: &str) -> IResult<&str, Expr> {
-    let (input, (expr, exprs)) = tuple((parse_factor, parse_times))(input)?;
-    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
-        '*' => Times(Box::new(expr1), Box::new(expr2.clone())),
+fn term2(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (expr, exprs)) = tuple((factor, times))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match *op {
+        "*" => Times(Box::new(expr1), Box::new(expr2.clone())),
         _ => unreachable!(),
     });
     Ok((input, expr))
 }
 
-fn parse_times(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
-    let parse_times = ws(char('*'));
-    let (input, exprs) = many0(tuple((parse_times, parse_factor)))(input)?;
-    Ok((input, exprs))
-}
-
-fn parse_factor(input: &str) -> IResult<&str, Expr> {
-    let (input, expr) = alt((parse_value, parse_paren, parse_if))(input)?;
-    Ok((input, expr))
-}
-
-fn parse_value(input: &str) -> IResult<&str, Expr> {
-    let (input, expr) = alt((parse_int, parse_bool))(input)?;
-    Ok((input, expr))
-}
-
-fn parse_int(input: &str) -> IResult<&str, Expr> {
-    let (input, i) = alt((ws(parse_pos_number), ws(parse_neg_number)))(input)?;
-    let expr = Int(i);
-    Ok((input, expr))
+fn times(input: &[Token]) -> IResult<&[Token], Vec<(&str, Expr)>> {
+    many0(tuple((token("*"), cut(factor))))(input)
 }
 
-fn parse_pos_number(input: &str) -> IResult<&str, i64> {
-    let (input, i) = digit1(input)?;
-    let i = i.parse::<i64>().unwrap();
-    Ok((input, i))
+fn factor(input: &[Token]) -> IResult<&[Token], Expr> {
+    expected("expression", alt((value, paren, if_expr)))(input)
 }
 
-fn parse_neg_number(input: &str) -> IResult<&str, i64> {
-    let (input, i) = recognize(tuple((char('-'), digit1)))(input)?;
-    let i = i.parse::<i64>().unwrap();
-    Ok((input, i))
-}
-
-fn parse_bool(input: &str) -> IResult<&str, Expr> {
-    let (input, b) = alt((parse_true, parse_false))(input)?;
-    let expr = Bool(b);
+fn value(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, expr) = alt((int_value, bool_value))(input)?;
     Ok((input, expr))
 }
 
-fn parse_true(input: &str) -> IResult<&str, bool> {
-    let (input, _) = ws(tag("true"))(input)?;
-    Ok((input, true))
+fn int_value(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, i) = int(input)?;
+    Ok((input, Int(i)))
 }
 
-fn parse_false(input: &str) -> IResult<&str, bool> {
-    let (input, _) = ws(tag("false"))(input)?;
-    Ok((input, false))
+fn bool_value(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, b) = alt((token("true"), token("false")))(input)?;
+    Ok((input, Bool(b == "true")))
 }
 
-fn parse_paren(input: &str) -> IResult<&str, Expr> {
-    let parse_lparen = ws(char('('));
-    let parse_rparen = ws(char(')'));
-    let (input, expr) = delimited(parse_lparen, parse_expr, parse_rparen)(input)?;
-    Ok((input, expr))
+fn paren(input: &[Token]) -> IResult<&[Token], Expr> {
+    delimited(token("("), cut(expr), cut(token(")")))(input)
 }
 
-fn parse_if(input: &str) -> IResult<&str, Expr> {
-    let (input, (_, expr1, _, expr2, _, expr3)) = tuple((
-        ws(tag("if")),
-        parse_expr,
-        ws(tag("then")),
-        parse_expr,
-        ws(tag("else")),
-        parse_expr,
+// 予約語を読んだら残りは省略できない
+fn if_expr(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (_, (expr1, _, expr2, _, expr3))) = tuple((
+        token("if"),
+        cut(tuple((expr, token("then"), expr, token("else"), expr))),
     ))(input)?;
     let expr = If(Box::new(expr1), Box::new(expr2), Box::new(expr3));
     Ok((input, expr))
@@ -144,7 +117,7 @@ mod tests {
     #[test]
     fn test_parse1() {
         assert_eq!(
-            parse("3 + 5").unwrap().1,
+            parse("3 + 5").unwrap(),
             Plus(Box::new(Int(3)), Box::new(Int(5)))
         );
     }
@@ -152,7 +125,7 @@ mod tests {
     #[test]
     fn test_parse2() {
         assert_eq!(
-            parse("8 - 2 - 3").unwrap().1,
+            parse("8 - 2 - 3").unwrap(),
             Minus(
                 Box::new(Minus(Box::new(Int(8)), Box::new(Int(2)))),
                 Box::new(Int(3))
@@ -163,7 +136,7 @@ mod tests {
     #[test]
     fn test_parse3() {
         assert_eq!(
-            parse("(4 + 5) * (1 - 10)").unwrap().1,
+            parse("(4 + 5) * (1 - 10)").unwrap(),
             Times(
                 Box::new(Plus(Box::new(Int(4)), Box::new(Int(5)))),
                 Box::new(Minus(Box::new(Int(1)), Box::new(Int(10))))
@@ -174,7 +147,7 @@ mod tests {
     #[test]
     fn test_parse4() {
         assert_eq!(
-            parse("if 4 < 5 then 2 + 3 else 8 * 8").unwrap().1,
+            parse("if 4 < 5 then 2 + 3 else 8 * 8").unwrap(),
             If(
                 Box::new(Lt(Box::new(Int(4)), Box::new(Int(5)))),
                 Box::new(Plus(Box::new(Int(2)), Box::new(Int(3)))),
@@ -186,7 +159,7 @@ mod tests {
     #[test]
     fn test_parse5() {
         assert_eq!(
-            parse("3 + if -23 < -2 * 8 then 8 else 2 + 4").unwrap().1,
+            parse("3 + if -23 < -2 * 8 then 8 else 2 + 4").unwrap(),
             Plus(
                 Box::new(Int(3)),
                 Box::new(If(
@@ -204,7 +177,7 @@ mod tests {
     #[test]
     fn test_parse6() {
         assert_eq!(
-            parse("3 + (if -23 < -2 * 8 then 8 else 2) + 4").unwrap().1,
+            parse("3 + (if -23 < -2 * 8 then 8 else 2) + 4").unwrap(),
             Plus(
                 Box::new(Plus(
                     Box::new(Int(3)),
@@ -221,4 +194,53 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_parse_error() {
+        let e = parse("if 3 < 4 tehn 1 else 2").unwrap_err();
+        assert_eq!((e.offset, e.line, e.column), (9, 1, 10));
+        assert_eq!(e.expected, vec!["`then`"]);
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 10: expected `then`\nif 3 < 4 tehn 1 else 2\n         ^"
+        );
+        assert_eq!(
+            parse("(1 +\n 2").unwrap_err().to_string(),
+            "line 2, column 3: expected `)`\n 2\n  ^"
+        );
+        assert_eq!(parse("3 * ").unwrap_err().expected, vec!["expression"]);
+    }
+
+    #[test]
+    fn test_parse_trailing() {
+        let e = parse("3 + 4 )) junk").unwrap_err();
+        assert_eq!(e.column, 7);
+        assert_eq!(e.expected, vec!["end of input"]);
+        assert!(parse("if true then 1 else 2 3").is_err());
+        assert!(parse("(1 + 2) ").is_ok());
+    }
+
+    #[test]
+    fn test_parse_comment() {
+        assert_eq!(
+            parse("(* x *) 1 + (* (* 2 *) *) 3 (* y *)").unwrap(),
+            Plus(Box::new(Int(1)), Box::new(Int(3)))
+        );
+        assert_eq!(parse("1 + (* 2").unwrap_err().expected, vec!["expression"]);
+    }
+
+    #[test]
+    fn test_parse_ext() {
+        // 拡張体系 (EvalML1Ext) の演算子は読まない
+        for input in [
+            "3 / 2",
+            "5 mod 2",
+            "-(1)",
+            "1 = 1",
+            "2 > 1",
+            "true && false",
+        ] {
+            assert!(parse(input).is_err(), "{}", input);
+        }
+    }
 }
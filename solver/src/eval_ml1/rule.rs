@@ -25,8 +25,8 @@ impl Rule {
         use crate::eval_ml1::rule::Rule::*;
 
         match self {
-            EInt(i, _) => Int(i.clone()),
-            EBool(b, _) => Bool(b.clone()),
+            EInt(i, _) => Int(*i),
+            EBool(b, _) => Bool(*b),
             EIfT(_, _, _, _, rule2, _) => rule2.evaluated(),
             EIfF(_, _, _, _, rule2, _) => rule2.evaluated(),
             EPlus(_, _, _, _, rule3, _) => rule3.evaluated(),
@@ -77,12 +77,12 @@ impl fmt::Display for Rule {
                 write!(f, "{}}}", ident(*depth))
             }
             EPlus(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
                 writeln!(
                     f,
-                    "{}{} + {} evalto {} by E-Plus {{",
+                    "{}{} evalto {} by E-Plus {{",
                     ident(*depth),
-                    expr1,
-                    expr2,
+                    expr,
                     self.evaluated()
                 )?;
                 writeln!(f, "{};", rule1)?;
@@ -91,12 +91,12 @@ impl fmt::Display for Rule {
                 write!(f, "{}}}", ident(*depth))
             }
             EMinus(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
                 writeln!(
                     f,
-                    "{}{} - {} evalto {} by E-Minus {{",
+                    "{}{} evalto {} by E-Minus {{",
                     ident(*depth),
-                    expr1,
-                    expr2,
+                    expr,
                     self.evaluated()
                 )?;
                 writeln!(f, "{};", rule1)?;
@@ -105,12 +105,12 @@ impl fmt::Display for Rule {
                 write!(f, "{}}}", ident(*depth))
             }
             ETimes(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
                 writeln!(
                     f,
-                    "{}{} * {} evalto {} by E-Times {{",
+                    "{}{} evalto {} by E-Times {{",
                     ident(*depth),
-                    expr1,
-                    expr2,
+                    expr,
                     self.evaluated()
                 )?;
                 writeln!(f, "{};", rule1)?;
@@ -119,12 +119,12 @@ impl fmt::Display for Rule {
                 write!(f, "{}}}", ident(*depth))
             }
             ELt(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
                 writeln!(
                     f,
-                    "{}{} < {} evalto {} by E-Lt {{",
+                    "{}{} evalto {} by E-Lt {{",
                     ident(*depth),
-                    expr1,
-                    expr2,
+                    expr,
                     self.evaluated()
                 )?;
                 writeln!(f, "{};", rule1)?;
@@ -1,17 +1,19 @@
+use crate::eval_error::{check_depth, checked, EvalError};
 use crate::eval_ml1::{
     ast::{Expr, Expr::*},
     rule::{Rule, Rule::*},
 };
 
-pub fn solve(expr: &Expr, depth: usize) -> Rule {
-    match expr {
-        Int(i) => EInt(i.clone(), depth),
-        Bool(b) => EBool(b.clone(), depth),
+pub fn solve(expr: &Expr, depth: usize) -> Result<Rule, EvalError> {
+    check_depth(depth)?;
+    let rule = match expr {
+        Int(i) => EInt(*i, depth),
+        Bool(b) => EBool(*b, depth),
         If(expr1, expr2, expr3) => {
-            let rule1 = solve(expr1, depth + 1);
+            let rule1 = solve(expr1, depth + 1)?;
             match rule1.evaluated() {
                 Bool(true) => {
-                    let rule2 = solve(expr2, depth + 1);
+                    let rule2 = solve(expr2, depth + 1)?;
                     EIfT(
                         *expr1.clone(),
                         *expr2.clone(),
@@ -22,7 +24,7 @@ pub fn solve(expr: &Expr, depth: usize) -> Rule {
                     )
                 }
                 Bool(false) => {
-                    let rule2 = solve(expr3, depth + 1);
+                    let rule2 = solve(expr3, depth + 1)?;
                     EIfF(
                         *expr1.clone(),
                         *expr2.clone(),
@@ -32,15 +34,15 @@ pub fn solve(expr: &Expr, depth: usize) -> Rule {
                         depth,
                     )
                 }
-                _ => unreachable!(),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             }
         }
         Plus(expr1, expr2) => {
-            let rule1 = solve(expr1, depth + 1);
-            let rule2 = solve(expr2, depth + 1);
+            let rule1 = solve(expr1, depth + 1)?;
+            let rule2 = solve(expr2, depth + 1)?;
             let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
-                (Int(i1), Int(i2)) => Int(i1 + i2),
-                _ => unreachable!(),
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_add(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             };
             let rule3 = BPlus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
             EPlus(
@@ -53,11 +55,11 @@ pub fn solve(expr: &Expr, depth: usize) -> Rule {
             )
         }
         Minus(expr1, expr2) => {
-            let rule1 = solve(expr1, depth + 1);
-            let rule2 = solve(expr2, depth + 1);
+            let rule1 = solve(expr1, depth + 1)?;
+            let rule2 = solve(expr2, depth + 1)?;
             let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
-                (Int(i1), Int(i2)) => Int(i1 - i2),
-                _ => unreachable!(),
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_sub(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             };
             let rule3 = BMinus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
             EMinus(
@@ -70,11 +72,11 @@ pub fn solve(expr: &Expr, depth: usize) -> Rule {
             )
         }
         Times(expr1, expr2) => {
-            let rule1 = solve(expr1, depth + 1);
-            let rule2 = solve(expr2, depth + 1);
+            let rule1 = solve(expr1, depth + 1)?;
+            let rule2 = solve(expr2, depth + 1)?;
             let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
-                (Int(i1), Int(i2)) => Int(i1 * i2),
-                _ => unreachable!(),
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_mul(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             };
             let rule3 = BTimes(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
             ETimes(
@@ -87,11 +89,11 @@ pub fn solve(expr: &Expr, depth: usize) -> Rule {
             )
         }
         Lt(expr1, expr2) => {
-            let rule1 = solve(expr1, depth + 1);
-            let rule2 = solve(expr2, depth + 1);
+            let rule1 = solve(expr1, depth + 1)?;
+            let rule2 = solve(expr2, depth + 1)?;
             let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
                 (Int(i1), Int(i2)) => Bool(i1 < i2),
-                _ => unreachable!(),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             };
             let rule3 = BLt(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
             ELt(
@@ -103,7 +105,8 @@ pub fn solve(expr: &Expr, depth: usize) -> Rule {
                 depth,
             )
         }
-    }
+    };
+    Ok(rule)
 }
 
 #[cfg(test)]
@@ -113,7 +116,7 @@ mod tests {
     #[test]
     fn test_solve1() {
         assert_eq!(
-            solve(&Plus(Box::new(Int(3)), Box::new(Int(5))), 0),
+            solve(&Plus(Box::new(Int(3)), Box::new(Int(5))), 0).unwrap(),
             EPlus(
                 Int(3),
                 Int(5),
@@ -134,7 +137,8 @@ mod tests {
                     Box::new(Int(3))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EMinus(
                 Minus(Box::new(Int(8)), Box::new(Int(2))),
                 Int(3),
@@ -162,7 +166,8 @@ mod tests {
                     Box::new(Minus(Box::new(Int(1)), Box::new(Int(10))))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             ETimes(
                 Plus(Box::new(Int(4)), Box::new(Int(5))),
                 Minus(Box::new(Int(1)), Box::new(Int(10))),
@@ -198,7 +203,8 @@ mod tests {
                     Box::new(Times(Box::new(Int(8)), Box::new(Int(8))))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EIfT(
                 Lt(Box::new(Int(4)), Box::new(Int(5))),
                 Plus(Box::new(Int(2)), Box::new(Int(3))),
@@ -240,7 +246,8 @@ mod tests {
                     ))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EPlus(
                 Int(3),
                 If(
@@ -302,7 +309,8 @@ mod tests {
                     Box::new(Int(4))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EPlus(
                 Plus(
                     Box::new(Int(3)),
@@ -0,0 +1,163 @@
+use crate::{
+    eval_error::{checked, EvalError},
+    eval_ml1_err::ast::{Expr, Expr::*},
+};
+
+pub fn eval(expr: &Expr) -> Result<Expr, EvalError> {
+    let value = match expr {
+        Int(i) => Int(*i),
+        Bool(b) => Bool(*b),
+        If(expr1, expr2, expr3) => match eval(expr1)? {
+            Bool(true) => eval(expr2)?,
+            Bool(false) => eval(expr3)?,
+            _ => Error,
+        },
+        Plus(expr1, expr2) => match (eval(expr1)?, eval(expr2)?) {
+            (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_add(i2))?),
+            _ => Error,
+        },
+        Minus(expr1, expr2) => match (eval(expr1)?, eval(expr2)?) {
+            (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_sub(i2))?),
+            _ => Error,
+        },
+        Times(expr1, expr2) => match (eval(expr1)?, eval(expr2)?) {
+            (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_mul(i2))?),
+            _ => Error,
+        },
+        Lt(expr1, expr2) => match (eval(expr1)?, eval(expr2)?) {
+            (Int(i1), Int(i2)) => Bool(i1 < i2),
+            _ => Error,
+        },
+        Error => Error,
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eval_error::EvalError,
+        eval_ml1_err::{ast::Expr::*, evaluator::eval},
+    };
+
+    #[test]
+    fn test_eval1() {
+        assert_eq!(eval(&Plus(Box::new(Int(3)), Box::new(Int(5)))), Ok(Int(8)));
+    }
+
+    #[test]
+    fn test_eval2() {
+        assert_eq!(
+            eval(&Minus(
+                Box::new(Minus(Box::new(Int(8)), Box::new(Int(2)))),
+                Box::new(Int(3))
+            )),
+            Ok(Int(3))
+        );
+    }
+
+    #[test]
+    fn test_eval3() {
+        assert_eq!(
+            eval(&Times(
+                Box::new(Plus(Box::new(Int(4)), Box::new(Int(5)))),
+                Box::new(Minus(Box::new(Int(1)), Box::new(Int(10))))
+            )),
+            Ok(Int(-81))
+        );
+    }
+
+    #[test]
+    fn test_eval4() {
+        assert_eq!(
+            eval(&If(
+                Box::new(Lt(Box::new(Int(4)), Box::new(Int(5)))),
+                Box::new(Plus(Box::new(Int(2)), Box::new(Int(3)))),
+                Box::new(Times(Box::new(Int(8)), Box::new(Int(8))))
+            )),
+            Ok(Int(5))
+        );
+    }
+
+    #[test]
+    fn test_eval5() {
+        assert_eq!(
+            eval(&Plus(
+                Box::new(Int(3)),
+                Box::new(If(
+                    Box::new(Lt(
+                        Box::new(Int(-23)),
+                        Box::new(Times(Box::new(Int(-2)), Box::new(Int(8))))
+                    )),
+                    Box::new(Int(8)),
+                    Box::new(Plus(Box::new(Int(2)), Box::new(Int(4))))
+                ))
+            )),
+            Ok(Int(11))
+        );
+    }
+
+    #[test]
+    fn test_eval6() {
+        assert_eq!(
+            eval(&Plus(
+                Box::new(Plus(
+                    Box::new(Int(3)),
+                    Box::new(If(
+                        Box::new(Lt(
+                            Box::new(Int(-23)),
+                            Box::new(Times(Box::new(Int(-2)), Box::new(Int(8))))
+                        )),
+                        Box::new(Int(8)),
+                        Box::new(Int(2))
+                    ))
+                )),
+                Box::new(Int(4))
+            )),
+            Ok(Int(15))
+        );
+    }
+
+    #[test]
+    fn test_eval7() {
+        assert_eq!(
+            eval(&Plus(
+                Box::new(Plus(Box::new(Int(1)), Box::new(Bool(true)))),
+                Box::new(Int(2))
+            )),
+            Ok(Error)
+        );
+    }
+
+    #[test]
+    fn test_eval8() {
+        assert_eq!(
+            eval(&If(
+                Box::new(Plus(Box::new(Int(2)), Box::new(Int(3)))),
+                Box::new(Int(1)),
+                Box::new(Int(3))
+            )),
+            Ok(Error)
+        );
+    }
+
+    #[test]
+    fn test_eval9() {
+        assert_eq!(
+            eval(&If(
+                Box::new(Lt(Box::new(Int(4)), Box::new(Int(5)))),
+                Box::new(Int(1)),
+                Box::new(Minus(Box::new(Int(3)), Box::new(Bool(false))))
+            )),
+            Ok(Int(1))
+        );
+    }
+
+    #[test]
+    fn test_eval_overflow() {
+        assert_eq!(
+            eval(&Times(Box::new(Int(9223372036854775807)), Box::new(Int(2)))),
+            Err(EvalError::Overflow("9223372036854775807 * 2".to_string()))
+        );
+    }
+}
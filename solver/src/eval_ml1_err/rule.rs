@@ -0,0 +1,423 @@
+use crate::{
+    eval_ml1_err::ast::{Expr, Expr::*},
+    util::ident,
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    EInt(i64, usize),
+    EBool(bool, usize),
+    EIfT(Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EIfF(Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EPlus(Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    EMinus(Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ETimes(Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ELt(Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    BPlus(Expr, Expr, Expr, usize),
+    BMinus(Expr, Expr, Expr, usize),
+    BTimes(Expr, Expr, Expr, usize),
+    BLt(Expr, Expr, Expr, usize),
+    EIfInt(Expr, Expr, Expr, Box<Rule>, usize),
+    EIfError(Expr, Expr, Expr, Box<Rule>, usize),
+    EIfTError(Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EIfFError(Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EPlusBoolL(Expr, Expr, Box<Rule>, usize),
+    EPlusBoolR(Expr, Expr, Box<Rule>, usize),
+    EPlusErrorL(Expr, Expr, Box<Rule>, usize),
+    EPlusErrorR(Expr, Expr, Box<Rule>, usize),
+    EMinusBoolL(Expr, Expr, Box<Rule>, usize),
+    EMinusBoolR(Expr, Expr, Box<Rule>, usize),
+    EMinusErrorL(Expr, Expr, Box<Rule>, usize),
+    EMinusErrorR(Expr, Expr, Box<Rule>, usize),
+    ETimesBoolL(Expr, Expr, Box<Rule>, usize),
+    ETimesBoolR(Expr, Expr, Box<Rule>, usize),
+    ETimesErrorL(Expr, Expr, Box<Rule>, usize),
+    ETimesErrorR(Expr, Expr, Box<Rule>, usize),
+    ELtBoolL(Expr, Expr, Box<Rule>, usize),
+    ELtBoolR(Expr, Expr, Box<Rule>, usize),
+    ELtErrorL(Expr, Expr, Box<Rule>, usize),
+    ELtErrorR(Expr, Expr, Box<Rule>, usize),
+}
+
+impl Rule {
+    pub fn evaluated(&self) -> Expr {
+        use crate::eval_ml1_err::rule::Rule::*;
+
+        match self {
+            EInt(i, _) => Int(*i),
+            EBool(b, _) => Bool(*b),
+            EIfT(_, _, _, _, rule2, _) => rule2.evaluated(),
+            EIfF(_, _, _, _, rule2, _) => rule2.evaluated(),
+            EPlus(_, _, _, _, rule3, _) => rule3.evaluated(),
+            EMinus(_, _, _, _, rule3, _) => rule3.evaluated(),
+            ETimes(_, _, _, _, rule3, _) => rule3.evaluated(),
+            ELt(_, _, _, _, rule3, _) => rule3.evaluated(),
+            BPlus(_, _, expr3, _) => expr3.clone(),
+            BMinus(_, _, expr3, _) => expr3.clone(),
+            BTimes(_, _, expr3, _) => expr3.clone(),
+            BLt(_, _, expr3, _) => expr3.clone(),
+            EIfInt(_, _, _, _, _) => Error,
+            EIfError(_, _, _, _, _) => Error,
+            EIfTError(_, _, _, _, _, _) => Error,
+            EIfFError(_, _, _, _, _, _) => Error,
+            EPlusBoolL(_, _, _, _) => Error,
+            EPlusBoolR(_, _, _, _) => Error,
+            EPlusErrorL(_, _, _, _) => Error,
+            EPlusErrorR(_, _, _, _) => Error,
+            EMinusBoolL(_, _, _, _) => Error,
+            EMinusBoolR(_, _, _, _) => Error,
+            EMinusErrorL(_, _, _, _) => Error,
+            EMinusErrorR(_, _, _, _) => Error,
+            ETimesBoolL(_, _, _, _) => Error,
+            ETimesBoolR(_, _, _, _) => Error,
+            ETimesErrorL(_, _, _, _) => Error,
+            ETimesErrorR(_, _, _, _) => Error,
+            ELtBoolL(_, _, _, _) => Error,
+            ELtBoolR(_, _, _, _) => Error,
+            ELtErrorL(_, _, _, _) => Error,
+            ELtErrorR(_, _, _, _) => Error,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_ml1_err::rule::Rule::*;
+
+        match self {
+            EInt(i, depth) => write!(f, "{}{} evalto {} by E-Int {{}}", ident(*depth), i, i),
+            EBool(b, depth) => write!(f, "{}{} evalto {} by E-Bool {{}}", ident(*depth), b, b),
+            EIfT(expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}if {} then {} else {} evalto {} by E-IfT {{",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EIfF(expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}if {} then {} else {} evalto {} by E-IfF {{",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EPlus(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Plus {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMinus(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Minus {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ETimes(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Times {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELt(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Lt {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            BPlus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} plus {} is {} by B-Plus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BMinus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} minus {} is {} by B-Minus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BTimes(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} times {} is {} by B-Times {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BLt(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} less than {} is {} by B-Lt {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            EIfInt(expr1, expr2, expr3, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}if {} then {} else {} evalto error by E-IfInt {{",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EIfError(expr1, expr2, expr3, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}if {} then {} else {} evalto error by E-IfError {{",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EIfTError(expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}if {} then {} else {} evalto error by E-IfTError {{",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EIfFError(expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}if {} then {} else {} evalto error by E-IfFError {{",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EPlusBoolL(expr1, expr2, rule, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto error by E-PlusBoolL {{",
+                    ident(*depth),
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EPlusBoolR(expr1, expr2, rule, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto error by E-PlusBoolR {{",
+                    ident(*depth),
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EPlusErrorL(expr1, expr2, rule, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto error by E-PlusErrorL {{",
+                    ident(*depth),
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EPlusErrorR(expr1, expr2, rule, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto error by E-PlusErrorR {{",
+                    ident(*depth),
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMinusBoolL(expr1, expr2, rule, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto error by E-MinusBoolL {{",
+                    ident(*depth),
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMinusBoolR(expr1, expr2, rule, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto error by E-MinusBoolR {{",
+                    ident(*depth),
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMinusErrorL(expr1, expr2, rule, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto error by E-MinusErrorL {{",
+                    ident(*depth),
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMinusErrorR(expr1, expr2, rule, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto error by E-MinusErrorR {{",
+                    ident(*depth),
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ETimesBoolL(expr1, expr2, rule, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto error by E-TimesBoolL {{",
+                    ident(*depth),
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ETimesBoolR(expr1, expr2, rule, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto error by E-TimesBoolR {{",
+                    ident(*depth),
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ETimesErrorL(expr1, expr2, rule, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto error by E-TimesErrorL {{",
+                    ident(*depth),
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ETimesErrorR(expr1, expr2, rule, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto error by E-TimesErrorR {{",
+                    ident(*depth),
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELtBoolL(expr1, expr2, rule, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(f, "{}{} evalto error by E-LtBoolL {{", ident(*depth), expr)?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELtBoolR(expr1, expr2, rule, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(f, "{}{} evalto error by E-LtBoolR {{", ident(*depth), expr)?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELtErrorL(expr1, expr2, rule, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(f, "{}{} evalto error by E-LtErrorL {{", ident(*depth), expr)?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELtErrorR(expr1, expr2, rule, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(f, "{}{} evalto error by E-LtErrorR {{", ident(*depth), expr)?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+        }
+    }
+}
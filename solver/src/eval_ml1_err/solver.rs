@@ -0,0 +1,424 @@
+use crate::eval_error::{check_depth, checked, EvalError};
+use crate::eval_ml1_err::{
+    ast::{Expr, Expr::*},
+    rule::{Rule, Rule::*},
+};
+
+pub fn solve(expr: &Expr, depth: usize) -> Result<Rule, EvalError> {
+    check_depth(depth)?;
+    let rule = match expr {
+        Int(i) => EInt(*i, depth),
+        Bool(b) => EBool(*b, depth),
+        If(expr1, expr2, expr3) => {
+            let rule1 = solve(expr1, depth + 1)?;
+            match rule1.evaluated() {
+                Bool(true) => {
+                    let rule2 = solve(expr2, depth + 1)?;
+                    match rule2.evaluated() {
+                        Error => EIfTError(
+                            *expr1.clone(),
+                            *expr2.clone(),
+                            *expr3.clone(),
+                            Box::new(rule1),
+                            Box::new(rule2),
+                            depth,
+                        ),
+                        _ => EIfT(
+                            *expr1.clone(),
+                            *expr2.clone(),
+                            *expr3.clone(),
+                            Box::new(rule1),
+                            Box::new(rule2),
+                            depth,
+                        ),
+                    }
+                }
+                Bool(false) => {
+                    let rule2 = solve(expr3, depth + 1)?;
+                    match rule2.evaluated() {
+                        Error => EIfFError(
+                            *expr1.clone(),
+                            *expr2.clone(),
+                            *expr3.clone(),
+                            Box::new(rule1),
+                            Box::new(rule2),
+                            depth,
+                        ),
+                        _ => EIfF(
+                            *expr1.clone(),
+                            *expr2.clone(),
+                            *expr3.clone(),
+                            Box::new(rule1),
+                            Box::new(rule2),
+                            depth,
+                        ),
+                    }
+                }
+                Int(_) => EIfInt(
+                    *expr1.clone(),
+                    *expr2.clone(),
+                    *expr3.clone(),
+                    Box::new(rule1),
+                    depth,
+                ),
+                Error => EIfError(
+                    *expr1.clone(),
+                    *expr2.clone(),
+                    *expr3.clone(),
+                    Box::new(rule1),
+                    depth,
+                ),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            }
+        }
+        Plus(expr1, expr2) => {
+            let rule1 = solve(expr1, depth + 1)?;
+            match rule1.evaluated() {
+                Bool(_) => {
+                    return Ok(EPlusBoolL(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        depth,
+                    ))
+                }
+                Error => {
+                    return Ok(EPlusErrorL(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        depth,
+                    ))
+                }
+                _ => {}
+            }
+            let rule2 = solve(expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_add(i2))?),
+                (_, Bool(_)) => {
+                    return Ok(EPlusBoolR(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule2),
+                        depth,
+                    ))
+                }
+                (_, Error) => {
+                    return Ok(EPlusErrorR(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule2),
+                        depth,
+                    ))
+                }
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BPlus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            EPlus(
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Minus(expr1, expr2) => {
+            let rule1 = solve(expr1, depth + 1)?;
+            match rule1.evaluated() {
+                Bool(_) => {
+                    return Ok(EMinusBoolL(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        depth,
+                    ))
+                }
+                Error => {
+                    return Ok(EMinusErrorL(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        depth,
+                    ))
+                }
+                _ => {}
+            }
+            let rule2 = solve(expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_sub(i2))?),
+                (_, Bool(_)) => {
+                    return Ok(EMinusBoolR(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule2),
+                        depth,
+                    ))
+                }
+                (_, Error) => {
+                    return Ok(EMinusErrorR(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule2),
+                        depth,
+                    ))
+                }
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BMinus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            EMinus(
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Times(expr1, expr2) => {
+            let rule1 = solve(expr1, depth + 1)?;
+            match rule1.evaluated() {
+                Bool(_) => {
+                    return Ok(ETimesBoolL(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        depth,
+                    ))
+                }
+                Error => {
+                    return Ok(ETimesErrorL(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        depth,
+                    ))
+                }
+                _ => {}
+            }
+            let rule2 = solve(expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_mul(i2))?),
+                (_, Bool(_)) => {
+                    return Ok(ETimesBoolR(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule2),
+                        depth,
+                    ))
+                }
+                (_, Error) => {
+                    return Ok(ETimesErrorR(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule2),
+                        depth,
+                    ))
+                }
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BTimes(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            ETimes(
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Lt(expr1, expr2) => {
+            let rule1 = solve(expr1, depth + 1)?;
+            match rule1.evaluated() {
+                Bool(_) => {
+                    return Ok(ELtBoolL(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        depth,
+                    ))
+                }
+                Error => {
+                    return Ok(ELtErrorL(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        depth,
+                    ))
+                }
+                _ => {}
+            }
+            let rule2 = solve(expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Bool(i1 < i2),
+                (_, Bool(_)) => {
+                    return Ok(ELtBoolR(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule2),
+                        depth,
+                    ))
+                }
+                (_, Error) => {
+                    return Ok(ELtErrorR(
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule2),
+                        depth,
+                    ))
+                }
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BLt(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            ELt(
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Error => return Err(EvalError::Stuck(expr.to_string())),
+    };
+    Ok(rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_ml1_err::{ast::Expr::*, rule::Rule::*, solver::solve};
+
+    #[test]
+    fn test_solve1() {
+        assert_eq!(
+            solve(&Plus(Box::new(Int(3)), Box::new(Int(5))), 0).unwrap(),
+            EPlus(
+                Int(3),
+                Int(5),
+                Box::new(EInt(3, 1)),
+                Box::new(EInt(5, 1)),
+                Box::new(BPlus(Int(3), Int(5), Int(8), 1)),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve2() {
+        assert_eq!(
+            solve(
+                &Plus(
+                    Box::new(Plus(Box::new(Int(1)), Box::new(Bool(true)))),
+                    Box::new(Int(2))
+                ),
+                0
+            )
+            .unwrap(),
+            EPlusErrorL(
+                Plus(Box::new(Int(1)), Box::new(Bool(true))),
+                Int(2),
+                Box::new(EPlusBoolR(Int(1), Bool(true), Box::new(EBool(true, 2)), 1)),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve3() {
+        assert_eq!(
+            solve(
+                &If(
+                    Box::new(Plus(Box::new(Int(2)), Box::new(Int(3)))),
+                    Box::new(Int(1)),
+                    Box::new(Int(3))
+                ),
+                0
+            )
+            .unwrap(),
+            EIfInt(
+                Plus(Box::new(Int(2)), Box::new(Int(3))),
+                Int(1),
+                Int(3),
+                Box::new(EPlus(
+                    Int(2),
+                    Int(3),
+                    Box::new(EInt(2, 2)),
+                    Box::new(EInt(3, 2)),
+                    Box::new(BPlus(Int(2), Int(3), Int(5), 2)),
+                    1
+                )),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve4() {
+        assert_eq!(
+            solve(
+                &If(
+                    Box::new(Lt(Box::new(Int(3)), Box::new(Int(4)))),
+                    Box::new(Lt(Box::new(Int(1)), Box::new(Bool(true)))),
+                    Box::new(Minus(Box::new(Int(3)), Box::new(Bool(false))))
+                ),
+                0
+            )
+            .unwrap(),
+            EIfTError(
+                Lt(Box::new(Int(3)), Box::new(Int(4))),
+                Lt(Box::new(Int(1)), Box::new(Bool(true))),
+                Minus(Box::new(Int(3)), Box::new(Bool(false))),
+                Box::new(ELt(
+                    Int(3),
+                    Int(4),
+                    Box::new(EInt(3, 2)),
+                    Box::new(EInt(4, 2)),
+                    Box::new(BLt(Int(3), Int(4), Bool(true), 2)),
+                    1
+                )),
+                Box::new(ELtBoolR(Int(1), Bool(true), Box::new(EBool(true, 2)), 1)),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve5() {
+        assert_eq!(
+            solve(
+                &Times(
+                    Box::new(Bool(false)),
+                    Box::new(Plus(Box::new(Int(1)), Box::new(Bool(true))))
+                ),
+                0
+            )
+            .unwrap(),
+            ETimesBoolL(
+                Bool(false),
+                Plus(Box::new(Int(1)), Box::new(Bool(true))),
+                Box::new(EBool(false, 1)),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve6() {
+        assert_eq!(
+            solve(
+                &Plus(
+                    Box::new(Plus(Box::new(Int(1)), Box::new(Bool(true)))),
+                    Box::new(Int(2))
+                ),
+                0
+            )
+            .unwrap()
+            .to_string(),
+            "1 + true + 2 evalto error by E-PlusErrorL {
+    1 + true evalto error by E-PlusBoolR {
+        true evalto true by E-Bool {}
+    }
+}"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Expr {
+    Int(i64),
+    Bool(bool),
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+    Plus(Box<Expr>, Box<Expr>),
+    Minus(Box<Expr>, Box<Expr>),
+    Times(Box<Expr>, Box<Expr>),
+    Lt(Box<Expr>, Box<Expr>),
+    // 以下は EvalML1 にない式
+    Div(Box<Expr>, Box<Expr>),
+    Mod(Box<Expr>, Box<Expr>),
+    Eq(Box<Expr>, Box<Expr>),
+    Gt(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Neg(Box<Expr>),
+}
+
+impl Expr {
+    // 結合の強さ (大きいほど強い)
+    pub(crate) fn precedence(&self) -> usize {
+        use crate::eval_ml1_ext::ast::Expr::*;
+
+        match self {
+            If(_, _, _) => 0,
+            Or(_, _) => 1,
+            And(_, _) => 2,
+            Lt(_, _) | Eq(_, _) | Gt(_, _) => 3,
+            Plus(_, _) | Minus(_, _) => 4,
+            Times(_, _) | Div(_, _) | Mod(_, _) => 5,
+            Neg(_) => 6,
+            _ => 7,
+        }
+    }
+
+    // 右にできるだけ伸びる式 (右端にあれば括弧がいらない)
+    pub(crate) fn is_open(&self) -> bool {
+        use crate::eval_ml1_ext::ast::Expr::*;
+
+        matches!(self, If(_, _, _))
+    }
+
+    pub(crate) fn paren(&self, paren: bool) -> String {
+        if paren {
+            format!("({})", self)
+        } else {
+            self.to_string()
+        }
+    }
+
+    // tail は後ろに何も続かないこと
+    fn show(&self, tail: bool) -> String {
+        use crate::eval_ml1_ext::ast::Expr::*;
+
+        let p = self.precedence();
+        let left = |expr: &Expr, paren: bool| {
+            if paren {
+                expr.paren(true)
+            } else {
+                expr.show(false)
+            }
+        };
+        let right = |expr: &Expr, paren: bool| {
+            if paren && !(tail && expr.is_open()) {
+                expr.paren(true)
+            } else {
+                expr.show(tail)
+            }
+        };
+        match self {
+            Int(i) => i.to_string(),
+            Bool(b) => b.to_string(),
+            If(expr1, expr2, expr3) => {
+                format!("if {} then {} else {}", expr1, expr2, expr3.show(tail))
+            }
+            Plus(expr1, expr2) => format!(
+                "{} + {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Minus(expr1, expr2) => format!(
+                "{} - {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Times(expr1, expr2) => format!(
+                "{} * {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Lt(expr1, expr2) => format!(
+                "{} < {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Div(expr1, expr2) => format!(
+                "{} / {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Mod(expr1, expr2) => format!(
+                "{} mod {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Eq(expr1, expr2) => format!(
+                "{} = {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Gt(expr1, expr2) => format!(
+                "{} > {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            // && と || は右結合
+            And(expr1, expr2) => format!(
+                "{} && {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() < p)
+            ),
+            Or(expr1, expr2) => format!(
+                "{} || {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() < p)
+            ),
+            // 整数の前は空けないと負の整数と区別できない
+            Neg(expr) => match **expr {
+                Int(_) => format!("- {}", expr),
+                _ => format!("-{}", right(expr, expr.precedence() < p)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.show(true))
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{
+    eval_ml1_ext::ast::{Expr, Expr::*},
+    syntax::{
+        error::{complete, expected, IResult, ParseError},
+        lexer::{ext_tokens, int, token, Token},
+    },
+};
+use nom::{
+    branch::alt,
+    combinator::{cut, opt},
+    multi::many0,
+    sequence::{delimited, preceded, tuple},
+};
+
+// 字句は syntax::lexer で拡張体系の予約語と記号を使って区切る
+// <expr> ::= <and> [ '||' <expr> ]
+// <and> ::= <comp> [ '&&' <and> ]
+// <comp> ::= <term1> [ ( '<' | '>' | '=' ) <term1> ]
+// <term1> ::= <term2> [ ( '+' | '-' ) <term2> ]*
+// <term2> ::= <unary> [ ( '*' | '/' | 'mod' ) <unary> ]*
+// <unary> ::= '-' <unary> | <factor>
+// <factor> ::= <value> | <paren> | <if>
+// <value> ::= <int> | <bool>
+// <int> ::= 数値
+// <bool> ::= 'true' | 'false'
+// <paren> ::= '(' <expr> ')'
+// <if> ::= 'if' <expr> 'then' <expr> 'else' <expr>
+
+pub fn parse(input: &str) -> Result<Expr, ParseError> {
+    complete(input, parse_expr)
+}
+
+pub fn parse_expr(input: &str) -> IResult<&str, Expr> {
+    ext_tokens(expr)(input)
+}
+
+pub fn parse_value(input: &str) -> IResult<&str, Expr> {
+    ext_tokens(value)(input)
+}
+
+fn expr(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (expr1, expr2)) = tuple((and, opt(preceded(token("||"), cut(expr)))))(input)?;
+    let expr = match expr2 {
+        Some(expr2) => Or(Box::new(expr1), Box::new(expr2)),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+fn and(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (expr1, expr2)) = tuple((comp, opt(preceded(token("&&"), cut(and)))))(input)?;
+    let expr = match expr2 {
+        Some(expr2) => And(Box::new(expr1), Box::new(expr2)),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+// 演算子を読んだら右辺は省略できない
+fn comp(input: &[Token]) -> IResult<&[Token], Expr> {
+    let op = alt((token("<"), token(">"), token("=")));
+    let (input, (expr1, expr2)) = tuple((term1, opt(tuple((op, cut(term1))))))(input)?;
+    let expr = match expr2 {
+        Some(("<", expr2)) => Lt(Box::new(expr1), Box::new(expr2)),
+        Some((">", expr2)) => Gt(Box::new(expr1), Box::new(expr2)),
+        Some(("=", expr2)) => Eq(Box::new(expr1), Box::new(expr2)),
+        Some(_) => unreachable!(),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+fn term1(input: &[Token]) -> IResult<&[Token], Expr> {
+    let op = alt((token("+"), token("-")));
+    let (input, (expr, exprs)) = tuple((term2, many0(tuple((op, cut(term2))))))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match *op {
+        "+" => Plus(Box::new(expr1), Box::new(expr2.clone())),
+        "-" => Minus(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+fn term2(input: &[Token]) -> IResult<&[Token], Expr> {
+    let op = alt((token("*"), token("/"), token("mod")));
+    let (input, (expr, exprs)) = tuple((unary, many0(tuple((op, cut(unary))))))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match *op {
+        "*" => Times(Box::new(expr1), Box::new(expr2.clone())),
+        "/" => Div(Box::new(expr1), Box::new(expr2.clone())),
+        "mod" => Mod(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+// '-' の直後に数字があれば負の整数として factor で読む
+fn unary(input: &[Token]) -> IResult<&[Token], Expr> {
+    if int(input).is_ok() {
+        return factor(input);
+    }
+    match token("-")(input) {
+        Ok((input, _)) => {
+            let (input, expr) = cut(unary)(input)?;
+            Ok((input, Neg(Box::new(expr))))
+        }
+        Err(_) => factor(input),
+    }
+}
+
+fn factor(input: &[Token]) -> IResult<&[Token], Expr> {
+    expected("expression", alt((value, paren, if_expr)))(input)
+}
+
+fn value(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, expr) = alt((int_value, bool_value))(input)?;
+    Ok((input, expr))
+}
+
+fn int_value(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, i) = int(input)?;
+    Ok((input, Int(i)))
+}
+
+fn bool_value(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, b) = alt((token("true"), token("false")))(input)?;
+    Ok((input, Bool(b == "true")))
+}
+
+fn paren(input: &[Token]) -> IResult<&[Token], Expr> {
+    delimited(token("("), cut(expr), cut(token(")")))(input)
+}
+
+// 予約語を読んだら残りは省略できない
+fn if_expr(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (_, (expr1, _, expr2, _, expr3))) = tuple((
+        token("if"),
+        cut(tuple((expr, token("then"), expr, token("else"), expr))),
+    ))(input)?;
+    let expr = If(Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_ml1_ext::{ast::Expr::*, parser::parse};
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(
+            parse("-(3 + 4) * 2 / 3 mod 2 > 0 && true || false").unwrap(),
+            Or(
+                Box::new(And(
+                    Box::new(Gt(
+                        Box::new(Mod(
+                            Box::new(Div(
+                                Box::new(Times(
+                                    Box::new(Neg(Box::new(Plus(
+                                        Box::new(Int(3)),
+                                        Box::new(Int(4))
+                                    )))),
+                                    Box::new(Int(2))
+                                )),
+                                Box::new(Int(3))
+                            )),
+                            Box::new(Int(2))
+                        )),
+                        Box::new(Int(0))
+                    )),
+                    Box::new(Bool(true))
+                )),
+                Box::new(Bool(false))
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse("true || false && 1 = 2").unwrap(),
+            Or(
+                Box::new(Bool(true)),
+                Box::new(And(
+                    Box::new(Bool(false)),
+                    Box::new(Eq(Box::new(Int(1)), Box::new(Int(2))))
+                ))
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_neg() {
+        assert_eq!(parse("-3").unwrap(), Int(-3));
+        assert_eq!(parse("- 3").unwrap(), Neg(Box::new(Int(3))));
+        assert_eq!(
+            parse("- -3 - 4").unwrap(),
+            Minus(Box::new(Neg(Box::new(Int(-3)))), Box::new(Int(4)))
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse("1 < 2 < 3").unwrap_err().expected,
+            vec!["end of input"]
+        );
+        assert_eq!(parse("1 mod").unwrap_err().expected, vec!["expression"]);
+        assert_eq!(parse("true &&").unwrap_err().expected, vec!["expression"]);
+    }
+}
@@ -0,0 +1,373 @@
+use crate::{
+    eval_ml1_ext::ast::{Expr, Expr::*},
+    util::ident,
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    EInt(i64, usize),
+    EBool(bool, usize),
+    EIfT(Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EIfF(Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EPlus(Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    EMinus(Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ETimes(Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ELt(Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    BPlus(Expr, Expr, Expr, usize),
+    BMinus(Expr, Expr, Expr, usize),
+    BTimes(Expr, Expr, Expr, usize),
+    BLt(Expr, Expr, Expr, usize),
+    EDiv(Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    EMod(Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    EEq(Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    EGt(Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    EAndT(Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EAndF(Expr, Expr, Box<Rule>, usize),
+    EOrT(Expr, Expr, Box<Rule>, usize),
+    EOrF(Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    ENeg(Expr, Box<Rule>, Box<Rule>, usize),
+    BDiv(Expr, Expr, Expr, usize),
+    BMod(Expr, Expr, Expr, usize),
+    BEq(Expr, Expr, Expr, usize),
+    BGt(Expr, Expr, Expr, usize),
+    BNeg(Expr, Expr, usize),
+}
+
+impl Rule {
+    pub fn evaluated(&self) -> Expr {
+        use crate::eval_ml1_ext::rule::Rule::*;
+
+        match self {
+            EInt(i, _) => Int(*i),
+            EBool(b, _) => Bool(*b),
+            EIfT(_, _, _, _, rule2, _) => rule2.evaluated(),
+            EIfF(_, _, _, _, rule2, _) => rule2.evaluated(),
+            EPlus(_, _, _, _, rule3, _) => rule3.evaluated(),
+            EMinus(_, _, _, _, rule3, _) => rule3.evaluated(),
+            ETimes(_, _, _, _, rule3, _) => rule3.evaluated(),
+            ELt(_, _, _, _, rule3, _) => rule3.evaluated(),
+            BPlus(_, _, expr3, _) => expr3.clone(),
+            BMinus(_, _, expr3, _) => expr3.clone(),
+            BTimes(_, _, expr3, _) => expr3.clone(),
+            BLt(_, _, expr3, _) => expr3.clone(),
+            EDiv(_, _, _, _, rule3, _) => rule3.evaluated(),
+            EMod(_, _, _, _, rule3, _) => rule3.evaluated(),
+            EEq(_, _, _, _, rule3, _) => rule3.evaluated(),
+            EGt(_, _, _, _, rule3, _) => rule3.evaluated(),
+            EAndT(_, _, _, rule2, _) => rule2.evaluated(),
+            EAndF(..) => Bool(false),
+            EOrT(..) => Bool(true),
+            EOrF(_, _, _, rule2, _) => rule2.evaluated(),
+            ENeg(_, _, rule2, _) => rule2.evaluated(),
+            BDiv(_, _, expr3, _) => expr3.clone(),
+            BMod(_, _, expr3, _) => expr3.clone(),
+            BEq(_, _, expr3, _) => expr3.clone(),
+            BGt(_, _, expr3, _) => expr3.clone(),
+            BNeg(_, expr2, _) => expr2.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_ml1_ext::rule::Rule::*;
+
+        match self {
+            EInt(i, depth) => write!(f, "{}{} evalto {} by E-Int {{}}", ident(*depth), i, i),
+            EBool(b, depth) => write!(f, "{}{} evalto {} by E-Bool {{}}", ident(*depth), b, b),
+            EIfT(expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}if {} then {} else {} evalto {} by E-IfT {{",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EIfF(expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}if {} then {} else {} evalto {} by E-IfF {{",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EPlus(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Plus {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMinus(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Minus {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ETimes(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Times {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELt(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Lt {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            BPlus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} plus {} is {} by B-Plus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BMinus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} minus {} is {} by B-Minus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BTimes(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} times {} is {} by B-Times {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BLt(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} less than {} is {} by B-Lt {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            EDiv(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Div(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Div {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMod(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Mod(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Mod {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EEq(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Eq(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Eq {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EGt(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Gt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Gt {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EAndT(expr1, expr2, rule1, rule2, depth) => {
+                let expr = And(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-AndT {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EAndF(expr1, expr2, rule1, depth) => {
+                let expr = And(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-AndF {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule1)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EOrT(expr1, expr2, rule1, depth) => {
+                let expr = Or(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-OrT {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule1)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EOrF(expr1, expr2, rule1, rule2, depth) => {
+                let expr = Or(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-OrF {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ENeg(expr1, rule1, rule2, depth) => {
+                let expr = Neg(Box::new(expr1.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Neg {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            BDiv(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} div {} is {} by B-Div {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BMod(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} mod {} is {} by B-Mod {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BEq(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} equals {} is {} by B-Eq {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BGt(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} greater than {} is {} by B-Gt {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BNeg(expr1, expr2, depth) => {
+                write!(
+                    f,
+                    "{}minus {} is {} by B-Neg {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2
+                )
+            }
+        }
+    }
+}
@@ -13,27 +13,95 @@ pub enum Expr {
     Var(String),
 }
 
-impl fmt::Display for Expr {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl Expr {
+    // 結合の強さ (大きいほど強い)
+    fn precedence(&self) -> usize {
+        use crate::eval_ml2::ast::Expr::*;
+
+        match self {
+            If(_, _, _) | Let(_, _, _) => 0,
+            Lt(_, _) => 1,
+            Plus(_, _) | Minus(_, _) => 3,
+            Times(_, _) => 4,
+            _ => 6,
+        }
+    }
+
+    // 右にできるだけ伸びる式 (右端にあれば括弧がいらない)
+    fn is_open(&self) -> bool {
+        use crate::eval_ml2::ast::Expr::*;
+
+        matches!(self, If(_, _, _) | Let(_, _, _))
+    }
+
+    fn paren(&self, paren: bool) -> String {
+        if paren {
+            format!("({})", self)
+        } else {
+            self.to_string()
+        }
+    }
+
+    // tail は後ろに何も続かないこと
+    fn show(&self, tail: bool) -> String {
         use crate::eval_ml2::ast::Expr::*;
 
+        let p = self.precedence();
+        let left = |expr: &Expr, paren: bool| {
+            if paren {
+                expr.paren(true)
+            } else {
+                expr.show(false)
+            }
+        };
+        let right = |expr: &Expr, paren: bool| {
+            if paren && !(tail && expr.is_open()) {
+                expr.paren(true)
+            } else {
+                expr.show(tail)
+            }
+        };
         match self {
-            Int(i) => write!(f, "{}", i),
-            Bool(b) => write!(f, "{}", b),
-            If(expr1, expr2, expr3) => write!(f, "(if {} then {} else {})", expr1, expr2, expr3),
-            Plus(expr1, expr2) => write!(f, "({} + {})", expr1, expr2),
-            Minus(expr1, expr2) => write!(f, "({} - {})", expr1, expr2),
-            Times(expr1, expr2) => write!(f, "({} * {})", expr1, expr2),
-            Lt(expr1, expr2) => write!(f, "({} < {})", expr1, expr2),
+            Int(i) => i.to_string(),
+            Bool(b) => b.to_string(),
+            If(expr1, expr2, expr3) => {
+                format!("if {} then {} else {}", expr1, expr2, expr3.show(tail))
+            }
+            Plus(expr1, expr2) => format!(
+                "{} + {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Minus(expr1, expr2) => format!(
+                "{} - {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Times(expr1, expr2) => format!(
+                "{} * {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Lt(expr1, expr2) => format!(
+                "{} < {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
             Let(expr1, expr2, expr3) => {
-                write!(f, "(let {} = {} in {})", expr1, expr2, expr3)
+                format!("let {} = {} in {}", expr1, expr2, expr3.show(tail))
             }
-            Var(s) => write!(f, "{}", s),
+            Var(s) => s.to_string(),
         }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, PartialOrd)]
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.show(true))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
 pub struct Env(Vec<(Expr, Expr)>);
 
 impl Env {
@@ -59,8 +127,12 @@ impl Env {
             .clone()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     pub fn last(&self) -> (Expr, Expr) {
-        self.0.last().map(|pair| pair.clone()).unwrap()
+        self.0.last().cloned().unwrap()
     }
 
     pub fn butlast(&self) -> Env {
@@ -82,7 +154,10 @@ impl fmt::Display for Env {
 
 #[cfg(test)]
 mod tests {
-    use crate::eval_ml2::ast::{Env, Expr::*};
+    use crate::eval_ml2::{
+        ast::{Env, Expr::*},
+        parser::parse_expr,
+    };
 
     #[test]
     fn test_env() {
@@ -110,4 +185,21 @@ mod tests {
             env.butlast()
         )
     }
+
+    #[test]
+    fn test_display() {
+        // 表示した式を読み直すと元の式に戻る
+        for input in [
+            "8 - 2 - 3",
+            "8 - (2 - 3)",
+            "3 + if -23 < -2 * 8 then 8 else 2 + 4",
+            "3 + (if -23 < -2 * 8 then 8 else 2) + 4",
+            "(let x = 1 in x) * 2",
+            "1 < (2 < 3)",
+            "if let x = 1 in x < 2 then 3 else let y = 4 in y",
+        ] {
+            let expr = parse_expr(input).unwrap().1;
+            assert_eq!(input, expr.to_string());
+        }
+    }
 }
@@ -2,8 +2,8 @@ use crate::eval_ml2::ast::{Env, Expr, Expr::*};
 
 pub fn eval(env: &Env, expr: &Expr) -> Expr {
     match expr {
-        Int(i) => Int(i.clone()),
-        Bool(b) => Bool(b.clone()),
+        Int(i) => Int(*i),
+        Bool(b) => Bool(*b),
         If(expr1, expr2, expr3) => match eval(env, expr1) {
             Bool(true) => eval(env, expr2),
             Bool(false) => eval(env, expr3),
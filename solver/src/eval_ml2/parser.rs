@@ -1,17 +1,20 @@
 use crate::{
     eval_ml2::ast::{Env, Expr, Expr::*},
-    util::ws,
+    syntax::{
+        error::{complete, expected, IResult, ParseError},
+        lexer::{identifier, int, token, tokens, Token, TokenKind},
+    },
 };
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{alphanumeric1, char, digit1},
-    combinator::{opt, recognize},
+    combinator::{cut, opt},
     multi::many0,
-    sequence::{delimited, tuple},
-    IResult,
+    sequence::{delimited, preceded, tuple},
 };
 
+// 字句は syntax::lexer で区切る
+// <judgment> ::= [ <env> '|-' ] <expr>
+// <env> ::= [ <var> '=' <expr> [ ',' <var> '=' <expr> ]* ]
 // <expr> ::= <term1> [ <' <term1> ]
 // <term1> ::= <term2> [ ( '+' | '-' ) <term2> ]*
 // <term2> ::= <factor> [ '*' <factor> ]*
@@ -22,53 +25,54 @@ use nom::{
 // <paren> ::= '(' <expr> ')'
 // <if> ::= 'if' <expr> 'then' <expr> 'else' <expr>
 // <let> ::= 'let' <var> '=' <expr> 'in' <expr>
-// <var> ::= 文字列 | 数値
-
-pub fn parse(input: &str) -> IResult<&str, (Env, Expr)> {
-    match input.find("|-") {
-        Some(i) => {
-            let (input1, input2) = input.split_at(i);
-            let (_, env) = parse_env(input1)?;
-            let (input, expr) = parse_expr(&input2[2..])?;
-            Ok((input, (env, expr)))
-        }
-        None => {
-            let (input, expr) = parse_expr(input)?;
-            Ok((input, (Env::new(), expr)))
-        }
-    }
+// <var> ::= 英字か '_' で始まる英数字と '_', '\'' の列 (予約語を除く)
+
+pub fn parse(input: &str) -> Result<(Env, Expr), ParseError> {
+    complete(input, parse_env_expr)
 }
 
-fn parse_env(input: &str) -> IResult<&str, Env> {
-    let mut env = Env::new();
-    let (input, pair) = opt(parse_pair)(input)?;
-    match pair {
-        Some((expr1, expr2)) => env.put(expr1, expr2),
-        None => return Ok((input, env)),
-    };
-    let (input, pairs) = opt(parse_pairs)(input)?;
-    if let Some(pairs) = pairs {
-        pairs
-            .into_iter()
-            .for_each(|(expr1, expr2)| env.put(expr1, expr2));
-    }
-    Ok((input, env))
+pub fn parse_env_expr(input: &str) -> IResult<&str, (Env, Expr)> {
+    tokens(env_expr)(input)
+}
+
+pub fn parse_expr(input: &str) -> IResult<&str, Expr> {
+    tokens(expr)(input)
 }
 
-fn parse_pair(input: &str) -> IResult<&str, (Expr, Expr)> {
-    let (input, (expr1, _, expr2)) = tuple((parse_var, ws(char('=')), parse_expr))(input)?;
-    let pair = (expr1, expr2);
-    Ok((input, pair))
+pub fn parse_value(input: &str) -> IResult<&str, Expr> {
+    tokens(value)(input)
 }
 
-fn parse_pairs(input: &str) -> IResult<&str, Vec<(Expr, Expr)>> {
-    let (input, pairs) = many0(tuple((ws(char(',')), parse_pair)))(input)?;
-    let pairs = pairs.into_iter().map(|(_, pair)| pair).collect();
-    Ok((input, pairs))
+fn env_expr(input: &[Token]) -> IResult<&[Token], (Env, Expr)> {
+    let turnstile = TokenKind::Symbol("|-");
+    if input.iter().any(|token| token.kind == turnstile) {
+        tuple((env, cut(expr)))(input)
+    } else {
+        let (input, expr) = expr(input)?;
+        Ok((input, (Env::new(), expr)))
+    }
 }
 
-fn parse_expr(input: &str) -> IResult<&str, Expr> {
-    let (input, (expr1, expr2)) = tuple((parse_term1, opt(parse_lt)))(input)?;
+// '|-' までを環境として読む
+fn env(input: &[Token]) -> IResult<&[Token], Env> {
+    let mut env = Env::new();
+    if let Ok((input, _)) = token("|-")(input) {
+        return Ok((input, env));
+    }
+    let mut input = input;
+    loop {
+        let (rest, (expr1, _, expr2, sep)) =
+            tuple((var, token("="), expr, alt((token(","), token("|-")))))(input)?;
+        env.put(expr1, expr2);
+        input = rest;
+        if sep == "|-" {
+            return Ok((input, env));
+        }
+    }
+}
+
+fn expr(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (expr1, expr2)) = tuple((term1, opt(lt)))(input)?;
     let expr = match expr2 {
         Some(expr2) => Lt(Box::new(expr1), Box::new(expr2)),
         None => expr1,
@@ -76,125 +80,84 @@ fn parse_expr(input: &str) -> IResult<&str, Expr> {
     Ok((input, expr))
 }
 
-fn parse_lt(input: &str) -> IResult<&str, Expr> {
-    let (input, (_, expr)) = tuple((ws(char('<')), parse_term1))(input)?;
-    Ok((input, expr))
+// 演算子を読んだら右辺は省略できない
+fn lt(input: &[Token]) -> IResult<&[Token], Expr> {
+    preceded(token("<"), cut(term1))(input)
 }
 
-fn parse_term1(input: &str) -> IResult<&str, Expr> {
-    let (input, (expr, exprs)) = tuple((parse_term2, parse_plus_minus))(input)?;
-    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
-        '+' => Plus(Box::new(expr1), Box::new(expr2.clone())),
-        '-' => Minus(Box::new(expr1), Box::new(expr2.clone())),
+fn term1(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (expr, exprs)) = tuple((term2, plus_minus))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match *op {
+        "+" => Plus(Box::new(expr1), Box::new(expr2.clone())),
+        "-" => Minus(Box::new(expr1), Box::new(expr2.clone())),
         _ => unreachable!(),
     });
     Ok((input, expr))
 }
 
-fn parse_plus_minus(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
-    let parse_plus = ws(char('+'));
-    let parse_minus = ws(char('-'));
-    let parse_op = alt((parse_plus, parse_minus));
-    let (input, exprs) = many0(tuple((parse_op, parse_term2)))(input)?;
-    Ok((input, exprs))
+fn plus_minus(input: &[Token]) -> IResult<&[Token], Vec<(&str, Expr)>> {
+    let op = alt((token("+"), token("-")));
+    many0(tuple((op, cut(term2))))(input)
 }
 
-fn parse_term2(input: &str) -> IResult<&str, Expr> {
-    let (input, (expr, exprs)) = tuple((parse_factor, parse_times))(input)?;
-    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
-        '*' => Times(Box::new(expr1), Box::new(expr2.clone())),
+fn term2(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (expr, exprs)) = tuple((factor, times))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match *op {
+        "*" => Times(Box::new(expr1), Box::new(expr2.clone())),
         _ => unreachable!(),
     });
     Ok((input, expr))
 }
 
-fn parse_times(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
-    let parse_times = ws(char('*'));
-    let (input, exprs) = many0(tuple((parse_times, parse_factor)))(input)?;
-    Ok((input, exprs))
-}
-
-fn parse_factor(input: &str) -> IResult<&str, Expr> {
-    let (input, expr) = alt((parse_value, parse_paren, parse_if, parse_let, parse_var))(input)?;
-    Ok((input, expr))
-}
-
-fn parse_value(input: &str) -> IResult<&str, Expr> {
-    let (input, expr) = alt((parse_int, parse_bool))(input)?;
-    Ok((input, expr))
-}
-
-fn parse_int(input: &str) -> IResult<&str, Expr> {
-    let (input, i) = alt((ws(parse_pos_number), ws(parse_neg_number)))(input)?;
-    let expr = Int(i);
-    Ok((input, expr))
-}
-
-fn parse_pos_number(input: &str) -> IResult<&str, i64> {
-    let (input, i) = digit1(input)?;
-    let i = i.parse::<i64>().unwrap();
-    Ok((input, i))
+fn times(input: &[Token]) -> IResult<&[Token], Vec<(&str, Expr)>> {
+    many0(tuple((token("*"), cut(factor))))(input)
 }
 
-fn parse_neg_number(input: &str) -> IResult<&str, i64> {
-    let (input, i) = recognize(tuple((char('-'), digit1)))(input)?;
-    let i = i.parse::<i64>().unwrap();
-    Ok((input, i))
+fn factor(input: &[Token]) -> IResult<&[Token], Expr> {
+    expected("expression", alt((value, paren, if_expr, let_expr, var)))(input)
 }
 
-fn parse_bool(input: &str) -> IResult<&str, Expr> {
-    let (input, b) = alt((parse_true, parse_false))(input)?;
-    let expr = Bool(b);
+fn value(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, expr) = alt((int_value, bool_value))(input)?;
     Ok((input, expr))
 }
 
-fn parse_true(input: &str) -> IResult<&str, bool> {
-    let (input, _) = ws(tag("true"))(input)?;
-    Ok((input, true))
+fn int_value(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, i) = int(input)?;
+    Ok((input, Int(i)))
 }
 
-fn parse_false(input: &str) -> IResult<&str, bool> {
-    let (input, _) = ws(tag("false"))(input)?;
-    Ok((input, false))
+fn bool_value(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, b) = alt((token("true"), token("false")))(input)?;
+    Ok((input, Bool(b == "true")))
 }
 
-fn parse_paren(input: &str) -> IResult<&str, Expr> {
-    let parse_lparen = ws(char('('));
-    let parse_rparen = ws(char(')'));
-    let (input, expr) = delimited(parse_lparen, parse_expr, parse_rparen)(input)?;
-    Ok((input, expr))
+fn paren(input: &[Token]) -> IResult<&[Token], Expr> {
+    delimited(token("("), cut(expr), cut(token(")")))(input)
 }
 
-fn parse_if(input: &str) -> IResult<&str, Expr> {
-    let (input, (_, expr1, _, expr2, _, expr3)) = tuple((
-        ws(tag("if")),
-        parse_expr,
-        ws(tag("then")),
-        parse_expr,
-        ws(tag("else")),
-        parse_expr,
+// 予約語を読んだら残りは省略できない
+fn if_expr(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (_, (expr1, _, expr2, _, expr3))) = tuple((
+        token("if"),
+        cut(tuple((expr, token("then"), expr, token("else"), expr))),
     ))(input)?;
     let expr = If(Box::new(expr1), Box::new(expr2), Box::new(expr3));
     Ok((input, expr))
 }
 
-fn parse_let(input: &str) -> IResult<&str, Expr> {
-    let (input, (_, expr1, _, expr2, _, expr3)) = tuple((
-        ws(tag("let")),
-        parse_var,
-        ws(char('=')),
-        parse_expr,
-        ws(tag("in")),
-        parse_expr,
+fn let_expr(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (_, (expr1, _, expr2, _, expr3))) = tuple((
+        token("let"),
+        cut(tuple((var, token("="), expr, token("in"), expr))),
     ))(input)?;
     let expr = Let(Box::new(expr1), Box::new(expr2), Box::new(expr3));
     Ok((input, expr))
 }
 
-fn parse_var(input: &str) -> IResult<&str, Expr> {
-    let (input, s) = ws(alphanumeric1)(input)?;
-    let expr = Var(s.to_string());
-    Ok((input, expr))
+fn var(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, s) = expected("variable", identifier)(input)?;
+    Ok((input, Var(s)))
 }
 
 #[cfg(test)]
@@ -207,7 +170,7 @@ mod tests {
     #[test]
     fn test_parse1() {
         assert_eq!(
-            parse("3 + 5").unwrap().1,
+            parse("3 + 5").unwrap(),
             (Env::new(), Plus(Box::new(Int(3)), Box::new(Int(5))))
         );
     }
@@ -215,7 +178,7 @@ mod tests {
     #[test]
     fn test_parse2() {
         assert_eq!(
-            parse("8 - 2 - 3").unwrap().1,
+            parse("8 - 2 - 3").unwrap(),
             (
                 Env::new(),
                 Minus(
@@ -229,7 +192,7 @@ mod tests {
     #[test]
     fn test_parse3() {
         assert_eq!(
-            parse("(4 + 5) * (1 - 10)").unwrap().1,
+            parse("(4 + 5) * (1 - 10)").unwrap(),
             (
                 Env::new(),
                 Times(
@@ -243,7 +206,7 @@ mod tests {
     #[test]
     fn test_parse4() {
         assert_eq!(
-            parse("if 4 < 5 then 2 + 3 else 8 * 8").unwrap().1,
+            parse("if 4 < 5 then 2 + 3 else 8 * 8").unwrap(),
             (
                 Env::new(),
                 If(
@@ -258,7 +221,7 @@ mod tests {
     #[test]
     fn test_parse5() {
         assert_eq!(
-            parse("3 + if -23 < -2 * 8 then 8 else 2 + 4").unwrap().1,
+            parse("3 + if -23 < -2 * 8 then 8 else 2 + 4").unwrap(),
             (
                 Env::new(),
                 Plus(
@@ -279,7 +242,7 @@ mod tests {
     #[test]
     fn test_parse6() {
         assert_eq!(
-            parse("3 + (if -23 < -2 * 8 then 8 else 2) + 4").unwrap().1,
+            parse("3 + (if -23 < -2 * 8 then 8 else 2) + 4").unwrap(),
             (
                 Env::new(),
                 Plus(
@@ -303,7 +266,7 @@ mod tests {
     #[test]
     fn test_parse7() {
         assert_eq!(
-            parse("x = 3, y = 2 |- x").unwrap().1,
+            parse("x = 3, y = 2 |- x").unwrap(),
             (
                 Env::from(vec![
                     (Var("x".to_string()), Int(3)),
@@ -317,9 +280,7 @@ mod tests {
     #[test]
     fn test_parse8() {
         assert_eq!(
-            parse("x = true, y = 4 |- if x then y + 1 else y")
-                .unwrap()
-                .1,
+            parse("x = true, y = 4 |- if x then y + 1 else y").unwrap(),
             (
                 Env::from(vec![
                     (Var("x".to_string()), Bool(true)),
@@ -337,7 +298,7 @@ mod tests {
     #[test]
     fn test_parse9() {
         assert_eq!(
-            parse("|- let x = 1 + 2 in x * 4").unwrap().1,
+            parse("|- let x = 1 + 2 in x * 4").unwrap(),
             (
                 Env::new(),
                 Let(
@@ -352,9 +313,7 @@ mod tests {
     #[test]
     fn test_parse10() {
         assert_eq!(
-            parse("|- let x = 3 * 3 in let y = 4 * x in x + y")
-                .unwrap()
-                .1,
+            parse("|- let x = 3 * 3 in let y = 4 * x in x + y").unwrap(),
             (
                 Env::new(),
                 Let(
@@ -376,7 +335,7 @@ mod tests {
     #[test]
     fn test_parse11() {
         assert_eq!(
-            parse("x = 3 |- let x = x * 2 in x + x").unwrap().1,
+            parse("x = 3 |- let x = x * 2 in x + x").unwrap(),
             (
                 Env::from(vec![(Var("x".to_string()), Int(3))]),
                 Let(
@@ -390,4 +349,101 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_parse_error() {
+        let e = parse("x = 1,\ny = 2\n|- (x + y").unwrap_err();
+        assert_eq!((e.offset, e.line, e.column), (22, 3, 10));
+        assert_eq!(e.expected, vec!["`)`"]);
+        let e = parse("x = 3 |- x *").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 13: expected expression\nx = 3 |- x *\n            ^"
+        );
+    }
+
+    #[test]
+    fn test_parse_trailing() {
+        let e = parse("3 + 4 )) junk").unwrap_err();
+        assert_eq!(e.column, 7);
+        assert_eq!(e.expected, vec!["end of input"]);
+        let e = parse("x = 3 y = 4 |- x").unwrap_err();
+        assert_eq!(e.column, 7);
+        assert_eq!(e.expected, vec!["`,`", "`|-`"]);
+    }
+
+    #[test]
+    fn test_parse_var() {
+        assert_eq!(
+            parse("x' = 1, _y2 = 2 |- x'").unwrap(),
+            (
+                Env::from(vec![
+                    (Var("x'".to_string()), Int(1)),
+                    (Var("_y2".to_string()), Int(2))
+                ]),
+                Var("x'".to_string())
+            )
+        );
+        assert_eq!(
+            parse("|- let mod = 1 in mod").unwrap().1,
+            Let(
+                Box::new(Var("mod".to_string())),
+                Box::new(Int(1)),
+                Box::new(Var("mod".to_string()))
+            )
+        );
+        assert_eq!(
+            parse("|- let iffy = 1 in iffy").unwrap().1,
+            Let(
+                Box::new(Var("iffy".to_string())),
+                Box::new(Int(1)),
+                Box::new(Var("iffy".to_string()))
+            )
+        );
+        for input in [
+            "|- let let = 1 in 2",
+            "|- let in = 1 in 2",
+            "|- let then = 1 in 2",
+            "|- let true = 1 in 2",
+            "|- let 123 = 1 in 2",
+            "let = 3 |- 1",
+            "|- let x = 1 x",
+        ] {
+            assert!(parse(input).is_err(), "{}", input);
+        }
+        let e = parse("|- let y = 1 + 2 y * 2").unwrap_err();
+        assert_eq!(e.column, 18);
+        assert_eq!(e.expected, vec!["`in`"]);
+        let e = parse("|- let true = 1 in 2").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 8: expected variable\n|- let true = 1 in 2\n       ^"
+        );
+    }
+
+    #[test]
+    fn test_parse_keyword() {
+        assert_eq!(
+            parse("letx = 1, iftrue = true |- if iftrue then letx else 0").unwrap(),
+            (
+                Env::from(vec![
+                    (Var("letx".to_string()), Int(1)),
+                    (Var("iftrue".to_string()), Bool(true))
+                ]),
+                If(
+                    Box::new(Var("iftrue".to_string())),
+                    Box::new(Var("letx".to_string())),
+                    Box::new(Int(0))
+                )
+            )
+        );
+        assert!(parse("|- letx = 1 in x").is_err());
+        assert_eq!(
+            parse("x = 1 (* |- *) |- (* x *) x").unwrap(),
+            (
+                Env::from(vec![(Var("x".to_string()), Int(1))]),
+                Var("x".to_string())
+            )
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     eval_ml2::ast::{Env, Expr, Expr::*},
-    util::ident,
+    util::{env_prefix, ident},
 };
 use std::fmt;
 
@@ -28,8 +28,8 @@ impl Rule {
         use crate::eval_ml2::rule::Rule::*;
 
         match self {
-            EInt(_, i, _) => Int(i.clone()),
-            EBool(_, b, _) => Bool(b.clone()),
+            EInt(_, i, _) => Int(*i),
+            EBool(_, b, _) => Bool(*b),
             EIfT(_, _, _, _, _, rule2, _) => rule2.evaluated(),
             EIfF(_, _, _, _, _, rule2, _) => rule2.evaluated(),
             EPlus(_, _, _, _, _, rule3, _) => rule3.evaluated(),
@@ -55,9 +55,9 @@ impl fmt::Display for Rule {
             EInt(env, i, depth) => {
                 write!(
                     f,
-                    "{}{} |- {} evalto {} by E-Int {{}}",
+                    "{}{}|- {} evalto {} by E-Int {{}}",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     i,
                     i
                 )
@@ -65,9 +65,9 @@ impl fmt::Display for Rule {
             EBool(env, b, depth) => {
                 write!(
                     f,
-                    "{}{} |- {} evalto {} by E-Bool {{}}",
+                    "{}{}|- {} evalto {} by E-Bool {{}}",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     b,
                     b
                 )
@@ -75,9 +75,9 @@ impl fmt::Display for Rule {
             EIfT(env, expr1, expr2, expr3, rule1, rule2, depth) => {
                 writeln!(
                     f,
-                    "{}{} |- if {} then {} else {} evalto {} by E-IfT {{",
+                    "{}{}|- if {} then {} else {} evalto {} by E-IfT {{",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     expr1,
                     expr2,
                     expr3,
@@ -90,9 +90,9 @@ impl fmt::Display for Rule {
             EIfF(env, expr1, expr2, expr3, rule1, rule2, depth) => {
                 writeln!(
                     f,
-                    "{}{} |- if {} then {} else {} evalto {} by E-IfF {{",
+                    "{}{}|- if {} then {} else {} evalto {} by E-IfF {{",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     expr1,
                     expr2,
                     expr3,
@@ -103,13 +103,13 @@ impl fmt::Display for Rule {
                 write!(f, "{}}}", ident(*depth))
             }
             EPlus(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
                 writeln!(
                     f,
-                    "{}{} |- {} + {} evalto {} by E-Plus {{",
+                    "{}{}|- {} evalto {} by E-Plus {{",
                     ident(*depth),
-                    env,
-                    expr1,
-                    expr2,
+                    env_prefix(env),
+                    expr,
                     self.evaluated()
                 )?;
                 writeln!(f, "{};", rule1)?;
@@ -118,13 +118,13 @@ impl fmt::Display for Rule {
                 write!(f, "{}}}", ident(*depth))
             }
             EMinus(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
                 writeln!(
                     f,
-                    "{}{} |- {} - {} evalto {} by E-Minus {{",
+                    "{}{}|- {} evalto {} by E-Minus {{",
                     ident(*depth),
-                    env,
-                    expr1,
-                    expr2,
+                    env_prefix(env),
+                    expr,
                     self.evaluated()
                 )?;
                 writeln!(f, "{};", rule1)?;
@@ -133,13 +133,13 @@ impl fmt::Display for Rule {
                 write!(f, "{}}}", ident(*depth))
             }
             ETimes(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
                 writeln!(
                     f,
-                    "{}{} |- {} * {} evalto {} by E-Times {{",
+                    "{}{}|- {} evalto {} by E-Times {{",
                     ident(*depth),
-                    env,
-                    expr1,
-                    expr2,
+                    env_prefix(env),
+                    expr,
                     self.evaluated()
                 )?;
                 writeln!(f, "{};", rule1)?;
@@ -148,13 +148,13 @@ impl fmt::Display for Rule {
                 write!(f, "{}}}", ident(*depth))
             }
             ELt(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
                 writeln!(
                     f,
-                    "{}{} |- {} < {} evalto {} by E-Lt {{",
+                    "{}{}|- {} evalto {} by E-Lt {{",
                     ident(*depth),
-                    env,
-                    expr1,
-                    expr2,
+                    env_prefix(env),
+                    expr,
                     self.evaluated()
                 )?;
                 writeln!(f, "{};", rule1)?;
@@ -204,9 +204,9 @@ impl fmt::Display for Rule {
             EVar1(env, expr, depth) => {
                 write!(
                     f,
-                    "{}{} |- {} evalto {} by E-Var1 {{}}",
+                    "{}{}|- {} evalto {} by E-Var1 {{}}",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     expr,
                     self.evaluated()
                 )
@@ -214,9 +214,9 @@ impl fmt::Display for Rule {
             EVar2(env, expr, rule, depth) => {
                 writeln!(
                     f,
-                    "{}{} |- {} evalto {} by E-Var2 {{",
+                    "{}{}|- {} evalto {} by E-Var2 {{",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     expr,
                     self.evaluated()
                 )?;
@@ -226,9 +226,9 @@ impl fmt::Display for Rule {
             ELet(env, expr1, expr2, expr3, rule1, rule2, depth) => {
                 writeln!(
                     f,
-                    "{}{} |- let {} = {} in {} evalto {} by E-Let {{",
+                    "{}{}|- let {} = {} in {} evalto {} by E-Let {{",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     expr1,
                     expr2,
                     expr3,
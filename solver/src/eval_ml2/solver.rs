@@ -1,17 +1,19 @@
+use crate::eval_error::{check_depth, checked, EvalError};
 use crate::eval_ml2::{
     ast::{Env, Expr, Expr::*},
     rule::{Rule, Rule::*},
 };
 
-pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
-    match expr {
-        Int(i) => EInt(env.clone(), i.clone(), depth),
-        Bool(b) => EBool(env.clone(), b.clone(), depth),
+pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Result<Rule, EvalError> {
+    check_depth(depth)?;
+    let rule = match expr {
+        Int(i) => EInt(env.clone(), *i, depth),
+        Bool(b) => EBool(env.clone(), *b, depth),
         If(expr1, expr2, expr3) => {
-            let rule1 = solve(env, expr1, depth + 1);
+            let rule1 = solve(env, expr1, depth + 1)?;
             match rule1.evaluated() {
                 Bool(true) => {
-                    let rule2 = solve(env, expr2, depth + 1);
+                    let rule2 = solve(env, expr2, depth + 1)?;
                     EIfT(
                         env.clone(),
                         *expr1.clone(),
@@ -23,7 +25,7 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
                     )
                 }
                 Bool(false) => {
-                    let rule2 = solve(env, expr3, depth + 1);
+                    let rule2 = solve(env, expr3, depth + 1)?;
                     EIfF(
                         env.clone(),
                         *expr1.clone(),
@@ -34,15 +36,15 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
                         depth,
                     )
                 }
-                _ => unreachable!(),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             }
         }
         Plus(expr1, expr2) => {
-            let rule1 = solve(env, expr1, depth + 1);
-            let rule2 = solve(env, expr2, depth + 1);
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
             let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
-                (Int(i1), Int(i2)) => Int(i1 + i2),
-                _ => unreachable!(),
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_add(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             };
             let rule3 = BPlus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
             EPlus(
@@ -56,11 +58,11 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
             )
         }
         Minus(expr1, expr2) => {
-            let rule1 = solve(env, expr1, depth + 1);
-            let rule2 = solve(env, expr2, depth + 1);
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
             let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
-                (Int(i1), Int(i2)) => Int(i1 - i2),
-                _ => unreachable!(),
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_sub(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             };
             let rule3 = BMinus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
             EMinus(
@@ -74,11 +76,11 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
             )
         }
         Times(expr1, expr2) => {
-            let rule1 = solve(env, expr1, depth + 1);
-            let rule2 = solve(env, expr2, depth + 1);
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
             let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
-                (Int(i1), Int(i2)) => Int(i1 * i2),
-                _ => unreachable!(),
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_mul(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             };
             let rule3 = BTimes(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
             ETimes(
@@ -92,11 +94,11 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
             )
         }
         Lt(expr1, expr2) => {
-            let rule1 = solve(env, expr1, depth + 1);
-            let rule2 = solve(env, expr2, depth + 1);
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
             let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
                 (Int(i1), Int(i2)) => Bool(i1 < i2),
-                _ => unreachable!(),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             };
             let rule3 = BLt(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
             ELt(
@@ -110,18 +112,21 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
             )
         }
         Var(_) => {
+            if env.is_empty() {
+                return Err(EvalError::Unbound(expr.to_string()));
+            }
             if env.last().0 == expr.clone() {
                 EVar1(env.clone(), expr.clone(), depth)
             } else {
-                let rule = solve(&env.butlast(), expr, depth + 1);
+                let rule = solve(&env.butlast(), expr, depth + 1)?;
                 EVar2(env.clone(), expr.clone(), Box::new(rule), depth)
             }
         }
         Let(expr1, expr2, expr3) => {
-            let rule1 = solve(env, expr2, depth + 1);
+            let rule1 = solve(env, expr2, depth + 1)?;
             let mut new_env = env.clone();
             new_env.put(*expr1.clone(), rule1.evaluated());
-            let rule2 = solve(&new_env, expr3, depth + 1);
+            let rule2 = solve(&new_env, expr3, depth + 1)?;
             ELet(
                 env.clone(),
                 *expr1.clone(),
@@ -132,7 +137,8 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
                 depth,
             )
         }
-    }
+    };
+    Ok(rule)
 }
 
 #[cfg(test)]
@@ -146,7 +152,7 @@ mod tests {
     #[test]
     fn test_solve1() {
         assert_eq!(
-            solve(&Env::new(), &Plus(Box::new(Int(3)), Box::new(Int(5))), 0),
+            solve(&Env::new(), &Plus(Box::new(Int(3)), Box::new(Int(5))), 0).unwrap(),
             EPlus(
                 Env::new(),
                 Int(3),
@@ -169,7 +175,8 @@ mod tests {
                     Box::new(Int(3))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EMinus(
                 Env::new(),
                 Minus(Box::new(Int(8)), Box::new(Int(2))),
@@ -200,7 +207,8 @@ mod tests {
                     Box::new(Minus(Box::new(Int(1)), Box::new(Int(10))))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             ETimes(
                 Env::new(),
                 Plus(Box::new(Int(4)), Box::new(Int(5))),
@@ -240,7 +248,8 @@ mod tests {
                     Box::new(Times(Box::new(Int(8)), Box::new(Int(8))))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EIfT(
                 Env::new(),
                 Lt(Box::new(Int(4)), Box::new(Int(5))),
@@ -286,7 +295,8 @@ mod tests {
                     ))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EPlus(
                 Env::new(),
                 Int(3),
@@ -353,7 +363,8 @@ mod tests {
                     Box::new(Int(4))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EPlus(
                 Env::new(),
                 Plus(
@@ -428,7 +439,8 @@ mod tests {
                 ]),
                 &Var("x".to_string()),
                 0
-            ),
+            )
+            .unwrap(),
             EVar2(
                 Env::from(vec![
                     (Var("x".to_string()), Int(3)),
@@ -459,7 +471,8 @@ mod tests {
                     Box::new(Var("y".to_string()))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EIfT(
                 Env::from(vec![
                     (Var("x".to_string()), Bool(true)),
@@ -523,7 +536,8 @@ mod tests {
                     Box::new(Times(Box::new(Var("x".to_string())), Box::new(Int(4))))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             ELet(
                 Env::new(),
                 Var("x".to_string()),
@@ -574,7 +588,8 @@ mod tests {
                     ))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             ELet(
                 Env::new(),
                 Var("x".to_string()),
@@ -669,7 +684,8 @@ mod tests {
                     )),
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             ELet(
                 Env::from(vec![(Var("x".to_string()), Int(3))]),
                 Var("x".to_string()),
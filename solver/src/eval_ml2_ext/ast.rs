@@ -0,0 +1,258 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Expr {
+    Int(i64),
+    Bool(bool),
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+    Plus(Box<Expr>, Box<Expr>),
+    Minus(Box<Expr>, Box<Expr>),
+    Times(Box<Expr>, Box<Expr>),
+    Lt(Box<Expr>, Box<Expr>),
+    // 以下は EvalML2 にない式
+    Div(Box<Expr>, Box<Expr>),
+    Mod(Box<Expr>, Box<Expr>),
+    Eq(Box<Expr>, Box<Expr>),
+    Gt(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Neg(Box<Expr>),
+    Let(Box<Expr>, Box<Expr>, Box<Expr>),
+    Var(String),
+}
+
+impl Expr {
+    // 結合の強さ (大きいほど強い)
+    fn precedence(&self) -> usize {
+        use crate::eval_ml2_ext::ast::Expr::*;
+
+        match self {
+            If(_, _, _) | Let(_, _, _) => 0,
+            Or(_, _) => 1,
+            And(_, _) => 2,
+            Lt(_, _) | Eq(_, _) | Gt(_, _) => 3,
+            Plus(_, _) | Minus(_, _) => 4,
+            Times(_, _) | Div(_, _) | Mod(_, _) => 5,
+            Neg(_) => 6,
+            _ => 7,
+        }
+    }
+
+    // 右にできるだけ伸びる式 (右端にあれば括弧がいらない)
+    fn is_open(&self) -> bool {
+        use crate::eval_ml2_ext::ast::Expr::*;
+
+        matches!(self, If(_, _, _) | Let(_, _, _))
+    }
+
+    fn paren(&self, paren: bool) -> String {
+        if paren {
+            format!("({})", self)
+        } else {
+            self.to_string()
+        }
+    }
+
+    // tail は後ろに何も続かないこと
+    fn show(&self, tail: bool) -> String {
+        use crate::eval_ml2_ext::ast::Expr::*;
+
+        let p = self.precedence();
+        let left = |expr: &Expr, paren: bool| {
+            if paren {
+                expr.paren(true)
+            } else {
+                expr.show(false)
+            }
+        };
+        let right = |expr: &Expr, paren: bool| {
+            if paren && !(tail && expr.is_open()) {
+                expr.paren(true)
+            } else {
+                expr.show(tail)
+            }
+        };
+        match self {
+            Int(i) => i.to_string(),
+            Bool(b) => b.to_string(),
+            If(expr1, expr2, expr3) => {
+                format!("if {} then {} else {}", expr1, expr2, expr3.show(tail))
+            }
+            Plus(expr1, expr2) => format!(
+                "{} + {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Minus(expr1, expr2) => format!(
+                "{} - {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Times(expr1, expr2) => format!(
+                "{} * {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Lt(expr1, expr2) => format!(
+                "{} < {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Div(expr1, expr2) => format!(
+                "{} / {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Mod(expr1, expr2) => format!(
+                "{} mod {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Eq(expr1, expr2) => format!(
+                "{} = {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Gt(expr1, expr2) => format!(
+                "{} > {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            // && と || は右結合
+            And(expr1, expr2) => format!(
+                "{} && {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() < p)
+            ),
+            Or(expr1, expr2) => format!(
+                "{} || {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() < p)
+            ),
+            // 整数の前は空けないと負の整数と区別できない
+            Neg(expr) => match **expr {
+                Int(_) => format!("- {}", expr),
+                _ => format!("-{}", right(expr, expr.precedence() < p)),
+            },
+            Let(expr1, expr2, expr3) => {
+                format!("let {} = {} in {}", expr1, expr2, expr3.show(tail))
+            }
+            Var(s) => s.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.show(true))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Env(Vec<(Expr, Expr)>);
+
+impl Env {
+    pub fn new() -> Env {
+        Env(vec![])
+    }
+
+    pub fn from(pairs: Vec<(Expr, Expr)>) -> Env {
+        Env(pairs)
+    }
+
+    pub fn put(&mut self, expr1: Expr, expr2: Expr) {
+        self.0.push((expr1, expr2))
+    }
+
+    pub fn get(&self, expr: &Expr) -> Expr {
+        self.0
+            .iter()
+            .rev()
+            .find(|(expr1, _)| expr1 == expr)
+            .unwrap()
+            .1
+            .clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn last(&self) -> (Expr, Expr) {
+        self.0.last().cloned().unwrap()
+    }
+
+    pub fn butlast(&self) -> Env {
+        let pairs = self.0[..self.0.len() - 1].to_vec();
+        Env(pairs)
+    }
+}
+
+impl fmt::Display for Env {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs = self
+            .0
+            .iter()
+            .map(|(expr1, expr2)| format!("{} = {}", expr1, expr2))
+            .collect::<Vec<_>>();
+        write!(f, "{}", pairs.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_ml2_ext::{
+        ast::{Env, Expr::*},
+        parser::parse_expr,
+    };
+
+    #[test]
+    fn test_env() {
+        let mut env = Env::new();
+        env.put(Var("x".to_string()), Int(1));
+        env.put(Var("y".to_string()), Int(2));
+        env.put(Var("x".to_string()), Int(3));
+        assert_eq!("x = 1, y = 2, x = 3", env.to_string());
+        assert_eq!(Int(3), env.get(&Var("x".to_string())));
+        assert_eq!(Int(2), env.get(&Var("y".to_string())));
+        assert_eq!(
+            "x = 1, y = 2",
+            Env::from(vec![
+                (Var("x".to_string()), Int(1)),
+                (Var("y".to_string()), Int(2)),
+            ])
+            .to_string()
+        );
+        assert_eq!((Var("x".to_string()), Int(3)), env.last());
+        assert_eq!(
+            Env::from(vec![
+                (Var("x".to_string()), Int(1)),
+                (Var("y".to_string()), Int(2))
+            ]),
+            env.butlast()
+        )
+    }
+
+    #[test]
+    fn test_display() {
+        // 表示した式を読み直すと元の式に戻る
+        for input in [
+            "8 - 2 - 3",
+            "8 - (2 - 3)",
+            "3 + if -23 < -2 * 8 then 8 else 2 + 4",
+            "3 + (if -23 < -2 * 8 then 8 else 2) + 4",
+            "(let x = 1 in x) * 2",
+            "1 < (2 < 3)",
+            "if let x = 1 in x < 2 then 3 else let y = 4 in y",
+            "- 3",
+            "-x * 2",
+            "-(x + 1)",
+            "a && b && c",
+            "(a || b) && c",
+            "x mod 2 = 1 || x / 2 > 3",
+        ] {
+            let expr = parse_expr(input).unwrap().1;
+            assert_eq!(input, expr.to_string());
+        }
+    }
+}
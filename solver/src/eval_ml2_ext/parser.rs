@@ -0,0 +1,247 @@
+use crate::{
+    eval_ml2_ext::ast::{Env, Expr, Expr::*},
+    syntax::{
+        error::{complete, expected, IResult, ParseError},
+        lexer::{ext_tokens, identifier, int, token, Token, TokenKind},
+    },
+};
+use nom::{
+    branch::alt,
+    combinator::{cut, opt},
+    multi::many0,
+    sequence::{delimited, preceded, tuple},
+};
+
+// 字句は syntax::lexer で拡張体系の予約語と記号を使って区切る
+// <judgment> ::= [ <env> '|-' ] <expr>
+// <env> ::= [ <var> '=' <expr> [ ',' <var> '=' <expr> ]* ]
+// <expr> ::= <and> [ '||' <expr> ]
+// <and> ::= <comp> [ '&&' <and> ]
+// <comp> ::= <term1> [ ( '<' | '>' | '=' ) <term1> ]
+// <term1> ::= <term2> [ ( '+' | '-' ) <term2> ]*
+// <term2> ::= <unary> [ ( '*' | '/' | 'mod' ) <unary> ]*
+// <unary> ::= '-' <unary> | <factor>
+// <factor> ::= <value> | <paren> | <if> | <let> | <var>
+// <value> ::= <int> | <bool>
+// <int> ::= 数値
+// <bool> ::= 'true' | 'false'
+// <paren> ::= '(' <expr> ')'
+// <if> ::= 'if' <expr> 'then' <expr> 'else' <expr>
+// <let> ::= 'let' <var> '=' <expr> 'in' <expr>
+// <var> ::= 英字か '_' で始まる英数字と '_', '\'' の列 (予約語を除く)
+
+pub fn parse(input: &str) -> Result<(Env, Expr), ParseError> {
+    complete(input, parse_env_expr)
+}
+
+pub fn parse_env_expr(input: &str) -> IResult<&str, (Env, Expr)> {
+    ext_tokens(env_expr)(input)
+}
+
+pub fn parse_expr(input: &str) -> IResult<&str, Expr> {
+    ext_tokens(expr)(input)
+}
+
+pub fn parse_value(input: &str) -> IResult<&str, Expr> {
+    ext_tokens(value)(input)
+}
+
+fn env_expr(input: &[Token]) -> IResult<&[Token], (Env, Expr)> {
+    let turnstile = TokenKind::Symbol("|-");
+    if input.iter().any(|token| token.kind == turnstile) {
+        tuple((env, cut(expr)))(input)
+    } else {
+        let (input, expr) = expr(input)?;
+        Ok((input, (Env::new(), expr)))
+    }
+}
+
+// '|-' までを環境として読む
+fn env(input: &[Token]) -> IResult<&[Token], Env> {
+    let mut env = Env::new();
+    if let Ok((input, _)) = token("|-")(input) {
+        return Ok((input, env));
+    }
+    let mut input = input;
+    loop {
+        let (rest, (expr1, _, expr2, sep)) =
+            tuple((var, token("="), expr, alt((token(","), token("|-")))))(input)?;
+        env.put(expr1, expr2);
+        input = rest;
+        if sep == "|-" {
+            return Ok((input, env));
+        }
+    }
+}
+
+fn expr(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (expr1, expr2)) = tuple((and, opt(preceded(token("||"), cut(expr)))))(input)?;
+    let expr = match expr2 {
+        Some(expr2) => Or(Box::new(expr1), Box::new(expr2)),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+fn and(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (expr1, expr2)) = tuple((comp, opt(preceded(token("&&"), cut(and)))))(input)?;
+    let expr = match expr2 {
+        Some(expr2) => And(Box::new(expr1), Box::new(expr2)),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+// 演算子を読んだら右辺は省略できない
+fn comp(input: &[Token]) -> IResult<&[Token], Expr> {
+    let op = alt((token("<"), token(">"), token("=")));
+    let (input, (expr1, expr2)) = tuple((term1, opt(tuple((op, cut(term1))))))(input)?;
+    let expr = match expr2 {
+        Some(("<", expr2)) => Lt(Box::new(expr1), Box::new(expr2)),
+        Some((">", expr2)) => Gt(Box::new(expr1), Box::new(expr2)),
+        Some(("=", expr2)) => Eq(Box::new(expr1), Box::new(expr2)),
+        Some(_) => unreachable!(),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+fn term1(input: &[Token]) -> IResult<&[Token], Expr> {
+    let op = alt((token("+"), token("-")));
+    let (input, (expr, exprs)) = tuple((term2, many0(tuple((op, cut(term2))))))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match *op {
+        "+" => Plus(Box::new(expr1), Box::new(expr2.clone())),
+        "-" => Minus(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+fn term2(input: &[Token]) -> IResult<&[Token], Expr> {
+    let op = alt((token("*"), token("/"), token("mod")));
+    let (input, (expr, exprs)) = tuple((unary, many0(tuple((op, cut(unary))))))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match *op {
+        "*" => Times(Box::new(expr1), Box::new(expr2.clone())),
+        "/" => Div(Box::new(expr1), Box::new(expr2.clone())),
+        "mod" => Mod(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+// '-' の直後に数字があれば負の整数として factor で読む
+fn unary(input: &[Token]) -> IResult<&[Token], Expr> {
+    if int(input).is_ok() {
+        return factor(input);
+    }
+    match token("-")(input) {
+        Ok((input, _)) => {
+            let (input, expr) = cut(unary)(input)?;
+            Ok((input, Neg(Box::new(expr))))
+        }
+        Err(_) => factor(input),
+    }
+}
+
+fn factor(input: &[Token]) -> IResult<&[Token], Expr> {
+    expected("expression", alt((value, paren, if_expr, let_expr, var)))(input)
+}
+
+fn value(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, expr) = alt((int_value, bool_value))(input)?;
+    Ok((input, expr))
+}
+
+fn int_value(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, i) = int(input)?;
+    Ok((input, Int(i)))
+}
+
+fn bool_value(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, b) = alt((token("true"), token("false")))(input)?;
+    Ok((input, Bool(b == "true")))
+}
+
+fn paren(input: &[Token]) -> IResult<&[Token], Expr> {
+    delimited(token("("), cut(expr), cut(token(")")))(input)
+}
+
+// 予約語を読んだら残りは省略できない
+fn if_expr(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (_, (expr1, _, expr2, _, expr3))) = tuple((
+        token("if"),
+        cut(tuple((expr, token("then"), expr, token("else"), expr))),
+    ))(input)?;
+    let expr = If(Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+fn let_expr(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, (_, (expr1, _, expr2, _, expr3))) = tuple((
+        token("let"),
+        cut(tuple((var, token("="), expr, token("in"), expr))),
+    ))(input)?;
+    let expr = Let(Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+fn var(input: &[Token]) -> IResult<&[Token], Expr> {
+    let (input, s) = expected("variable", identifier)(input)?;
+    Ok((input, Var(s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_ml2_ext::{
+        ast::{Env, Expr::*},
+        parser::parse,
+    };
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(
+            parse("x = 3 |- x mod 2 = 1 || x > 5").unwrap(),
+            (
+                Env::from(vec![(Var("x".to_string()), Int(3))]),
+                Or(
+                    Box::new(Eq(
+                        Box::new(Mod(Box::new(Var("x".to_string())), Box::new(Int(2)))),
+                        Box::new(Int(1))
+                    )),
+                    Box::new(Gt(Box::new(Var("x".to_string())), Box::new(Int(5))))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse("|- let x = -y / 2 in x && true").unwrap(),
+            (
+                Env::new(),
+                Let(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Div(
+                        Box::new(Neg(Box::new(Var("y".to_string())))),
+                        Box::new(Int(2))
+                    )),
+                    Box::new(And(Box::new(Var("x".to_string())), Box::new(Bool(true))))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_var() {
+        assert!(parse("|- let mod = 1 in mod").is_err());
+        assert_eq!(
+            parse("|- let modulo = 1 in modulo").unwrap().1,
+            Let(
+                Box::new(Var("modulo".to_string())),
+                Box::new(Int(1)),
+                Box::new(Var("modulo".to_string()))
+            )
+        );
+    }
+}
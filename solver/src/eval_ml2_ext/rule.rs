@@ -0,0 +1,449 @@
+use crate::{
+    eval_ml2_ext::ast::{Env, Expr, Expr::*},
+    util::{env_prefix, ident},
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    EInt(Env, i64, usize),
+    EBool(Env, bool, usize),
+    EIfT(Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EIfF(Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EPlus(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    EMinus(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ETimes(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ELt(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    BPlus(Expr, Expr, Expr, usize),
+    BMinus(Expr, Expr, Expr, usize),
+    BTimes(Expr, Expr, Expr, usize),
+    BLt(Expr, Expr, Expr, usize),
+    EDiv(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    EMod(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    EEq(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    EGt(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    EAndT(Env, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EAndF(Env, Expr, Expr, Box<Rule>, usize),
+    EOrT(Env, Expr, Expr, Box<Rule>, usize),
+    EOrF(Env, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    ENeg(Env, Expr, Box<Rule>, Box<Rule>, usize),
+    BDiv(Expr, Expr, Expr, usize),
+    BMod(Expr, Expr, Expr, usize),
+    BEq(Expr, Expr, Expr, usize),
+    BGt(Expr, Expr, Expr, usize),
+    BNeg(Expr, Expr, usize),
+    EVar1(Env, Expr, usize),
+    EVar2(Env, Expr, Box<Rule>, usize),
+    ELet(Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+}
+
+impl Rule {
+    pub fn evaluated(&self) -> Expr {
+        use crate::eval_ml2_ext::rule::Rule::*;
+
+        match self {
+            EInt(_, i, _) => Int(*i),
+            EBool(_, b, _) => Bool(*b),
+            EIfT(_, _, _, _, _, rule2, _) => rule2.evaluated(),
+            EIfF(_, _, _, _, _, rule2, _) => rule2.evaluated(),
+            EPlus(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            EMinus(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ETimes(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ELt(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            BPlus(_, _, expr3, _) => expr3.clone(),
+            BMinus(_, _, expr3, _) => expr3.clone(),
+            BTimes(_, _, expr3, _) => expr3.clone(),
+            BLt(_, _, expr3, _) => expr3.clone(),
+            EDiv(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            EMod(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            EEq(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            EGt(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            EAndT(_, _, _, _, rule2, _) => rule2.evaluated(),
+            EAndF(..) => Bool(false),
+            EOrT(..) => Bool(true),
+            EOrF(_, _, _, _, rule2, _) => rule2.evaluated(),
+            ENeg(_, _, _, rule2, _) => rule2.evaluated(),
+            BDiv(_, _, expr3, _) => expr3.clone(),
+            BMod(_, _, expr3, _) => expr3.clone(),
+            BEq(_, _, expr3, _) => expr3.clone(),
+            BGt(_, _, expr3, _) => expr3.clone(),
+            BNeg(_, expr2, _) => expr2.clone(),
+            EVar1(env, _, _) => env.last().1,
+            EVar2(_, _, rule, _) => rule.evaluated(),
+            ELet(_, _, _, _, _, rule2, _) => rule2.evaluated(),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_ml2_ext::rule::Rule::*;
+
+        match self {
+            EInt(env, i, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Int {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    i,
+                    i
+                )
+            }
+            EBool(env, b, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Bool {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    b,
+                    b
+                )
+            }
+            EIfT(env, expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- if {} then {} else {} evalto {} by E-IfT {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EIfF(env, expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- if {} then {} else {} evalto {} by E-IfF {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EPlus(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Plus {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMinus(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Minus {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ETimes(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Times {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELt(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Lt {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            BPlus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} plus {} is {} by B-Plus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BMinus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} minus {} is {} by B-Minus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BTimes(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} times {} is {} by B-Times {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BLt(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} less than {} is {} by B-Lt {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            EDiv(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Div(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Div {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMod(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Mod(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Mod {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EEq(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Eq(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Eq {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EGt(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Gt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Gt {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EAndT(env, expr1, expr2, rule1, rule2, depth) => {
+                let expr = And(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-AndT {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EAndF(env, expr1, expr2, rule1, depth) => {
+                let expr = And(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-AndF {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule1)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EOrT(env, expr1, expr2, rule1, depth) => {
+                let expr = Or(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-OrT {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule1)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EOrF(env, expr1, expr2, rule1, rule2, depth) => {
+                let expr = Or(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-OrF {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ENeg(env, expr1, rule1, rule2, depth) => {
+                let expr = Neg(Box::new(expr1.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Neg {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            BDiv(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} div {} is {} by B-Div {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BMod(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} mod {} is {} by B-Mod {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BEq(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} equals {} is {} by B-Eq {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BGt(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} greater than {} is {} by B-Gt {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BNeg(expr1, expr2, depth) => {
+                write!(
+                    f,
+                    "{}minus {} is {} by B-Neg {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2
+                )
+            }
+            EVar1(env, expr, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Var1 {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )
+            }
+            EVar2(env, expr, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Var2 {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELet(env, expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- let {} = {} in {} evalto {} by E-Let {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+        }
+    }
+}
@@ -11,29 +11,124 @@ pub enum Expr {
     Lt(Box<Expr>, Box<Expr>),
     Let(Box<Expr>, Box<Expr>, Box<Expr>),
     Var(String),
+    Fun(Box<Expr>, Box<Expr>),
+    App(Box<Expr>, Box<Expr>),
+    LetRec(Box<Expr>, Box<Expr>, Box<Expr>, Box<Expr>),
+    Closure(Env, Box<Expr>, Box<Expr>),
+    RecClosure(Env, Box<Expr>, Box<Expr>, Box<Expr>),
 }
 
-impl fmt::Display for Expr {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl Expr {
+    // 結合の強さ (大きいほど強い)
+    fn precedence(&self) -> usize {
         use crate::eval_ml3::ast::Expr::*;
 
         match self {
-            Int(i) => write!(f, "{}", i),
-            Bool(b) => write!(f, "{}", b),
-            If(expr1, expr2, expr3) => write!(f, "(if {} then {} else {})", expr1, expr2, expr3),
-            Plus(expr1, expr2) => write!(f, "({} + {})", expr1, expr2),
-            Minus(expr1, expr2) => write!(f, "({} - {})", expr1, expr2),
-            Times(expr1, expr2) => write!(f, "({} * {})", expr1, expr2),
-            Lt(expr1, expr2) => write!(f, "({} < {})", expr1, expr2),
+            If(_, _, _) | Let(_, _, _) | Fun(_, _) | LetRec(_, _, _, _) => 0,
+            Lt(_, _) => 1,
+            Plus(_, _) | Minus(_, _) => 3,
+            Times(_, _) => 4,
+            App(_, _) => 5,
+            Int(i) if *i < 0 => 5,
+            _ => 6,
+        }
+    }
+
+    // 右にできるだけ伸びる式 (右端にあれば括弧がいらない)
+    fn is_open(&self) -> bool {
+        use crate::eval_ml3::ast::Expr::*;
+
+        matches!(
+            self,
+            If(_, _, _) | Let(_, _, _) | Fun(_, _) | LetRec(_, _, _, _)
+        )
+    }
+
+    fn paren(&self, paren: bool) -> String {
+        if paren {
+            format!("({})", self)
+        } else {
+            self.to_string()
+        }
+    }
+
+    // tail は後ろに何も続かないこと
+    fn show(&self, tail: bool) -> String {
+        use crate::eval_ml3::ast::Expr::*;
+
+        let p = self.precedence();
+        let left = |expr: &Expr, paren: bool| {
+            if paren {
+                expr.paren(true)
+            } else {
+                expr.show(false)
+            }
+        };
+        let right = |expr: &Expr, paren: bool| {
+            if paren && !(tail && expr.is_open()) {
+                expr.paren(true)
+            } else {
+                expr.show(tail)
+            }
+        };
+        match self {
+            Int(i) => i.to_string(),
+            Bool(b) => b.to_string(),
+            If(expr1, expr2, expr3) => {
+                format!("if {} then {} else {}", expr1, expr2, expr3.show(tail))
+            }
+            Plus(expr1, expr2) => format!(
+                "{} + {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Minus(expr1, expr2) => format!(
+                "{} - {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Times(expr1, expr2) => format!(
+                "{} * {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Lt(expr1, expr2) => format!(
+                "{} < {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
             Let(expr1, expr2, expr3) => {
-                write!(f, "(let {} = {} in {})", expr1, expr2, expr3)
+                format!("let {} = {} in {}", expr1, expr2, expr3.show(tail))
+            }
+            Var(s) => s.to_string(),
+            Fun(expr1, expr2) => format!("fun {} -> {}", expr1, expr2.show(tail)),
+            App(expr1, expr2) => format!(
+                "{} {}",
+                left(expr1, expr1.precedence() < p),
+                expr2.paren(expr2.precedence() <= p)
+            ),
+            LetRec(expr1, expr2, expr3, expr4) => format!(
+                "let rec {} = fun {} -> {} in {}",
+                expr1,
+                expr2,
+                expr3,
+                expr4.show(tail)
+            ),
+            Closure(env, expr1, expr2) => format!("({})[fun {} -> {}]", env, expr1, expr2),
+            RecClosure(env, expr1, expr2, expr3) => {
+                format!("({})[rec {} = fun {} -> {}]", env, expr1, expr2, expr3)
             }
-            Var(s) => write!(f, "{}", s),
         }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, PartialOrd)]
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.show(true))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
 pub struct Env(Vec<(Expr, Expr)>);
 
 impl Env {
@@ -59,8 +154,12 @@ impl Env {
             .clone()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     pub fn last(&self) -> (Expr, Expr) {
-        self.0.last().map(|pair| pair.clone()).unwrap()
+        self.0.last().cloned().unwrap()
     }
 
     pub fn butlast(&self) -> Env {
@@ -110,4 +209,36 @@ mod tests {
             env.butlast()
         )
     }
+
+    #[test]
+    fn test_closure() {
+        let env = Env::from(vec![
+            (Var("x".to_string()), Int(1)),
+            (
+                Var("f".to_string()),
+                Closure(
+                    Env::from(vec![(Var("y".to_string()), Int(2))]),
+                    Box::new(Var("z".to_string())),
+                    Box::new(Plus(
+                        Box::new(Var("y".to_string())),
+                        Box::new(Var("z".to_string())),
+                    )),
+                ),
+            ),
+        ]);
+        assert_eq!("x = 1, f = (y = 2)[fun z -> y + z]", env.to_string());
+        assert_eq!(
+            "()[rec f = fun x -> f x]",
+            RecClosure(
+                Env::new(),
+                Box::new(Var("f".to_string())),
+                Box::new(Var("x".to_string())),
+                Box::new(App(
+                    Box::new(Var("f".to_string())),
+                    Box::new(Var("x".to_string())),
+                )),
+            )
+            .to_string()
+        );
+    }
 }
@@ -2,8 +2,8 @@ use crate::eval_ml3::ast::{Env, Expr, Expr::*};
 
 pub fn eval(env: &Env, expr: &Expr) -> Expr {
     match expr {
-        Int(i) => Int(i.clone()),
-        Bool(b) => Bool(b.clone()),
+        Int(i) => Int(*i),
+        Bool(b) => Bool(*b),
         If(expr1, expr2, expr3) => match eval(env, expr1) {
             Bool(true) => eval(env, expr2),
             Bool(false) => eval(env, expr3),
@@ -33,6 +33,33 @@ pub fn eval(env: &Env, expr: &Expr) -> Expr {
             eval(&env, expr3)
         }
         Var(_) => env.get(expr),
+        Fun(expr1, expr2) => Closure(env.clone(), expr1.clone(), expr2.clone()),
+        App(expr1, expr2) => {
+            let expr1 = eval(env, expr1);
+            let expr2 = eval(env, expr2);
+            match expr1.clone() {
+                Closure(env, expr3, expr4) => {
+                    let mut env = env;
+                    env.put(*expr3, expr2);
+                    eval(&env, &expr4)
+                }
+                RecClosure(env, expr3, expr4, expr5) => {
+                    let mut env = env;
+                    env.put(*expr3, expr1);
+                    env.put(*expr4, expr2);
+                    eval(&env, &expr5)
+                }
+                _ => unreachable!(),
+            }
+        }
+        LetRec(expr1, expr2, expr3, expr4) => {
+            let closure = RecClosure(env.clone(), expr1.clone(), expr2.clone(), expr3.clone());
+            let mut env = env.clone();
+            env.put(*expr1.clone(), closure);
+            eval(&env, expr4)
+        }
+        Closure(_, _, _) => expr.clone(),
+        RecClosure(_, _, _, _) => expr.clone(),
     }
 }
 
@@ -225,4 +252,49 @@ mod tests {
             Int(12)
         );
     }
+
+    #[test]
+    fn test_eval12() {
+        assert_eq!(
+            eval(
+                &Env::new(),
+                &Let(
+                    Box::new(Var("f".to_string())),
+                    Box::new(Fun(
+                        Box::new(Var("x".to_string())),
+                        Box::new(Plus(Box::new(Var("x".to_string())), Box::new(Int(1))))
+                    )),
+                    Box::new(App(Box::new(Var("f".to_string())), Box::new(Int(2))))
+                )
+            ),
+            Int(3)
+        );
+    }
+
+    #[test]
+    fn test_eval13() {
+        // let rec fact = fun n -> if n < 2 then 1 else n * fact (n - 1) in fact 3
+        assert_eq!(
+            eval(
+                &Env::new(),
+                &LetRec(
+                    Box::new(Var("fact".to_string())),
+                    Box::new(Var("n".to_string())),
+                    Box::new(If(
+                        Box::new(Lt(Box::new(Var("n".to_string())), Box::new(Int(2)))),
+                        Box::new(Int(1)),
+                        Box::new(Times(
+                            Box::new(Var("n".to_string())),
+                            Box::new(App(
+                                Box::new(Var("fact".to_string())),
+                                Box::new(Minus(Box::new(Var("n".to_string())), Box::new(Int(1))))
+                            ))
+                        ))
+                    )),
+                    Box::new(App(Box::new(Var("fact".to_string())), Box::new(Int(3))))
+                )
+            ),
+            Int(6)
+        );
+    }
 }
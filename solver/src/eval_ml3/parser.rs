@@ -1,12 +1,13 @@
 use crate::{
     eval_ml3::ast::{Env, Expr, Expr::*},
+    syntax::lexer::{keyword, name},
     util::ws,
 };
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{alphanumeric1, char, digit1},
-    combinator::{opt, recognize},
+    character::complete::{char, digit1},
+    combinator::{map_res, opt, recognize, verify},
     multi::many0,
     sequence::{delimited, tuple},
     IResult,
@@ -14,15 +15,25 @@ use nom::{
 
 // <expr> ::= <term1> [ <' <term1> ]
 // <term1> ::= <term2> [ ( '+' | '-' ) <term2> ]*
-// <term2> ::= <factor> [ '*' <factor> ]*
-// <factor> ::= <value> | <paren> | <if> | <let> | <var>
+// <term2> ::= <app> [ '*' <app> ]*
+// <app> ::= <factor> [ <arg> ]*
+// <factor> ::= <value> | <paren> | <if> | <let_rec> | <let> | <fun> | <var>
+// <arg> ::= <pos_int> | <bool> | <paren> | <var>
 // <value> ::= <int> | <bool>
 // <int> ::= 数値
 // <bool> ::= 'true' | 'false'
 // <paren> ::= '(' <expr> ')'
 // <if> ::= 'if' <expr> 'then' <expr> 'else' <expr>
+// <let_rec> ::= 'let' 'rec' <var> '=' 'fun' <var> '->' <expr> 'in' <expr>
 // <let> ::= 'let' <var> '=' <expr> 'in' <expr>
-// <var> ::= 文字列 | 数値
+// <fun> ::= 'fun' <var> '->' <expr>
+// <var> ::= 英字か '_' で始まる英数字と '_', '\'' の列 (予約語を除く)
+// <closure> ::= '(' <env> ')' '[' 'fun' <var> '->' <expr> ']'
+// <rec_closure> ::= '(' <env> ')' '[' 'rec' <var> '=' 'fun' <var> '->' <expr> ']'
+
+const KEYWORDS: [&str; 9] = [
+    "if", "then", "else", "let", "rec", "in", "fun", "true", "false",
+];
 
 pub fn parse(input: &str) -> IResult<&str, (Env, Expr)> {
     match input.find("|-") {
@@ -39,6 +50,11 @@ pub fn parse(input: &str) -> IResult<&str, (Env, Expr)> {
     }
 }
 
+// 評価結果 (クロージャを含む)
+pub fn parse_result(input: &str) -> IResult<&str, Expr> {
+    alt((parse_closure, parse_rec_closure, parse_expr))(input)
+}
+
 fn parse_env(input: &str) -> IResult<&str, Env> {
     let mut env = Env::new();
     let (input, pair) = opt(parse_pair)(input)?;
@@ -56,7 +72,8 @@ fn parse_env(input: &str) -> IResult<&str, Env> {
 }
 
 fn parse_pair(input: &str) -> IResult<&str, (Expr, Expr)> {
-    let (input, (expr1, _, expr2)) = tuple((parse_var, ws(char('=')), parse_expr))(input)?;
+    let parse_value = alt((parse_closure, parse_rec_closure, parse_expr));
+    let (input, (expr1, _, expr2)) = tuple((parse_var, ws(char('=')), parse_value))(input)?;
     let pair = (expr1, expr2);
     Ok((input, pair))
 }
@@ -67,7 +84,7 @@ fn parse_pairs(input: &str) -> IResult<&str, Vec<(Expr, Expr)>> {
     Ok((input, pairs))
 }
 
-fn parse_expr(input: &str) -> IResult<&str, Expr> {
+pub fn parse_expr(input: &str) -> IResult<&str, Expr> {
     let (input, (expr1, expr2)) = tuple((parse_term1, opt(parse_lt)))(input)?;
     let expr = match expr2 {
         Some(expr2) => Lt(Box::new(expr1), Box::new(expr2)),
@@ -100,7 +117,7 @@ fn parse_plus_minus(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
 }
 
 fn parse_term2(input: &str) -> IResult<&str, Expr> {
-    let (input, (expr, exprs)) = tuple((parse_factor, parse_times))(input)?;
+    let (input, (expr, exprs)) = tuple((parse_app, parse_times))(input)?;
     let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
         '*' => Times(Box::new(expr1), Box::new(expr2.clone())),
         _ => unreachable!(),
@@ -110,12 +127,37 @@ fn parse_term2(input: &str) -> IResult<&str, Expr> {
 
 fn parse_times(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
     let parse_times = ws(char('*'));
-    let (input, exprs) = many0(tuple((parse_times, parse_factor)))(input)?;
+    let (input, exprs) = many0(tuple((parse_times, parse_app)))(input)?;
     Ok((input, exprs))
 }
 
+fn parse_app(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_factor, many0(parse_arg)))(input)?;
+    let expr = exprs
+        .into_iter()
+        .fold(expr, |expr1, expr2| App(Box::new(expr1), Box::new(expr2)));
+    Ok((input, expr))
+}
+
 fn parse_factor(input: &str) -> IResult<&str, Expr> {
-    let (input, expr) = alt((parse_value, parse_paren, parse_if, parse_let, parse_var))(input)?;
+    let (input, expr) = alt((
+        parse_value,
+        parse_paren,
+        parse_if,
+        parse_let_rec,
+        parse_let,
+        parse_fun,
+        parse_var,
+    ))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_arg(input: &str) -> IResult<&str, Expr> {
+    let parse_pos_int = |input| {
+        let (input, i) = ws(parse_pos_number)(input)?;
+        Ok((input, Int(i)))
+    };
+    let (input, expr) = alt((parse_pos_int, parse_bool, parse_paren, parse_var))(input)?;
     Ok((input, expr))
 }
 
@@ -131,15 +173,13 @@ fn parse_int(input: &str) -> IResult<&str, Expr> {
 }
 
 fn parse_pos_number(input: &str) -> IResult<&str, i64> {
-    let (input, i) = digit1(input)?;
-    let i = i.parse::<i64>().unwrap();
-    Ok((input, i))
+    map_res(digit1, |i: &str| i.parse::<i64>())(input)
 }
 
 fn parse_neg_number(input: &str) -> IResult<&str, i64> {
-    let (input, i) = recognize(tuple((char('-'), digit1)))(input)?;
-    let i = i.parse::<i64>().unwrap();
-    Ok((input, i))
+    map_res(recognize(tuple((char('-'), digit1))), |i: &str| {
+        i.parse::<i64>()
+    })(input)
 }
 
 fn parse_bool(input: &str) -> IResult<&str, Expr> {
@@ -149,12 +189,12 @@ fn parse_bool(input: &str) -> IResult<&str, Expr> {
 }
 
 fn parse_true(input: &str) -> IResult<&str, bool> {
-    let (input, _) = ws(tag("true"))(input)?;
+    let (input, _) = ws(keyword("true"))(input)?;
     Ok((input, true))
 }
 
 fn parse_false(input: &str) -> IResult<&str, bool> {
-    let (input, _) = ws(tag("false"))(input)?;
+    let (input, _) = ws(keyword("false"))(input)?;
     Ok((input, false))
 }
 
@@ -167,11 +207,11 @@ fn parse_paren(input: &str) -> IResult<&str, Expr> {
 
 fn parse_if(input: &str) -> IResult<&str, Expr> {
     let (input, (_, expr1, _, expr2, _, expr3)) = tuple((
-        ws(tag("if")),
+        ws(keyword("if")),
         parse_expr,
-        ws(tag("then")),
+        ws(keyword("then")),
         parse_expr,
-        ws(tag("else")),
+        ws(keyword("else")),
         parse_expr,
     ))(input)?;
     let expr = If(Box::new(expr1), Box::new(expr2), Box::new(expr3));
@@ -180,28 +220,96 @@ fn parse_if(input: &str) -> IResult<&str, Expr> {
 
 fn parse_let(input: &str) -> IResult<&str, Expr> {
     let (input, (_, expr1, _, expr2, _, expr3)) = tuple((
-        ws(tag("let")),
+        ws(keyword("let")),
         parse_var,
         ws(char('=')),
         parse_expr,
-        ws(tag("in")),
+        ws(keyword("in")),
         parse_expr,
     ))(input)?;
     let expr = Let(Box::new(expr1), Box::new(expr2), Box::new(expr3));
     Ok((input, expr))
 }
 
-fn parse_var(input: &str) -> IResult<&str, Expr> {
-    let (input, s) = ws(alphanumeric1)(input)?;
+fn parse_let_rec(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, _, expr1, _, _, expr2, _, expr3, _, expr4)) = tuple((
+        ws(keyword("let")),
+        ws(keyword("rec")),
+        parse_var,
+        ws(char('=')),
+        ws(keyword("fun")),
+        parse_var,
+        ws(tag("->")),
+        parse_expr,
+        ws(keyword("in")),
+        parse_expr,
+    ))(input)?;
+    let expr = LetRec(
+        Box::new(expr1),
+        Box::new(expr2),
+        Box::new(expr3),
+        Box::new(expr4),
+    );
+    Ok((input, expr))
+}
+
+fn parse_fun(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr1, _, expr2)) =
+        tuple((ws(keyword("fun")), parse_var, ws(tag("->")), parse_expr))(input)?;
+    let expr = Fun(Box::new(expr1), Box::new(expr2));
+    Ok((input, expr))
+}
+
+fn parse_closure(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, env, _, _, _, expr1, _, expr2, _)) = tuple((
+        ws(char('(')),
+        parse_env,
+        ws(char(')')),
+        ws(char('[')),
+        ws(keyword("fun")),
+        parse_var,
+        ws(tag("->")),
+        parse_expr,
+        ws(char(']')),
+    ))(input)?;
+    let expr = Closure(env, Box::new(expr1), Box::new(expr2));
+    Ok((input, expr))
+}
+
+fn parse_rec_closure(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, env, _, _, _, expr1, _, _, expr2, _, expr3, _)) = tuple((
+        ws(char('(')),
+        parse_env,
+        ws(char(')')),
+        ws(char('[')),
+        ws(keyword("rec")),
+        parse_var,
+        ws(char('=')),
+        ws(keyword("fun")),
+        parse_var,
+        ws(tag("->")),
+        parse_expr,
+        ws(char(']')),
+    ))(input)?;
+    let expr = RecClosure(env, Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+pub fn parse_var(input: &str) -> IResult<&str, Expr> {
+    let (input, s) = ws(parse_ident)(input)?;
     let expr = Var(s.to_string());
     Ok((input, expr))
 }
 
+fn parse_ident(input: &str) -> IResult<&str, &str> {
+    verify(name, |s: &str| !KEYWORDS.contains(&s))(input)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::eval_ml3::{
         ast::{Env, Expr::*},
-        parser::parse,
+        parser::{parse, parse_var},
     };
 
     #[test]
@@ -390,4 +498,168 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_parse12() {
+        assert_eq!(
+            parse("|- f x + g 1 2").unwrap().1,
+            (
+                Env::new(),
+                Plus(
+                    Box::new(App(
+                        Box::new(Var("f".to_string())),
+                        Box::new(Var("x".to_string()))
+                    )),
+                    Box::new(App(
+                        Box::new(App(Box::new(Var("g".to_string())), Box::new(Int(1)))),
+                        Box::new(Int(2))
+                    ))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse13() {
+        assert_eq!(
+            parse("|- let f = fun x -> x - 1 in if f 3 < 1 then 0 else f (f 2)")
+                .unwrap()
+                .1,
+            (
+                Env::new(),
+                Let(
+                    Box::new(Var("f".to_string())),
+                    Box::new(Fun(
+                        Box::new(Var("x".to_string())),
+                        Box::new(Minus(Box::new(Var("x".to_string())), Box::new(Int(1))))
+                    )),
+                    Box::new(If(
+                        Box::new(Lt(
+                            Box::new(App(Box::new(Var("f".to_string())), Box::new(Int(3)))),
+                            Box::new(Int(1))
+                        )),
+                        Box::new(Int(0)),
+                        Box::new(App(
+                            Box::new(Var("f".to_string())),
+                            Box::new(App(Box::new(Var("f".to_string())), Box::new(Int(2))))
+                        ))
+                    ))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse14() {
+        assert_eq!(
+            parse("|- let rec f = fun x -> f x in f 1").unwrap().1,
+            (
+                Env::new(),
+                LetRec(
+                    Box::new(Var("f".to_string())),
+                    Box::new(Var("x".to_string())),
+                    Box::new(App(
+                        Box::new(Var("f".to_string())),
+                        Box::new(Var("x".to_string()))
+                    )),
+                    Box::new(App(Box::new(Var("f".to_string())), Box::new(Int(1))))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse15() {
+        assert_eq!(
+            parse("y = 2, f = (y = 2)[fun x -> x + y], g = ()[rec g = fun x -> g x] |- f 1")
+                .unwrap()
+                .1,
+            (
+                Env::from(vec![
+                    (Var("y".to_string()), Int(2)),
+                    (
+                        Var("f".to_string()),
+                        Closure(
+                            Env::from(vec![(Var("y".to_string()), Int(2))]),
+                            Box::new(Var("x".to_string())),
+                            Box::new(Plus(
+                                Box::new(Var("x".to_string())),
+                                Box::new(Var("y".to_string()))
+                            ))
+                        )
+                    ),
+                    (
+                        Var("g".to_string()),
+                        RecClosure(
+                            Env::new(),
+                            Box::new(Var("g".to_string())),
+                            Box::new(Var("x".to_string())),
+                            Box::new(App(
+                                Box::new(Var("g".to_string())),
+                                Box::new(Var("x".to_string()))
+                            ))
+                        )
+                    )
+                ]),
+                App(Box::new(Var("f".to_string())), Box::new(Int(1)))
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse16() {
+        assert_eq!(
+            parse("|- fun x -> fun y -> x y 1").unwrap().1,
+            (
+                Env::new(),
+                Fun(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Fun(
+                        Box::new(Var("y".to_string())),
+                        Box::new(App(
+                            Box::new(App(
+                                Box::new(Var("x".to_string())),
+                                Box::new(Var("y".to_string()))
+                            )),
+                            Box::new(Int(1))
+                        ))
+                    ))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_keyword_prefix() {
+        assert_eq!(
+            parse("f truex").unwrap().1,
+            (
+                Env::new(),
+                App(
+                    Box::new(Var("f".to_string())),
+                    Box::new(Var("truex".to_string()))
+                )
+            )
+        );
+        assert_eq!(
+            parse("letx").unwrap().1,
+            (Env::new(), Var("letx".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_ident() {
+        assert_eq!(
+            parse("|- fun _x1 -> _x1").unwrap().1,
+            (
+                Env::new(),
+                Fun(
+                    Box::new(Var("_x1".to_string())),
+                    Box::new(Var("_x1".to_string()))
+                )
+            )
+        );
+        assert!(parse("fun 1 -> 1").is_err());
+        assert!(parse_var("123").is_err());
+    }
 }
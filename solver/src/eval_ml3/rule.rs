@@ -1,6 +1,6 @@
 use crate::{
     eval_ml3::ast::{Env, Expr, Expr::*},
-    util::ident,
+    util::{env_prefix, ident},
 };
 use std::fmt;
 
@@ -21,6 +21,10 @@ pub enum Rule {
     EVar1(Env, Expr, usize),
     EVar2(Env, Expr, Box<Rule>, usize),
     ELet(Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EFun(Env, Expr, Expr, usize),
+    EApp(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ELetRec(Env, Expr, Expr, Expr, Expr, Box<Rule>, usize),
+    EAppRec(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
 }
 
 impl Rule {
@@ -28,8 +32,8 @@ impl Rule {
         use crate::eval_ml3::rule::Rule::*;
 
         match self {
-            EInt(_, i, _) => Int(i.clone()),
-            EBool(_, b, _) => Bool(b.clone()),
+            EInt(_, i, _) => Int(*i),
+            EBool(_, b, _) => Bool(*b),
             EIfT(_, _, _, _, _, rule2, _) => rule2.evaluated(),
             EIfF(_, _, _, _, _, rule2, _) => rule2.evaluated(),
             EPlus(_, _, _, _, _, rule3, _) => rule3.evaluated(),
@@ -43,6 +47,14 @@ impl Rule {
             EVar1(env, _, _) => env.last().1,
             EVar2(_, _, rule, _) => rule.evaluated(),
             ELet(_, _, _, _, _, rule2, _) => rule2.evaluated(),
+            EFun(env, expr1, expr2, _) => Closure(
+                env.clone(),
+                Box::new(expr1.clone()),
+                Box::new(expr2.clone()),
+            ),
+            EApp(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ELetRec(_, _, _, _, _, rule, _) => rule.evaluated(),
+            EAppRec(_, _, _, _, _, rule3, _) => rule3.evaluated(),
         }
     }
 }
@@ -55,9 +67,9 @@ impl fmt::Display for Rule {
             EInt(env, i, depth) => {
                 write!(
                     f,
-                    "{}{} |- {} evalto {} by E-Int {{}}",
+                    "{}{}|- {} evalto {} by E-Int {{}}",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     i,
                     i
                 )
@@ -65,9 +77,9 @@ impl fmt::Display for Rule {
             EBool(env, b, depth) => {
                 write!(
                     f,
-                    "{}{} |- {} evalto {} by E-Bool {{}}",
+                    "{}{}|- {} evalto {} by E-Bool {{}}",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     b,
                     b
                 )
@@ -75,9 +87,9 @@ impl fmt::Display for Rule {
             EIfT(env, expr1, expr2, expr3, rule1, rule2, depth) => {
                 writeln!(
                     f,
-                    "{}{} |- if {} then {} else {} evalto {} by E-IfT {{",
+                    "{}{}|- if {} then {} else {} evalto {} by E-IfT {{",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     expr1,
                     expr2,
                     expr3,
@@ -90,9 +102,9 @@ impl fmt::Display for Rule {
             EIfF(env, expr1, expr2, expr3, rule1, rule2, depth) => {
                 writeln!(
                     f,
-                    "{}{} |- if {} then {} else {} evalto {} by E-IfF {{",
+                    "{}{}|- if {} then {} else {} evalto {} by E-IfF {{",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     expr1,
                     expr2,
                     expr3,
@@ -103,13 +115,13 @@ impl fmt::Display for Rule {
                 write!(f, "{}}}", ident(*depth))
             }
             EPlus(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
                 writeln!(
                     f,
-                    "{}{} |- {} + {} evalto {} by E-Plus {{",
+                    "{}{}|- {} evalto {} by E-Plus {{",
                     ident(*depth),
-                    env,
-                    expr1,
-                    expr2,
+                    env_prefix(env),
+                    expr,
                     self.evaluated()
                 )?;
                 writeln!(f, "{};", rule1)?;
@@ -118,13 +130,13 @@ impl fmt::Display for Rule {
                 write!(f, "{}}}", ident(*depth))
             }
             EMinus(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
                 writeln!(
                     f,
-                    "{}{} |- {} - {} evalto {} by E-Minus {{",
+                    "{}{}|- {} evalto {} by E-Minus {{",
                     ident(*depth),
-                    env,
-                    expr1,
-                    expr2,
+                    env_prefix(env),
+                    expr,
                     self.evaluated()
                 )?;
                 writeln!(f, "{};", rule1)?;
@@ -133,13 +145,13 @@ impl fmt::Display for Rule {
                 write!(f, "{}}}", ident(*depth))
             }
             ETimes(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
                 writeln!(
                     f,
-                    "{}{} |- {} * {} evalto {} by E-Times {{",
+                    "{}{}|- {} evalto {} by E-Times {{",
                     ident(*depth),
-                    env,
-                    expr1,
-                    expr2,
+                    env_prefix(env),
+                    expr,
                     self.evaluated()
                 )?;
                 writeln!(f, "{};", rule1)?;
@@ -148,13 +160,13 @@ impl fmt::Display for Rule {
                 write!(f, "{}}}", ident(*depth))
             }
             ELt(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
                 writeln!(
                     f,
-                    "{}{} |- {} < {} evalto {} by E-Lt {{",
+                    "{}{}|- {} evalto {} by E-Lt {{",
                     ident(*depth),
-                    env,
-                    expr1,
-                    expr2,
+                    env_prefix(env),
+                    expr,
                     self.evaluated()
                 )?;
                 writeln!(f, "{};", rule1)?;
@@ -192,21 +204,22 @@ impl fmt::Display for Rule {
                     expr3
                 )
             }
-            BLt(expr1, expr2, _, depth) => {
+            BLt(expr1, expr2, expr3, depth) => {
                 write!(
                     f,
-                    "{}{} is less than {} by B-Lt {{}}",
+                    "{}{} less than {} is {} by B-Lt {{}}",
                     ident(*depth),
                     expr1,
                     expr2,
+                    expr3
                 )
             }
             EVar1(env, expr, depth) => {
                 write!(
                     f,
-                    "{}{} |- {} evalto {} by E-Var1 {{}}",
+                    "{}{}|- {} evalto {} by E-Var1 {{}}",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     expr,
                     self.evaluated()
                 )
@@ -214,9 +227,9 @@ impl fmt::Display for Rule {
             EVar2(env, expr, rule, depth) => {
                 writeln!(
                     f,
-                    "{}{} |- {} evalto {} by E-Var2 {{",
+                    "{}{}|- {} evalto {} by E-Var2 {{",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     expr,
                     self.evaluated()
                 )?;
@@ -226,9 +239,9 @@ impl fmt::Display for Rule {
             ELet(env, expr1, expr2, expr3, rule1, rule2, depth) => {
                 writeln!(
                     f,
-                    "{}{} |- let {} = {} in {} evalto {} by E-Let {{",
+                    "{}{}|- let {} = {} in {} evalto {} by E-Let {{",
                     ident(*depth),
-                    env,
+                    env_prefix(env),
                     expr1,
                     expr2,
                     expr3,
@@ -238,6 +251,62 @@ impl fmt::Display for Rule {
                 writeln!(f, "{}", rule2)?;
                 write!(f, "{}}}", ident(*depth))
             }
+            EFun(env, expr1, expr2, depth) => {
+                write!(
+                    f,
+                    "{}{}|- fun {} -> {} evalto {} by E-Fun {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    self.evaluated()
+                )
+            }
+            EApp(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = App(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-App {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELetRec(env, expr1, expr2, expr3, expr4, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- let rec {} = fun {} -> {} in {} evalto {} by E-LetRec {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    expr3,
+                    expr4,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EAppRec(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = App(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-AppRec {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
         }
     }
 }
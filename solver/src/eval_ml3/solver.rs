@@ -1,17 +1,19 @@
+use crate::eval_error::{check_depth, checked, EvalError};
 use crate::eval_ml3::{
     ast::{Env, Expr, Expr::*},
     rule::{Rule, Rule::*},
 };
 
-pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
-    match expr {
-        Int(i) => EInt(env.clone(), i.clone(), depth),
-        Bool(b) => EBool(env.clone(), b.clone(), depth),
+pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Result<Rule, EvalError> {
+    check_depth(depth)?;
+    let rule = match expr {
+        Int(i) => EInt(env.clone(), *i, depth),
+        Bool(b) => EBool(env.clone(), *b, depth),
         If(expr1, expr2, expr3) => {
-            let rule1 = solve(env, expr1, depth + 1);
+            let rule1 = solve(env, expr1, depth + 1)?;
             match rule1.evaluated() {
                 Bool(true) => {
-                    let rule2 = solve(env, expr2, depth + 1);
+                    let rule2 = solve(env, expr2, depth + 1)?;
                     EIfT(
                         env.clone(),
                         *expr1.clone(),
@@ -23,7 +25,7 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
                     )
                 }
                 Bool(false) => {
-                    let rule2 = solve(env, expr3, depth + 1);
+                    let rule2 = solve(env, expr3, depth + 1)?;
                     EIfF(
                         env.clone(),
                         *expr1.clone(),
@@ -34,15 +36,15 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
                         depth,
                     )
                 }
-                _ => unreachable!(),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             }
         }
         Plus(expr1, expr2) => {
-            let rule1 = solve(env, expr1, depth + 1);
-            let rule2 = solve(env, expr2, depth + 1);
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
             let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
-                (Int(i1), Int(i2)) => Int(i1 + i2),
-                _ => unreachable!(),
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_add(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             };
             let rule3 = BPlus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
             EPlus(
@@ -56,11 +58,11 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
             )
         }
         Minus(expr1, expr2) => {
-            let rule1 = solve(env, expr1, depth + 1);
-            let rule2 = solve(env, expr2, depth + 1);
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
             let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
-                (Int(i1), Int(i2)) => Int(i1 - i2),
-                _ => unreachable!(),
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_sub(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             };
             let rule3 = BMinus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
             EMinus(
@@ -74,11 +76,11 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
             )
         }
         Times(expr1, expr2) => {
-            let rule1 = solve(env, expr1, depth + 1);
-            let rule2 = solve(env, expr2, depth + 1);
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
             let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
-                (Int(i1), Int(i2)) => Int(i1 * i2),
-                _ => unreachable!(),
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_mul(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             };
             let rule3 = BTimes(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
             ETimes(
@@ -92,11 +94,11 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
             )
         }
         Lt(expr1, expr2) => {
-            let rule1 = solve(env, expr1, depth + 1);
-            let rule2 = solve(env, expr2, depth + 1);
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
             let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
                 (Int(i1), Int(i2)) => Bool(i1 < i2),
-                _ => unreachable!(),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
             };
             let rule3 = BLt(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
             ELt(
@@ -110,18 +112,21 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
             )
         }
         Var(_) => {
+            if env.is_empty() {
+                return Err(EvalError::Unbound(expr.to_string()));
+            }
             if env.last().0 == expr.clone() {
                 EVar1(env.clone(), expr.clone(), depth)
             } else {
-                let rule = solve(&env.butlast(), expr, depth + 1);
+                let rule = solve(&env.butlast(), expr, depth + 1)?;
                 EVar2(env.clone(), expr.clone(), Box::new(rule), depth)
             }
         }
         Let(expr1, expr2, expr3) => {
-            let rule1 = solve(env, expr2, depth + 1);
+            let rule1 = solve(env, expr2, depth + 1)?;
             let mut new_env = env.clone();
             new_env.put(*expr1.clone(), rule1.evaluated());
-            let rule2 = solve(&new_env, expr3, depth + 1);
+            let rule2 = solve(&new_env, expr3, depth + 1)?;
             ELet(
                 env.clone(),
                 *expr1.clone(),
@@ -132,7 +137,62 @@ pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Rule {
                 depth,
             )
         }
-    }
+        Fun(expr1, expr2) => EFun(env.clone(), *expr1.clone(), *expr2.clone(), depth),
+        App(expr1, expr2) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
+            match rule1.evaluated() {
+                Closure(env2, expr3, expr4) => {
+                    let mut new_env = env2;
+                    new_env.put(*expr3, rule2.evaluated());
+                    let rule3 = solve(&new_env, &expr4, depth + 1)?;
+                    EApp(
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        Box::new(rule3),
+                        depth,
+                    )
+                }
+                RecClosure(env2, expr3, expr4, expr5) => {
+                    let mut new_env = env2;
+                    new_env.put(*expr3, rule1.evaluated());
+                    new_env.put(*expr4, rule2.evaluated());
+                    let rule3 = solve(&new_env, &expr5, depth + 1)?;
+                    EAppRec(
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        Box::new(rule3),
+                        depth,
+                    )
+                }
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            }
+        }
+        LetRec(expr1, expr2, expr3, expr4) => {
+            let closure = RecClosure(env.clone(), expr1.clone(), expr2.clone(), expr3.clone());
+            let mut new_env = env.clone();
+            new_env.put(*expr1.clone(), closure);
+            let rule = solve(&new_env, expr4, depth + 1)?;
+            ELetRec(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                *expr4.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+        Closure(_, _, _) => return Err(EvalError::Stuck(expr.to_string())),
+        RecClosure(_, _, _, _) => return Err(EvalError::Stuck(expr.to_string())),
+    };
+    Ok(rule)
 }
 
 #[cfg(test)]
@@ -146,7 +206,7 @@ mod tests {
     #[test]
     fn test_solve1() {
         assert_eq!(
-            solve(&Env::new(), &Plus(Box::new(Int(3)), Box::new(Int(5))), 0),
+            solve(&Env::new(), &Plus(Box::new(Int(3)), Box::new(Int(5))), 0).unwrap(),
             EPlus(
                 Env::new(),
                 Int(3),
@@ -169,7 +229,8 @@ mod tests {
                     Box::new(Int(3))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EMinus(
                 Env::new(),
                 Minus(Box::new(Int(8)), Box::new(Int(2))),
@@ -200,7 +261,8 @@ mod tests {
                     Box::new(Minus(Box::new(Int(1)), Box::new(Int(10))))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             ETimes(
                 Env::new(),
                 Plus(Box::new(Int(4)), Box::new(Int(5))),
@@ -240,7 +302,8 @@ mod tests {
                     Box::new(Times(Box::new(Int(8)), Box::new(Int(8))))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EIfT(
                 Env::new(),
                 Lt(Box::new(Int(4)), Box::new(Int(5))),
@@ -286,7 +349,8 @@ mod tests {
                     ))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EPlus(
                 Env::new(),
                 Int(3),
@@ -353,7 +417,8 @@ mod tests {
                     Box::new(Int(4))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EPlus(
                 Env::new(),
                 Plus(
@@ -428,7 +493,8 @@ mod tests {
                 ]),
                 &Var("x".to_string()),
                 0
-            ),
+            )
+            .unwrap(),
             EVar2(
                 Env::from(vec![
                     (Var("x".to_string()), Int(3)),
@@ -459,7 +525,8 @@ mod tests {
                     Box::new(Var("y".to_string()))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             EIfT(
                 Env::from(vec![
                     (Var("x".to_string()), Bool(true)),
@@ -523,7 +590,8 @@ mod tests {
                     Box::new(Times(Box::new(Var("x".to_string())), Box::new(Int(4))))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             ELet(
                 Env::new(),
                 Var("x".to_string()),
@@ -574,7 +642,8 @@ mod tests {
                     ))
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             ELet(
                 Env::new(),
                 Var("x".to_string()),
@@ -669,7 +738,8 @@ mod tests {
                     )),
                 ),
                 0
-            ),
+            )
+            .unwrap(),
             ELet(
                 Env::from(vec![(Var("x".to_string()), Int(3))]),
                 Var("x".to_string()),
@@ -721,4 +791,136 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_solve12() {
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &App(
+                    Box::new(Fun(
+                        Box::new(Var("x".to_string())),
+                        Box::new(Var("x".to_string()))
+                    )),
+                    Box::new(Int(1))
+                ),
+                0
+            )
+            .unwrap(),
+            EApp(
+                Env::new(),
+                Fun(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Var("x".to_string()))
+                ),
+                Int(1),
+                Box::new(EFun(
+                    Env::new(),
+                    Var("x".to_string()),
+                    Var("x".to_string()),
+                    1
+                )),
+                Box::new(EInt(Env::new(), 1, 1)),
+                Box::new(EVar1(
+                    Env::from(vec![(Var("x".to_string()), Int(1))]),
+                    Var("x".to_string()),
+                    1
+                )),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve13() {
+        let closure = RecClosure(
+            Env::new(),
+            Box::new(Var("f".to_string())),
+            Box::new(Var("x".to_string())),
+            Box::new(Var("x".to_string())),
+        );
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &LetRec(
+                    Box::new(Var("f".to_string())),
+                    Box::new(Var("x".to_string())),
+                    Box::new(Var("x".to_string())),
+                    Box::new(App(Box::new(Var("f".to_string())), Box::new(Int(2))))
+                ),
+                0
+            )
+            .unwrap(),
+            ELetRec(
+                Env::new(),
+                Var("f".to_string()),
+                Var("x".to_string()),
+                Var("x".to_string()),
+                App(Box::new(Var("f".to_string())), Box::new(Int(2))),
+                Box::new(EAppRec(
+                    Env::from(vec![(Var("f".to_string()), closure.clone())]),
+                    Var("f".to_string()),
+                    Int(2),
+                    Box::new(EVar1(
+                        Env::from(vec![(Var("f".to_string()), closure.clone())]),
+                        Var("f".to_string()),
+                        2
+                    )),
+                    Box::new(EInt(
+                        Env::from(vec![(Var("f".to_string()), closure.clone())]),
+                        2,
+                        2
+                    )),
+                    Box::new(EVar1(
+                        Env::from(vec![
+                            (Var("f".to_string()), closure.clone()),
+                            (Var("x".to_string()), Int(2))
+                        ]),
+                        Var("x".to_string()),
+                        2
+                    )),
+                    1
+                )),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve14() {
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &App(
+                    Box::new(Fun(
+                        Box::new(Var("x".to_string())),
+                        Box::new(Var("x".to_string()))
+                    )),
+                    Box::new(Int(1))
+                ),
+                0
+            )
+            .unwrap()
+            .to_string(),
+            "|- (fun x -> x) 1 evalto 1 by E-App {
+    |- fun x -> x evalto ()[fun x -> x] by E-Fun {};
+    |- 1 evalto 1 by E-Int {};
+    x = 1 |- x evalto 1 by E-Var1 {}
+}"
+        );
+    }
+
+    #[test]
+    fn test_display_lt() {
+        assert_eq!(
+            solve(&Env::new(), &Lt(Box::new(Int(3)), Box::new(Int(4))), 0)
+                .unwrap()
+                .to_string(),
+            "|- 3 < 4 evalto true by E-Lt {
+    |- 3 evalto 3 by E-Int {};
+    |- 4 evalto 4 by E-Int {};
+    3 less than 4 is true by B-Lt {}
+}"
+        );
+    }
 }
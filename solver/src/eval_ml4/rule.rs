@@ -0,0 +1,390 @@
+use crate::{
+    eval_ml4::ast::{Env, Expr, Expr::*},
+    util::{env_prefix, ident},
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    EInt(Env, i64, usize),
+    EBool(Env, bool, usize),
+    EIfT(Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EIfF(Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EPlus(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    EMinus(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ETimes(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ELt(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    BPlus(Expr, Expr, Expr, usize),
+    BMinus(Expr, Expr, Expr, usize),
+    BTimes(Expr, Expr, Expr, usize),
+    BLt(Expr, Expr, Expr, usize),
+    EVar(Env, Expr, usize),
+    ELet(Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EFun(Env, Expr, Expr, usize),
+    EApp(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ELetRec(Env, Expr, Expr, Expr, Expr, Box<Rule>, usize),
+    EAppRec(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ENil(Env, usize),
+    ECons(Env, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EMatchNil(
+        Env,
+        Expr,
+        Expr,
+        Expr,
+        Expr,
+        Expr,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+    EMatchCons(
+        Env,
+        Expr,
+        Expr,
+        Expr,
+        Expr,
+        Expr,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+}
+
+impl Rule {
+    pub fn evaluated(&self) -> Expr {
+        use crate::eval_ml4::rule::Rule::*;
+
+        match self {
+            EInt(_, i, _) => Int(*i),
+            EBool(_, b, _) => Bool(*b),
+            EIfT(_, _, _, _, _, rule2, _) => rule2.evaluated(),
+            EIfF(_, _, _, _, _, rule2, _) => rule2.evaluated(),
+            EPlus(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            EMinus(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ETimes(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ELt(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            BPlus(_, _, expr3, _) => expr3.clone(),
+            BMinus(_, _, expr3, _) => expr3.clone(),
+            BTimes(_, _, expr3, _) => expr3.clone(),
+            BLt(_, _, expr3, _) => expr3.clone(),
+            EVar(env, expr, _) => env.get(expr),
+            ELet(_, _, _, _, _, rule2, _) => rule2.evaluated(),
+            EFun(env, expr1, expr2, _) => Closure(
+                env.clone(),
+                Box::new(expr1.clone()),
+                Box::new(expr2.clone()),
+            ),
+            EApp(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ELetRec(_, _, _, _, _, rule, _) => rule.evaluated(),
+            EAppRec(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ENil(_, _) => Nil,
+            ECons(_, _, _, rule1, rule2, _) => {
+                Cons(Box::new(rule1.evaluated()), Box::new(rule2.evaluated()))
+            }
+            EMatchNil(_, _, _, _, _, _, _, rule2, _) => rule2.evaluated(),
+            EMatchCons(_, _, _, _, _, _, _, rule2, _) => rule2.evaluated(),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_ml4::rule::Rule::*;
+
+        match self {
+            EInt(env, i, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Int {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    i,
+                    i
+                )
+            }
+            EBool(env, b, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Bool {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    b,
+                    b
+                )
+            }
+            EIfT(env, expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- if {} then {} else {} evalto {} by E-IfT {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EIfF(env, expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- if {} then {} else {} evalto {} by E-IfF {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EPlus(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Plus {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMinus(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Minus {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ETimes(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Times {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELt(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Lt {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            BPlus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} plus {} is {} by B-Plus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BMinus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} minus {} is {} by B-Minus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BTimes(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} times {} is {} by B-Times {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BLt(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} less than {} is {} by B-Lt {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            EVar(env, expr, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Var {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )
+            }
+            ELet(env, expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- let {} = {} in {} evalto {} by E-Let {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EFun(env, expr1, expr2, depth) => {
+                write!(
+                    f,
+                    "{}{}|- fun {} -> {} evalto {} by E-Fun {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    self.evaluated()
+                )
+            }
+            EApp(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = App(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-App {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELetRec(env, expr1, expr2, expr3, expr4, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- let rec {} = fun {} -> {} in {} evalto {} by E-LetRec {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    expr3,
+                    expr4,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EAppRec(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = App(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-AppRec {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ENil(env, depth) => {
+                write!(
+                    f,
+                    "{}{}|- [] evalto [] by E-Nil {{}}",
+                    ident(*depth),
+                    env_prefix(env)
+                )
+            }
+            ECons(env, expr1, expr2, rule1, rule2, depth) => {
+                let expr = Cons(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Cons {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMatchNil(env, expr1, expr2, expr3, expr4, expr5, rule1, rule2, depth) => {
+                let expr = Match(
+                    Box::new(expr1.clone()),
+                    Box::new(expr2.clone()),
+                    Box::new(expr3.clone()),
+                    Box::new(expr4.clone()),
+                    Box::new(expr5.clone()),
+                );
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-MatchNil {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMatchCons(env, expr1, expr2, expr3, expr4, expr5, rule1, rule2, depth) => {
+                let expr = Match(
+                    Box::new(expr1.clone()),
+                    Box::new(expr2.clone()),
+                    Box::new(expr3.clone()),
+                    Box::new(expr4.clone()),
+                    Box::new(expr5.clone()),
+                );
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-MatchCons {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+        }
+    }
+}
@@ -0,0 +1,396 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Expr {
+    Int(i64),
+    Bool(bool),
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+    Plus(Box<Expr>, Box<Expr>),
+    Minus(Box<Expr>, Box<Expr>),
+    Times(Box<Expr>, Box<Expr>),
+    Lt(Box<Expr>, Box<Expr>),
+    Let(Box<Expr>, Box<Expr>, Box<Expr>),
+    Var(String),
+    Fun(Box<Expr>, Box<Expr>),
+    App(Box<Expr>, Box<Expr>),
+    LetRec(Box<Expr>, Box<Expr>, Box<Expr>, Box<Expr>),
+    Closure(Env, Box<Expr>, Box<Expr>),
+    RecClosure(Env, Box<Expr>, Box<Expr>, Box<Expr>),
+    Nil,
+    Cons(Box<Expr>, Box<Expr>),
+    Match(Box<Expr>, Vec<(Pattern, Expr)>),
+}
+
+impl Expr {
+    // 結合の強さ (大きいほど強い)
+    fn precedence(&self) -> usize {
+        use crate::eval_ml5::ast::Expr::*;
+
+        match self {
+            If(_, _, _) | Let(_, _, _) | Fun(_, _) | LetRec(_, _, _, _) | Match(_, _) => 0,
+            Lt(_, _) => 1,
+            Cons(_, _) => 2,
+            Plus(_, _) | Minus(_, _) => 3,
+            Times(_, _) => 4,
+            App(_, _) => 5,
+            Int(i) if *i < 0 => 5,
+            _ => 6,
+        }
+    }
+
+    fn ends_with_match(&self) -> bool {
+        use crate::eval_ml5::ast::Expr::*;
+
+        match self {
+            Match(_, _) => true,
+            If(_, _, expr) | Let(_, _, expr) | Fun(_, expr) | LetRec(_, _, _, expr) => {
+                expr.ends_with_match()
+            }
+            _ => false,
+        }
+    }
+
+    fn paren(&self, paren: bool) -> String {
+        if paren {
+            format!("({})", self)
+        } else {
+            self.to_string()
+        }
+    }
+
+    // 右にできるだけ伸びる式 (右端にあれば括弧がいらない)
+    fn is_open(&self) -> bool {
+        use crate::eval_ml5::ast::Expr::*;
+
+        matches!(
+            self,
+            If(_, _, _) | Let(_, _, _) | Fun(_, _) | LetRec(_, _, _, _) | Match(_, _)
+        )
+    }
+
+    // tail は後ろに何も続かないこと
+    fn show(&self, tail: bool) -> String {
+        use crate::eval_ml5::ast::Expr::*;
+
+        let p = self.precedence();
+        let left = |expr: &Expr, paren: bool| {
+            if paren {
+                expr.paren(true)
+            } else {
+                expr.show(false)
+            }
+        };
+        // 右端なら if や let も括弧なしで書ける
+        let right = |expr: &Expr, paren: bool| {
+            if paren && !(tail && expr.is_open()) {
+                expr.paren(true)
+            } else {
+                expr.show(tail)
+            }
+        };
+        match self {
+            Int(i) => i.to_string(),
+            Bool(b) => b.to_string(),
+            If(expr1, expr2, expr3) => {
+                format!("if {} then {} else {}", expr1, expr2, expr3.show(tail))
+            }
+            Plus(expr1, expr2) => format!(
+                "{} + {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Minus(expr1, expr2) => format!(
+                "{} - {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Times(expr1, expr2) => format!(
+                "{} * {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Lt(expr1, expr2) => format!(
+                "{} < {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Let(expr1, expr2, expr3) => {
+                format!("let {} = {} in {}", expr1, expr2, expr3.show(tail))
+            }
+            Var(s) => s.to_string(),
+            Fun(expr1, expr2) => format!("fun {} -> {}", expr1, expr2.show(tail)),
+            App(expr1, expr2) => format!(
+                "{} {}",
+                left(expr1, expr1.precedence() < p),
+                expr2.paren(expr2.precedence() <= p)
+            ),
+            LetRec(expr1, expr2, expr3, expr4) => {
+                format!(
+                    "let rec {} = fun {} -> {} in {}",
+                    expr1,
+                    expr2,
+                    expr3,
+                    expr4.show(tail)
+                )
+            }
+            Closure(env, expr1, expr2) => format!("({})[fun {} -> {}]", env, expr1, expr2),
+            RecClosure(env, expr1, expr2, expr3) => {
+                format!("({})[rec {} = fun {} -> {}]", env, expr1, expr2, expr3)
+            }
+            Nil => "[]".to_string(),
+            Cons(expr1, expr2) => format!(
+                "{} :: {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() < p)
+            ),
+            Match(expr, clauses) => {
+                // 最後以外の節の本体が match で終わると後続の節を取り込んでしまう
+                let clauses = clauses
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (pattern, expr))| {
+                        let expr = if i + 1 < clauses.len() {
+                            left(expr, expr.ends_with_match())
+                        } else {
+                            expr.show(tail)
+                        };
+                        format!("{} -> {}", pattern, expr)
+                    })
+                    .collect::<Vec<_>>();
+                format!("match {} with {}", expr, clauses.join(" | "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.show(true))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Pattern {
+    Var(String),
+    Nil,
+    Cons(Box<Pattern>, Box<Pattern>),
+    Wild,
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_ml5::ast::Pattern::*;
+
+        match self {
+            Var(s) => write!(f, "{}", s),
+            Nil => write!(f, "[]"),
+            Cons(pattern1, pattern2) => match **pattern1 {
+                Cons(_, _) => write!(f, "({}) :: {}", pattern1, pattern2),
+                _ => write!(f, "{} :: {}", pattern1, pattern2),
+            },
+            Wild => write!(f, "_"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Env(Vec<(Expr, Expr)>);
+
+impl Env {
+    pub fn new() -> Env {
+        Env(vec![])
+    }
+
+    pub fn from(pairs: Vec<(Expr, Expr)>) -> Env {
+        Env(pairs)
+    }
+
+    pub fn put(&mut self, expr1: Expr, expr2: Expr) {
+        self.0.push((expr1, expr2))
+    }
+
+    pub fn append(&mut self, env: Env) {
+        self.0.extend(env.0)
+    }
+
+    pub fn get(&self, expr: &Expr) -> Expr {
+        self.0
+            .iter()
+            .rev()
+            .find(|(expr1, _)| expr1 == expr)
+            .unwrap()
+            .1
+            .clone()
+    }
+
+    pub fn contains(&self, expr: &Expr) -> bool {
+        self.0.iter().any(|(expr1, _)| expr1 == expr)
+    }
+
+    pub fn last(&self) -> (Expr, Expr) {
+        self.0.last().cloned().unwrap()
+    }
+
+    pub fn butlast(&self) -> Env {
+        let pairs = self.0[..self.0.len() - 1].to_vec();
+        Env(pairs)
+    }
+}
+
+impl fmt::Display for Env {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs = self
+            .0
+            .iter()
+            .map(|(expr1, expr2)| format!("{} = {}", expr1, expr2))
+            .collect::<Vec<_>>();
+        write!(f, "{}", pairs.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_ml5::ast::{Env, Expr::*, Pattern};
+
+    #[test]
+    fn test_env() {
+        let mut env = Env::new();
+        env.put(Var("x".to_string()), Int(1));
+        env.put(Var("y".to_string()), Int(2));
+        env.put(Var("x".to_string()), Int(3));
+        assert_eq!("x = 1, y = 2, x = 3", env.to_string());
+        assert_eq!(Int(3), env.get(&Var("x".to_string())));
+        assert_eq!(Int(2), env.get(&Var("y".to_string())));
+        assert_eq!(
+            "x = 1, y = 2",
+            Env::from(vec![
+                (Var("x".to_string()), Int(1)),
+                (Var("y".to_string()), Int(2)),
+            ])
+            .to_string()
+        );
+        assert_eq!((Var("x".to_string()), Int(3)), env.last());
+        assert_eq!(
+            Env::from(vec![
+                (Var("x".to_string()), Int(1)),
+                (Var("y".to_string()), Int(2))
+            ]),
+            env.butlast()
+        )
+    }
+
+    #[test]
+    fn test_closure() {
+        let env = Env::from(vec![
+            (Var("x".to_string()), Int(1)),
+            (
+                Var("f".to_string()),
+                Closure(
+                    Env::from(vec![(Var("y".to_string()), Int(2))]),
+                    Box::new(Var("z".to_string())),
+                    Box::new(Plus(
+                        Box::new(Var("y".to_string())),
+                        Box::new(Var("z".to_string())),
+                    )),
+                ),
+            ),
+        ]);
+        assert_eq!("x = 1, f = (y = 2)[fun z -> y + z]", env.to_string());
+        assert_eq!(
+            "()[rec f = fun x -> f x]",
+            RecClosure(
+                Env::new(),
+                Box::new(Var("f".to_string())),
+                Box::new(Var("x".to_string())),
+                Box::new(App(
+                    Box::new(Var("f".to_string())),
+                    Box::new(Var("x".to_string())),
+                )),
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_display1() {
+        let list = Cons(
+            Box::new(Int(1)),
+            Box::new(Cons(Box::new(Int(2)), Box::new(Nil))),
+        );
+        assert_eq!("1 :: 2 :: []", list.to_string());
+        assert_eq!(
+            "(1 :: 2 :: []) :: []",
+            Cons(Box::new(list.clone()), Box::new(Nil)).to_string()
+        );
+        assert_eq!(
+            "(1 + 2) * 3 :: f (-1)",
+            Cons(
+                Box::new(Times(
+                    Box::new(Plus(Box::new(Int(1)), Box::new(Int(2)))),
+                    Box::new(Int(3))
+                )),
+                Box::new(App(Box::new(Var("f".to_string())), Box::new(Int(-1))))
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_display2() {
+        assert_eq!(
+            "1 - (2 - 3) < 4",
+            Lt(
+                Box::new(Minus(
+                    Box::new(Int(1)),
+                    Box::new(Minus(Box::new(Int(2)), Box::new(Int(3))))
+                )),
+                Box::new(Int(4))
+            )
+            .to_string()
+        );
+        assert_eq!(
+            "match x with [] -> (match y with _ -> 0) | a :: _ -> fun c -> match c with _ -> a",
+            Match(
+                Box::new(Var("x".to_string())),
+                vec![
+                    (
+                        Pattern::Nil,
+                        Match(
+                            Box::new(Var("y".to_string())),
+                            vec![(Pattern::Wild, Int(0))]
+                        )
+                    ),
+                    (
+                        Pattern::Cons(
+                            Box::new(Pattern::Var("a".to_string())),
+                            Box::new(Pattern::Wild)
+                        ),
+                        Fun(
+                            Box::new(Var("c".to_string())),
+                            Box::new(Match(
+                                Box::new(Var("c".to_string())),
+                                vec![(Pattern::Wild, Var("a".to_string()))]
+                            ))
+                        )
+                    )
+                ]
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_pattern() {
+        let pattern = Pattern::Cons(
+            Box::new(Pattern::Cons(
+                Box::new(Pattern::Var("x".to_string())),
+                Box::new(Pattern::Nil),
+            )),
+            Box::new(Pattern::Cons(
+                Box::new(Pattern::Wild),
+                Box::new(Pattern::Var("y".to_string())),
+            )),
+        );
+        assert_eq!("(x :: []) :: _ :: y", pattern.to_string());
+    }
+}
@@ -0,0 +1,800 @@
+use crate::{
+    eval_ml5::ast::{Env, Expr, Expr::*, Pattern},
+    syntax::lexer::{keyword, name},
+    util::ws,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, digit1},
+    combinator::{map_res, opt, recognize, verify},
+    multi::{many0, separated_list1},
+    sequence::{delimited, tuple},
+    IResult,
+};
+
+// <expr> ::= <cons> [ '<' <cons> ]
+// <cons> ::= <term1> [ '::' <cons> ]
+// <term1> ::= <term2> [ ( '+' | '-' ) <term2> ]*
+// <term2> ::= <app> [ '*' <app> ]*
+// <app> ::= <factor> [ <arg> ]*
+// <factor> ::= <value> | <nil> | <paren> | <if> | <let_rec> | <let> | <fun> | <match> | <var>
+// <arg> ::= <pos_int> | <bool> | <nil> | <paren> | <var>
+// <value> ::= <int> | <bool>
+// <int> ::= 数値
+// <bool> ::= 'true' | 'false'
+// <paren> ::= '(' <expr> ')'
+// <if> ::= 'if' <expr> 'then' <expr> 'else' <expr>
+// <let_rec> ::= 'let' 'rec' <var> '=' 'fun' <var> '->' <expr> 'in' <expr>
+// <let> ::= 'let' <var> '=' <expr> 'in' <expr>
+// <fun> ::= 'fun' <var> '->' <expr>
+// <match> ::= 'match' <expr> 'with' <clause> [ '|' <clause> ]*
+// <clause> ::= <pattern> '->' <expr>
+// <pattern> ::= <pattern_atom> [ '::' <pattern> ]
+// <pattern_atom> ::= <var> | '[' ']' | '_' | '(' <pattern> ')'
+// <nil> ::= '[' ']'
+// <var> ::= 英字か '_' で始まる英数字と '_', '\'' の列 (予約語を除く)
+// <closure> ::= '(' <env> ')' '[' 'fun' <var> '->' <expr> ']'
+// <rec_closure> ::= '(' <env> ')' '[' 'rec' <var> '=' 'fun' <var> '->' <expr> ']'
+// <result> ::= <result_cons> | <expr>
+// <result_cons> ::= ( <closure> | <rec_closure> | <term1> ) [ '::' <result_cons> ]
+
+// '_' はワイルドカードのパターン
+const KEYWORDS: [&str; 12] = [
+    "if", "then", "else", "let", "rec", "in", "fun", "match", "with", "true", "false", "_",
+];
+
+pub fn parse(input: &str) -> IResult<&str, (Env, Expr)> {
+    match input.find("|-") {
+        Some(i) => {
+            let (input1, input2) = input.split_at(i);
+            let (_, env) = parse_env(input1)?;
+            let (input, expr) = parse_expr(&input2[2..])?;
+            Ok((input, (env, expr)))
+        }
+        None => {
+            let (input, expr) = parse_expr(input)?;
+            Ok((input, (Env::new(), expr)))
+        }
+    }
+}
+
+// 評価結果 (クロージャや, クロージャを要素に持つリストを含む)
+pub fn parse_result(input: &str) -> IResult<&str, Expr> {
+    alt((parse_result_cons, parse_expr))(input)
+}
+
+fn parse_result_cons(input: &str) -> IResult<&str, Expr> {
+    let parse_atom = alt((parse_closure, parse_rec_closure, parse_term1));
+    let (input, (expr1, expr2)) =
+        tuple((parse_atom, opt(tuple((ws(tag("::")), parse_result_cons)))))(input)?;
+    let expr = match expr2 {
+        Some((_, expr2)) => Cons(Box::new(expr1), Box::new(expr2)),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+fn parse_env(input: &str) -> IResult<&str, Env> {
+    let mut env = Env::new();
+    let (input, pair) = opt(parse_pair)(input)?;
+    match pair {
+        Some((expr1, expr2)) => env.put(expr1, expr2),
+        None => return Ok((input, env)),
+    };
+    let (input, pairs) = opt(parse_pairs)(input)?;
+    if let Some(pairs) = pairs {
+        pairs
+            .into_iter()
+            .for_each(|(expr1, expr2)| env.put(expr1, expr2));
+    }
+    Ok((input, env))
+}
+
+fn parse_pair(input: &str) -> IResult<&str, (Expr, Expr)> {
+    let (input, (expr1, _, expr2)) = tuple((parse_var, ws(char('=')), parse_result))(input)?;
+    let pair = (expr1, expr2);
+    Ok((input, pair))
+}
+
+fn parse_pairs(input: &str) -> IResult<&str, Vec<(Expr, Expr)>> {
+    let (input, pairs) = many0(tuple((ws(char(',')), parse_pair)))(input)?;
+    let pairs = pairs.into_iter().map(|(_, pair)| pair).collect();
+    Ok((input, pairs))
+}
+
+pub fn parse_expr(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr1, expr2)) = tuple((parse_cons, opt(parse_lt)))(input)?;
+    let expr = match expr2 {
+        Some(expr2) => Lt(Box::new(expr1), Box::new(expr2)),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+fn parse_lt(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr)) = tuple((ws(char('<')), parse_cons))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_cons(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr1, expr2)) =
+        tuple((parse_term1, opt(tuple((ws(tag("::")), parse_cons)))))(input)?;
+    let expr = match expr2 {
+        Some((_, expr2)) => Cons(Box::new(expr1), Box::new(expr2)),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+fn parse_term1(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_term2, parse_plus_minus))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
+        '+' => Plus(Box::new(expr1), Box::new(expr2.clone())),
+        '-' => Minus(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+fn parse_plus_minus(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
+    let parse_plus = ws(char('+'));
+    let parse_minus = ws(char('-'));
+    let parse_op = alt((parse_plus, parse_minus));
+    let (input, exprs) = many0(tuple((parse_op, parse_term2)))(input)?;
+    Ok((input, exprs))
+}
+
+fn parse_term2(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_app, parse_times))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
+        '*' => Times(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+fn parse_times(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
+    let parse_times = ws(char('*'));
+    let (input, exprs) = many0(tuple((parse_times, parse_app)))(input)?;
+    Ok((input, exprs))
+}
+
+fn parse_app(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_factor, many0(parse_arg)))(input)?;
+    let expr = exprs
+        .into_iter()
+        .fold(expr, |expr1, expr2| App(Box::new(expr1), Box::new(expr2)));
+    Ok((input, expr))
+}
+
+fn parse_factor(input: &str) -> IResult<&str, Expr> {
+    let (input, expr) = alt((
+        parse_value,
+        parse_nil,
+        parse_paren,
+        parse_if,
+        parse_let_rec,
+        parse_let,
+        parse_fun,
+        parse_match,
+        parse_var,
+    ))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_arg(input: &str) -> IResult<&str, Expr> {
+    let parse_pos_int = |input| {
+        let (input, i) = ws(parse_pos_number)(input)?;
+        Ok((input, Int(i)))
+    };
+    let (input, expr) = alt((parse_pos_int, parse_bool, parse_nil, parse_paren, parse_var))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_value(input: &str) -> IResult<&str, Expr> {
+    let (input, expr) = alt((parse_int, parse_bool))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_int(input: &str) -> IResult<&str, Expr> {
+    let (input, i) = alt((ws(parse_pos_number), ws(parse_neg_number)))(input)?;
+    let expr = Int(i);
+    Ok((input, expr))
+}
+
+fn parse_pos_number(input: &str) -> IResult<&str, i64> {
+    map_res(digit1, |i: &str| i.parse::<i64>())(input)
+}
+
+fn parse_neg_number(input: &str) -> IResult<&str, i64> {
+    map_res(recognize(tuple((char('-'), digit1))), |i: &str| {
+        i.parse::<i64>()
+    })(input)
+}
+
+fn parse_bool(input: &str) -> IResult<&str, Expr> {
+    let (input, b) = alt((parse_true, parse_false))(input)?;
+    let expr = Bool(b);
+    Ok((input, expr))
+}
+
+fn parse_true(input: &str) -> IResult<&str, bool> {
+    let (input, _) = ws(keyword("true"))(input)?;
+    Ok((input, true))
+}
+
+fn parse_false(input: &str) -> IResult<&str, bool> {
+    let (input, _) = ws(keyword("false"))(input)?;
+    Ok((input, false))
+}
+
+fn parse_nil(input: &str) -> IResult<&str, Expr> {
+    let (input, _) = tuple((ws(char('[')), ws(char(']'))))(input)?;
+    Ok((input, Nil))
+}
+
+fn parse_paren(input: &str) -> IResult<&str, Expr> {
+    let parse_lparen = ws(char('('));
+    let parse_rparen = ws(char(')'));
+    let (input, expr) = delimited(parse_lparen, parse_expr, parse_rparen)(input)?;
+    Ok((input, expr))
+}
+
+fn parse_if(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr1, _, expr2, _, expr3)) = tuple((
+        ws(keyword("if")),
+        parse_expr,
+        ws(keyword("then")),
+        parse_expr,
+        ws(keyword("else")),
+        parse_expr,
+    ))(input)?;
+    let expr = If(Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+fn parse_let(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr1, _, expr2, _, expr3)) = tuple((
+        ws(keyword("let")),
+        parse_var,
+        ws(char('=')),
+        parse_expr,
+        ws(keyword("in")),
+        parse_expr,
+    ))(input)?;
+    let expr = Let(Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+fn parse_let_rec(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, _, expr1, _, _, expr2, _, expr3, _, expr4)) = tuple((
+        ws(keyword("let")),
+        ws(keyword("rec")),
+        parse_var,
+        ws(char('=')),
+        ws(keyword("fun")),
+        parse_var,
+        ws(tag("->")),
+        parse_expr,
+        ws(keyword("in")),
+        parse_expr,
+    ))(input)?;
+    let expr = LetRec(
+        Box::new(expr1),
+        Box::new(expr2),
+        Box::new(expr3),
+        Box::new(expr4),
+    );
+    Ok((input, expr))
+}
+
+fn parse_fun(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr1, _, expr2)) =
+        tuple((ws(keyword("fun")), parse_var, ws(tag("->")), parse_expr))(input)?;
+    let expr = Fun(Box::new(expr1), Box::new(expr2));
+    Ok((input, expr))
+}
+
+fn parse_match(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr, _, clauses)) = tuple((
+        ws(keyword("match")),
+        parse_expr,
+        ws(keyword("with")),
+        separated_list1(ws(char('|')), parse_clause),
+    ))(input)?;
+    let expr = Match(Box::new(expr), clauses);
+    Ok((input, expr))
+}
+
+fn parse_clause(input: &str) -> IResult<&str, (Pattern, Expr)> {
+    let (input, (pattern, _, expr)) = tuple((parse_pattern, ws(tag("->")), parse_expr))(input)?;
+    Ok((input, (pattern, expr)))
+}
+
+fn parse_pattern(input: &str) -> IResult<&str, Pattern> {
+    let (input, (pattern1, pattern2)) = tuple((
+        parse_pattern_atom,
+        opt(tuple((ws(tag("::")), parse_pattern))),
+    ))(input)?;
+    let pattern = match pattern2 {
+        Some((_, pattern2)) => Pattern::Cons(Box::new(pattern1), Box::new(pattern2)),
+        None => pattern1,
+    };
+    Ok((input, pattern))
+}
+
+fn parse_pattern_atom(input: &str) -> IResult<&str, Pattern> {
+    let parse_pattern_var = |input| {
+        let (input, s) = ws(parse_ident)(input)?;
+        Ok((input, Pattern::Var(s.to_string())))
+    };
+    let parse_pattern_nil = |input| {
+        let (input, _) = parse_nil(input)?;
+        Ok((input, Pattern::Nil))
+    };
+    let parse_pattern_wild = |input| {
+        let (input, _) = ws(char('_'))(input)?;
+        Ok((input, Pattern::Wild))
+    };
+    let parse_pattern_paren = delimited(ws(char('(')), parse_pattern, ws(char(')')));
+    let (input, pattern) = alt((
+        parse_pattern_var,
+        parse_pattern_nil,
+        parse_pattern_wild,
+        parse_pattern_paren,
+    ))(input)?;
+    Ok((input, pattern))
+}
+
+fn parse_closure(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, env, _, _, _, expr1, _, expr2, _)) = tuple((
+        ws(char('(')),
+        parse_env,
+        ws(char(')')),
+        ws(char('[')),
+        ws(keyword("fun")),
+        parse_var,
+        ws(tag("->")),
+        parse_expr,
+        ws(char(']')),
+    ))(input)?;
+    let expr = Closure(env, Box::new(expr1), Box::new(expr2));
+    Ok((input, expr))
+}
+
+fn parse_rec_closure(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, env, _, _, _, expr1, _, _, expr2, _, expr3, _)) = tuple((
+        ws(char('(')),
+        parse_env,
+        ws(char(')')),
+        ws(char('[')),
+        ws(keyword("rec")),
+        parse_var,
+        ws(char('=')),
+        ws(keyword("fun")),
+        parse_var,
+        ws(tag("->")),
+        parse_expr,
+        ws(char(']')),
+    ))(input)?;
+    let expr = RecClosure(env, Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+pub fn parse_var(input: &str) -> IResult<&str, Expr> {
+    let (input, s) = ws(parse_ident)(input)?;
+    let expr = Var(s.to_string());
+    Ok((input, expr))
+}
+
+fn parse_ident(input: &str) -> IResult<&str, &str> {
+    verify(name, |s: &str| !KEYWORDS.contains(&s))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_ml5::{
+        ast::{Env, Expr::*, Pattern},
+        parser::{parse, parse_expr, parse_result, parse_var},
+        solver::solve,
+    };
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(
+            parse("3 + 5").unwrap().1,
+            (Env::new(), Plus(Box::new(Int(3)), Box::new(Int(5))))
+        );
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse("8 - 2 - 3").unwrap().1,
+            (
+                Env::new(),
+                Minus(
+                    Box::new(Minus(Box::new(Int(8)), Box::new(Int(2)))),
+                    Box::new(Int(3))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse3() {
+        assert_eq!(
+            parse("(4 + 5) * (1 - 10)").unwrap().1,
+            (
+                Env::new(),
+                Times(
+                    Box::new(Plus(Box::new(Int(4)), Box::new(Int(5)))),
+                    Box::new(Minus(Box::new(Int(1)), Box::new(Int(10))))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse4() {
+        assert_eq!(
+            parse("if 4 < 5 then 2 + 3 else 8 * 8").unwrap().1,
+            (
+                Env::new(),
+                If(
+                    Box::new(Lt(Box::new(Int(4)), Box::new(Int(5)))),
+                    Box::new(Plus(Box::new(Int(2)), Box::new(Int(3)))),
+                    Box::new(Times(Box::new(Int(8)), Box::new(Int(8))))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse5() {
+        assert_eq!(
+            parse("3 + if -23 < -2 * 8 then 8 else 2 + 4").unwrap().1,
+            (
+                Env::new(),
+                Plus(
+                    Box::new(Int(3)),
+                    Box::new(If(
+                        Box::new(Lt(
+                            Box::new(Int(-23)),
+                            Box::new(Times(Box::new(Int(-2)), Box::new(Int(8))))
+                        )),
+                        Box::new(Int(8)),
+                        Box::new(Plus(Box::new(Int(2)), Box::new(Int(4))))
+                    ))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse6() {
+        assert_eq!(
+            parse("3 + (if -23 < -2 * 8 then 8 else 2) + 4").unwrap().1,
+            (
+                Env::new(),
+                Plus(
+                    Box::new(Plus(
+                        Box::new(Int(3)),
+                        Box::new(If(
+                            Box::new(Lt(
+                                Box::new(Int(-23)),
+                                Box::new(Times(Box::new(Int(-2)), Box::new(Int(8))))
+                            )),
+                            Box::new(Int(8)),
+                            Box::new(Int(2))
+                        ))
+                    )),
+                    Box::new(Int(4))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse7() {
+        assert_eq!(
+            parse("x = 3, y = 2 |- x").unwrap().1,
+            (
+                Env::from(vec![
+                    (Var("x".to_string()), Int(3)),
+                    (Var("y".to_string()), Int(2))
+                ]),
+                Var("x".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse8() {
+        assert_eq!(
+            parse("x = true, y = 4 |- if x then y + 1 else y")
+                .unwrap()
+                .1,
+            (
+                Env::from(vec![
+                    (Var("x".to_string()), Bool(true)),
+                    (Var("y".to_string()), Int(4))
+                ]),
+                If(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Plus(Box::new(Var("y".to_string())), Box::new(Int(1)))),
+                    Box::new(Var("y".to_string()))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse9() {
+        assert_eq!(
+            parse("|- let x = 1 + 2 in x * 4").unwrap().1,
+            (
+                Env::new(),
+                Let(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Plus(Box::new(Int(1)), Box::new(Int(2)))),
+                    Box::new(Times(Box::new(Var("x".to_string())), Box::new(Int(4))))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse10() {
+        assert_eq!(
+            parse("|- let x = 3 * 3 in let y = 4 * x in x + y")
+                .unwrap()
+                .1,
+            (
+                Env::new(),
+                Let(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Times(Box::new(Int(3)), Box::new(Int(3)))),
+                    Box::new(Let(
+                        Box::new(Var("y".to_string())),
+                        Box::new(Times(Box::new(Int(4)), Box::new(Var("x".to_string())))),
+                        Box::new(Plus(
+                            Box::new(Var("x".to_string())),
+                            Box::new(Var("y".to_string()))
+                        ))
+                    ))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse11() {
+        assert_eq!(
+            parse("x = 3 |- let x = x * 2 in x + x").unwrap().1,
+            (
+                Env::from(vec![(Var("x".to_string()), Int(3))]),
+                Let(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Times(Box::new(Var("x".to_string())), Box::new(Int(2)))),
+                    Box::new(Plus(
+                        Box::new(Var("x".to_string())),
+                        Box::new(Var("x".to_string()))
+                    )),
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse12() {
+        assert_eq!(
+            parse("|- f x + g 1 2").unwrap().1,
+            (
+                Env::new(),
+                Plus(
+                    Box::new(App(
+                        Box::new(Var("f".to_string())),
+                        Box::new(Var("x".to_string()))
+                    )),
+                    Box::new(App(
+                        Box::new(App(Box::new(Var("g".to_string())), Box::new(Int(1)))),
+                        Box::new(Int(2))
+                    ))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse13() {
+        assert_eq!(
+            parse("|- let f = fun x -> x - 1 in if f 3 < 1 then 0 else f (f 2)")
+                .unwrap()
+                .1,
+            (
+                Env::new(),
+                Let(
+                    Box::new(Var("f".to_string())),
+                    Box::new(Fun(
+                        Box::new(Var("x".to_string())),
+                        Box::new(Minus(Box::new(Var("x".to_string())), Box::new(Int(1))))
+                    )),
+                    Box::new(If(
+                        Box::new(Lt(
+                            Box::new(App(Box::new(Var("f".to_string())), Box::new(Int(3)))),
+                            Box::new(Int(1))
+                        )),
+                        Box::new(Int(0)),
+                        Box::new(App(
+                            Box::new(Var("f".to_string())),
+                            Box::new(App(Box::new(Var("f".to_string())), Box::new(Int(2))))
+                        ))
+                    ))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse14() {
+        assert_eq!(
+            parse("|- let rec f = fun x -> f x in f 1").unwrap().1,
+            (
+                Env::new(),
+                LetRec(
+                    Box::new(Var("f".to_string())),
+                    Box::new(Var("x".to_string())),
+                    Box::new(App(
+                        Box::new(Var("f".to_string())),
+                        Box::new(Var("x".to_string()))
+                    )),
+                    Box::new(App(Box::new(Var("f".to_string())), Box::new(Int(1))))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse15() {
+        assert_eq!(
+            parse("y = 2, f = (y = 2)[fun x -> x + y], g = ()[rec g = fun x -> g x] |- f 1")
+                .unwrap()
+                .1,
+            (
+                Env::from(vec![
+                    (Var("y".to_string()), Int(2)),
+                    (
+                        Var("f".to_string()),
+                        Closure(
+                            Env::from(vec![(Var("y".to_string()), Int(2))]),
+                            Box::new(Var("x".to_string())),
+                            Box::new(Plus(
+                                Box::new(Var("x".to_string())),
+                                Box::new(Var("y".to_string()))
+                            ))
+                        )
+                    ),
+                    (
+                        Var("g".to_string()),
+                        RecClosure(
+                            Env::new(),
+                            Box::new(Var("g".to_string())),
+                            Box::new(Var("x".to_string())),
+                            Box::new(App(
+                                Box::new(Var("g".to_string())),
+                                Box::new(Var("x".to_string()))
+                            ))
+                        )
+                    )
+                ]),
+                App(Box::new(Var("f".to_string())), Box::new(Int(1)))
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse16() {
+        assert_eq!(
+            parse("|- fun x -> fun y -> x y 1").unwrap().1,
+            (
+                Env::new(),
+                Fun(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Fun(
+                        Box::new(Var("y".to_string())),
+                        Box::new(App(
+                            Box::new(App(
+                                Box::new(Var("x".to_string())),
+                                Box::new(Var("y".to_string()))
+                            )),
+                            Box::new(Int(1))
+                        ))
+                    ))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse17() {
+        assert_eq!(
+            parse("1 + 2 :: 3 :: [] < []").unwrap().1,
+            (
+                Env::new(),
+                Lt(
+                    Box::new(Cons(
+                        Box::new(Plus(Box::new(Int(1)), Box::new(Int(2)))),
+                        Box::new(Cons(Box::new(Int(3)), Box::new(Nil)))
+                    )),
+                    Box::new(Nil)
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse18() {
+        assert_eq!(
+            parse("x = 1 :: [] |- match x with [] -> 0 | (a :: _) :: b -> a | _ -> 1")
+                .unwrap()
+                .1,
+            (
+                Env::from(vec![(
+                    Var("x".to_string()),
+                    Cons(Box::new(Int(1)), Box::new(Nil))
+                )]),
+                Match(
+                    Box::new(Var("x".to_string())),
+                    vec![
+                        (Pattern::Nil, Int(0)),
+                        (
+                            Pattern::Cons(
+                                Box::new(Pattern::Cons(
+                                    Box::new(Pattern::Var("a".to_string())),
+                                    Box::new(Pattern::Wild)
+                                )),
+                                Box::new(Pattern::Var("b".to_string()))
+                            ),
+                            Var("a".to_string())
+                        ),
+                        (Pattern::Wild, Int(1))
+                    ]
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_ident() {
+        assert_eq!(
+            parse_expr("f truex").unwrap().1,
+            App(
+                Box::new(Var("f".to_string())),
+                Box::new(Var("truex".to_string()))
+            )
+        );
+        assert_eq!(parse_var("_x1").unwrap().1, Var("_x1".to_string()));
+        assert!(parse_var("123").is_err());
+        assert!(parse_var("_").is_err());
+    }
+
+    #[test]
+    fn test_parse_result() {
+        let (env, expr) = parse("|- (fun x -> x) :: (fun y -> y) :: []").unwrap().1;
+        let value = solve(&env, &expr, 0).unwrap().evaluated();
+        assert_eq!(value.to_string(), "()[fun x -> x] :: ()[fun y -> y] :: []");
+        assert_eq!(
+            parse_result(&value.to_string()).unwrap(),
+            ("", value.clone())
+        );
+        assert_eq!(
+            parse(&format!("l = {} |- l", value)).unwrap().1,
+            (
+                Env::from(vec![(Var("l".to_string()), value)]),
+                Var("l".to_string())
+            )
+        );
+    }
+}
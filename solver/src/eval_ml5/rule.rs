@@ -0,0 +1,506 @@
+use crate::{
+    eval_ml5::ast::{Env, Expr, Expr::*, Pattern},
+    util::{env_prefix, ident},
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    EInt(Env, i64, usize),
+    EBool(Env, bool, usize),
+    EIfT(Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EIfF(Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EPlus(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    EMinus(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ETimes(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ELt(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    BPlus(Expr, Expr, Expr, usize),
+    BMinus(Expr, Expr, Expr, usize),
+    BTimes(Expr, Expr, Expr, usize),
+    BLt(Expr, Expr, Expr, usize),
+    EVar(Env, Expr, usize),
+    ELet(Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EFun(Env, Expr, Expr, usize),
+    EApp(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ELetRec(Env, Expr, Expr, Expr, Expr, Box<Rule>, usize),
+    EAppRec(Env, Expr, Expr, Box<Rule>, Box<Rule>, Box<Rule>, usize),
+    ENil(Env, usize),
+    ECons(Env, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EMatchM1(
+        Env,
+        Expr,
+        Vec<(Pattern, Expr)>,
+        Box<Rule>,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+    EMatchM2(
+        Env,
+        Expr,
+        Vec<(Pattern, Expr)>,
+        Box<Rule>,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+    EMatchN(
+        Env,
+        Expr,
+        Vec<(Pattern, Expr)>,
+        Box<Rule>,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+    MVar(String, Expr, usize),
+    MNil(usize),
+    MCons(Pattern, Pattern, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    MWild(Expr, usize),
+    NMConsNil(Pattern, Pattern, usize),
+    NMNilCons(Expr, Expr, usize),
+    NMConsConsL(Pattern, Pattern, Expr, Expr, Box<Rule>, usize),
+    NMConsConsR(Pattern, Pattern, Expr, Expr, Box<Rule>, usize),
+}
+
+impl Rule {
+    pub fn evaluated(&self) -> Expr {
+        use crate::eval_ml5::rule::Rule::*;
+
+        match self {
+            EInt(_, i, _) => Int(*i),
+            EBool(_, b, _) => Bool(*b),
+            EIfT(_, _, _, _, _, rule2, _) => rule2.evaluated(),
+            EIfF(_, _, _, _, _, rule2, _) => rule2.evaluated(),
+            EPlus(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            EMinus(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ETimes(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ELt(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            BPlus(_, _, expr3, _) => expr3.clone(),
+            BMinus(_, _, expr3, _) => expr3.clone(),
+            BTimes(_, _, expr3, _) => expr3.clone(),
+            BLt(_, _, expr3, _) => expr3.clone(),
+            EVar(env, expr, _) => env.get(expr),
+            ELet(_, _, _, _, _, rule2, _) => rule2.evaluated(),
+            EFun(env, expr1, expr2, _) => Closure(
+                env.clone(),
+                Box::new(expr1.clone()),
+                Box::new(expr2.clone()),
+            ),
+            EApp(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ELetRec(_, _, _, _, _, rule, _) => rule.evaluated(),
+            EAppRec(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ENil(_, _) => Nil,
+            ECons(_, _, _, rule1, rule2, _) => {
+                Cons(Box::new(rule1.evaluated()), Box::new(rule2.evaluated()))
+            }
+            EMatchM1(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            EMatchM2(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            EMatchN(_, _, _, _, _, rule3, _) => rule3.evaluated(),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn matched(&self) -> Env {
+        use crate::eval_ml5::rule::Rule::*;
+
+        match self {
+            MVar(s, expr, _) => Env::from(vec![(Var(s.clone()), expr.clone())]),
+            MNil(_) => Env::new(),
+            MCons(_, _, _, _, rule1, rule2, _) => {
+                let mut env = rule1.matched();
+                env.append(rule2.matched());
+                env
+            }
+            MWild(_, _) => Env::new(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_ml5::rule::Rule::*;
+
+        match self {
+            EInt(env, i, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Int {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    i,
+                    i
+                )
+            }
+            EBool(env, b, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Bool {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    b,
+                    b
+                )
+            }
+            EIfT(env, expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- if {} then {} else {} evalto {} by E-IfT {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EIfF(env, expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- if {} then {} else {} evalto {} by E-IfF {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EPlus(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Plus {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMinus(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Minus {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ETimes(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Times {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELt(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Lt {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            BPlus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} plus {} is {} by B-Plus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BMinus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} minus {} is {} by B-Minus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BTimes(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} times {} is {} by B-Times {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BLt(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} less than {} is {} by B-Lt {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            EVar(env, expr, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Var {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )
+            }
+            ELet(env, expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- let {} = {} in {} evalto {} by E-Let {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EFun(env, expr1, expr2, depth) => {
+                write!(
+                    f,
+                    "{}{}|- fun {} -> {} evalto {} by E-Fun {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    self.evaluated()
+                )
+            }
+            EApp(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = App(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-App {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELetRec(env, expr1, expr2, expr3, expr4, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- let rec {} = fun {} -> {} in {} evalto {} by E-LetRec {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr1,
+                    expr2,
+                    expr3,
+                    expr4,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EAppRec(env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = App(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-AppRec {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ENil(env, depth) => {
+                write!(
+                    f,
+                    "{}{}|- [] evalto [] by E-Nil {{}}",
+                    ident(*depth),
+                    env_prefix(env)
+                )
+            }
+            ECons(env, expr1, expr2, rule1, rule2, depth) => {
+                let expr = Cons(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-Cons {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMatchM1(env, expr, clauses, rule1, rule2, rule3, depth) => {
+                let expr = Match(Box::new(expr.clone()), clauses.clone());
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-MatchM1 {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMatchM2(env, expr, clauses, rule1, rule2, rule3, depth) => {
+                let expr = Match(Box::new(expr.clone()), clauses.clone());
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-MatchM2 {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMatchN(env, expr, clauses, rule1, rule2, rule3, depth) => {
+                let expr = Match(Box::new(expr.clone()), clauses.clone());
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {} by E-MatchN {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            MVar(s, expr, depth) => {
+                write!(
+                    f,
+                    "{}{} matches {} when ({}) by M-Var {{}}",
+                    ident(*depth),
+                    s,
+                    expr,
+                    self.matched()
+                )
+            }
+            MNil(depth) => {
+                write!(f, "{}[] matches [] when () by M-Nil {{}}", ident(*depth))
+            }
+            MCons(pattern1, pattern2, expr1, expr2, rule1, rule2, depth) => {
+                let pattern = Pattern::Cons(Box::new(pattern1.clone()), Box::new(pattern2.clone()));
+                let expr = Cons(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} matches {} when ({}) by M-Cons {{",
+                    ident(*depth),
+                    pattern,
+                    expr,
+                    self.matched()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            MWild(expr, depth) => {
+                write!(
+                    f,
+                    "{}_ matches {} when () by M-Wild {{}}",
+                    ident(*depth),
+                    expr
+                )
+            }
+            NMConsNil(pattern1, pattern2, depth) => {
+                let pattern = Pattern::Cons(Box::new(pattern1.clone()), Box::new(pattern2.clone()));
+                write!(
+                    f,
+                    "{}{} doesn't match [] by NM-ConsNil {{}}",
+                    ident(*depth),
+                    pattern
+                )
+            }
+            NMNilCons(expr1, expr2, depth) => {
+                let expr = Cons(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                write!(
+                    f,
+                    "{}[] doesn't match {} by NM-NilCons {{}}",
+                    ident(*depth),
+                    expr
+                )
+            }
+            NMConsConsL(pattern1, pattern2, expr1, expr2, rule, depth) => {
+                let pattern = Pattern::Cons(Box::new(pattern1.clone()), Box::new(pattern2.clone()));
+                let expr = Cons(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} doesn't match {} by NM-ConsConsL {{",
+                    ident(*depth),
+                    pattern,
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            NMConsConsR(pattern1, pattern2, expr1, expr2, rule, depth) => {
+                let pattern = Pattern::Cons(Box::new(pattern1.clone()), Box::new(pattern2.clone()));
+                let expr = Cons(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} doesn't match {} by NM-ConsConsR {{",
+                    ident(*depth),
+                    pattern,
+                    expr
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+        }
+    }
+}
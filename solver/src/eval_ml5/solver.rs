@@ -0,0 +1,1138 @@
+use crate::eval_error::{check_depth, checked, EvalError};
+use crate::eval_ml5::{
+    ast::{Env, Expr, Expr::*, Pattern},
+    rule::{Rule, Rule::*},
+};
+
+pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Result<Rule, EvalError> {
+    check_depth(depth)?;
+    let rule = match expr {
+        Int(i) => EInt(env.clone(), *i, depth),
+        Bool(b) => EBool(env.clone(), *b, depth),
+        If(expr1, expr2, expr3) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            match rule1.evaluated() {
+                Bool(true) => {
+                    let rule2 = solve(env, expr2, depth + 1)?;
+                    EIfT(
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        *expr3.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+                Bool(false) => {
+                    let rule2 = solve(env, expr3, depth + 1)?;
+                    EIfF(
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        *expr3.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            }
+        }
+        Plus(expr1, expr2) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_add(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BPlus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            EPlus(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Minus(expr1, expr2) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_sub(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BMinus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            EMinus(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Times(expr1, expr2) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_mul(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BTimes(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            ETimes(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Lt(expr1, expr2) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Bool(i1 < i2),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BLt(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            ELt(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Var(_) => {
+            if !env.contains(expr) {
+                return Err(EvalError::Unbound(expr.to_string()));
+            }
+            EVar(env.clone(), expr.clone(), depth)
+        }
+        Let(expr1, expr2, expr3) => {
+            let rule1 = solve(env, expr2, depth + 1)?;
+            let mut new_env = env.clone();
+            new_env.put(*expr1.clone(), rule1.evaluated());
+            let rule2 = solve(&new_env, expr3, depth + 1)?;
+            ELet(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            )
+        }
+        Fun(expr1, expr2) => EFun(env.clone(), *expr1.clone(), *expr2.clone(), depth),
+        App(expr1, expr2) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
+            match rule1.evaluated() {
+                Closure(env2, expr3, expr4) => {
+                    let mut new_env = env2;
+                    new_env.put(*expr3, rule2.evaluated());
+                    let rule3 = solve(&new_env, &expr4, depth + 1)?;
+                    EApp(
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        Box::new(rule3),
+                        depth,
+                    )
+                }
+                RecClosure(env2, expr3, expr4, expr5) => {
+                    let mut new_env = env2;
+                    new_env.put(*expr3, rule1.evaluated());
+                    new_env.put(*expr4, rule2.evaluated());
+                    let rule3 = solve(&new_env, &expr5, depth + 1)?;
+                    EAppRec(
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        Box::new(rule3),
+                        depth,
+                    )
+                }
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            }
+        }
+        LetRec(expr1, expr2, expr3, expr4) => {
+            let closure = RecClosure(env.clone(), expr1.clone(), expr2.clone(), expr3.clone());
+            let mut new_env = env.clone();
+            new_env.put(*expr1.clone(), closure);
+            let rule = solve(&new_env, expr4, depth + 1)?;
+            ELetRec(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                *expr4.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+        Closure(_, _, _) => return Err(EvalError::Stuck(expr.to_string())),
+        RecClosure(_, _, _, _) => return Err(EvalError::Stuck(expr.to_string())),
+        Nil => ENil(env.clone(), depth),
+        Cons(expr1, expr2) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
+            ECons(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            )
+        }
+        Match(expr1, clauses) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let (pattern, expr2) = &clauses[0];
+            // 値がリストでなければマッチ式全体が行き詰まる
+            let result =
+                solve_match(pattern, &rule1.evaluated(), depth + 1).map_err(|e| match e {
+                    EvalError::Stuck(_) => EvalError::Stuck(expr.to_string()),
+                    e => e,
+                })?;
+            match result {
+                Ok((bindings, rule2)) => {
+                    let mut new_env = env.clone();
+                    new_env.append(bindings);
+                    let rule3 = solve(&new_env, expr2, depth + 1)?;
+                    if clauses.len() == 1 {
+                        EMatchM1(
+                            env.clone(),
+                            *expr1.clone(),
+                            clauses.clone(),
+                            Box::new(rule1),
+                            Box::new(rule2),
+                            Box::new(rule3),
+                            depth,
+                        )
+                    } else {
+                        EMatchM2(
+                            env.clone(),
+                            *expr1.clone(),
+                            clauses.clone(),
+                            Box::new(rule1),
+                            Box::new(rule2),
+                            Box::new(rule3),
+                            depth,
+                        )
+                    }
+                }
+                Err(rule2) => {
+                    // どの節にもマッチしない
+                    if clauses.len() == 1 {
+                        return Err(EvalError::Stuck(expr.to_string()));
+                    }
+                    let expr3 = Match(expr1.clone(), clauses[1..].to_vec());
+                    let rule3 = solve(env, &expr3, depth + 1)?;
+                    EMatchN(
+                        env.clone(),
+                        *expr1.clone(),
+                        clauses.clone(),
+                        Box::new(rule1),
+                        rule2,
+                        Box::new(rule3),
+                        depth,
+                    )
+                }
+            }
+        }
+    };
+    Ok(rule)
+}
+
+// パターンにマッチすれば Ok(Ok(..))、マッチしなければ Ok(Err(..))
+pub fn solve_match(
+    pattern: &Pattern,
+    expr: &Expr,
+    depth: usize,
+) -> Result<Result<(Env, Rule), Box<Rule>>, EvalError> {
+    check_depth(depth)?;
+    let result = match (pattern, expr) {
+        (Pattern::Var(s), _) => {
+            let env = Env::from(vec![(Var(s.clone()), expr.clone())]);
+            Ok((env, MVar(s.clone(), expr.clone(), depth)))
+        }
+        (Pattern::Nil, Nil) => Ok((Env::new(), MNil(depth))),
+        (Pattern::Cons(pattern1, pattern2), Cons(expr1, expr2)) => {
+            match solve_match(pattern1, expr1, depth + 1)? {
+                Ok((mut env, rule1)) => match solve_match(pattern2, expr2, depth + 1)? {
+                    Ok((env2, rule2)) => {
+                        env.append(env2);
+                        let rule = MCons(
+                            *pattern1.clone(),
+                            *pattern2.clone(),
+                            *expr1.clone(),
+                            *expr2.clone(),
+                            Box::new(rule1),
+                            Box::new(rule2),
+                            depth,
+                        );
+                        Ok((env, rule))
+                    }
+                    Err(rule2) => Err(Box::new(NMConsConsR(
+                        *pattern1.clone(),
+                        *pattern2.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        rule2,
+                        depth,
+                    ))),
+                },
+                Err(rule1) => Err(Box::new(NMConsConsL(
+                    *pattern1.clone(),
+                    *pattern2.clone(),
+                    *expr1.clone(),
+                    *expr2.clone(),
+                    rule1,
+                    depth,
+                ))),
+            }
+        }
+        (Pattern::Wild, _) => Ok((Env::new(), MWild(expr.clone(), depth))),
+        (Pattern::Cons(pattern1, pattern2), Nil) => Err(Box::new(NMConsNil(
+            *pattern1.clone(),
+            *pattern2.clone(),
+            depth,
+        ))),
+        (Pattern::Nil, Cons(expr1, expr2)) => {
+            Err(Box::new(NMNilCons(*expr1.clone(), *expr2.clone(), depth)))
+        }
+        _ => return Err(EvalError::Stuck(expr.to_string())),
+    };
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_ml5::{
+        ast::{Env, Expr::*, Pattern},
+        parser::parse,
+        rule::Rule::*,
+        solver::{solve, solve_match},
+    };
+
+    #[test]
+    fn test_solve1() {
+        assert_eq!(
+            solve(&Env::new(), &Plus(Box::new(Int(3)), Box::new(Int(5))), 0).unwrap(),
+            EPlus(
+                Env::new(),
+                Int(3),
+                Int(5),
+                Box::new(EInt(Env::new(), 3, 1)),
+                Box::new(EInt(Env::new(), 5, 1)),
+                Box::new(BPlus(Int(3), Int(5), Int(8), 1)),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve2() {
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &Minus(
+                    Box::new(Minus(Box::new(Int(8)), Box::new(Int(2)))),
+                    Box::new(Int(3))
+                ),
+                0
+            )
+            .unwrap(),
+            EMinus(
+                Env::new(),
+                Minus(Box::new(Int(8)), Box::new(Int(2))),
+                Int(3),
+                Box::new(EMinus(
+                    Env::new(),
+                    Int(8),
+                    Int(2),
+                    Box::new(EInt(Env::new(), 8, 2)),
+                    Box::new(EInt(Env::new(), 2, 2)),
+                    Box::new(BMinus(Int(8), Int(2), Int(6), 2)),
+                    1
+                )),
+                Box::new(EInt(Env::new(), 3, 1)),
+                Box::new(BMinus(Int(6), Int(3), Int(3), 1)),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve3() {
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &Times(
+                    Box::new(Plus(Box::new(Int(4)), Box::new(Int(5)))),
+                    Box::new(Minus(Box::new(Int(1)), Box::new(Int(10))))
+                ),
+                0
+            )
+            .unwrap(),
+            ETimes(
+                Env::new(),
+                Plus(Box::new(Int(4)), Box::new(Int(5))),
+                Minus(Box::new(Int(1)), Box::new(Int(10))),
+                Box::new(EPlus(
+                    Env::new(),
+                    Int(4),
+                    Int(5),
+                    Box::new(EInt(Env::new(), 4, 2)),
+                    Box::new(EInt(Env::new(), 5, 2)),
+                    Box::new(BPlus(Int(4), Int(5), Int(9), 2)),
+                    1
+                )),
+                Box::new(EMinus(
+                    Env::new(),
+                    Int(1),
+                    Int(10),
+                    Box::new(EInt(Env::new(), 1, 2)),
+                    Box::new(EInt(Env::new(), 10, 2)),
+                    Box::new(BMinus(Int(1), Int(10), Int(-9), 2)),
+                    1
+                )),
+                Box::new(BTimes(Int(9), Int(-9), Int(-81), 1)),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve4() {
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &If(
+                    Box::new(Lt(Box::new(Int(4)), Box::new(Int(5)))),
+                    Box::new(Plus(Box::new(Int(2)), Box::new(Int(3)))),
+                    Box::new(Times(Box::new(Int(8)), Box::new(Int(8))))
+                ),
+                0
+            )
+            .unwrap(),
+            EIfT(
+                Env::new(),
+                Lt(Box::new(Int(4)), Box::new(Int(5))),
+                Plus(Box::new(Int(2)), Box::new(Int(3))),
+                Times(Box::new(Int(8)), Box::new(Int(8))),
+                Box::new(ELt(
+                    Env::new(),
+                    Int(4),
+                    Int(5),
+                    Box::new(EInt(Env::new(), 4, 2)),
+                    Box::new(EInt(Env::new(), 5, 2)),
+                    Box::new(BLt(Int(4), Int(5), Bool(true), 2)),
+                    1
+                )),
+                Box::new(EPlus(
+                    Env::new(),
+                    Int(2),
+                    Int(3),
+                    Box::new(EInt(Env::new(), 2, 2)),
+                    Box::new(EInt(Env::new(), 3, 2)),
+                    Box::new(BPlus(Int(2), Int(3), Int(5), 2)),
+                    1
+                )),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve5() {
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &Plus(
+                    Box::new(Int(3)),
+                    Box::new(If(
+                        Box::new(Lt(
+                            Box::new(Int(-23)),
+                            Box::new(Times(Box::new(Int(-2)), Box::new(Int(8))))
+                        )),
+                        Box::new(Int(8)),
+                        Box::new(Plus(Box::new(Int(2)), Box::new(Int(4))))
+                    ))
+                ),
+                0
+            )
+            .unwrap(),
+            EPlus(
+                Env::new(),
+                Int(3),
+                If(
+                    Box::new(Lt(
+                        Box::new(Int(-23)),
+                        Box::new(Times(Box::new(Int(-2)), Box::new(Int(8))))
+                    )),
+                    Box::new(Int(8)),
+                    Box::new(Plus(Box::new(Int(2)), Box::new(Int(4))))
+                ),
+                Box::new(EInt(Env::new(), 3, 1)),
+                Box::new(EIfT(
+                    Env::new(),
+                    Lt(
+                        Box::new(Int(-23)),
+                        Box::new(Times(Box::new(Int(-2)), Box::new(Int(8))))
+                    ),
+                    Int(8),
+                    Plus(Box::new(Int(2)), Box::new(Int(4))),
+                    Box::new(ELt(
+                        Env::new(),
+                        Int(-23),
+                        Times(Box::new(Int(-2)), Box::new(Int(8))),
+                        Box::new(EInt(Env::new(), -23, 3)),
+                        Box::new(ETimes(
+                            Env::new(),
+                            Int(-2),
+                            Int(8),
+                            Box::new(EInt(Env::new(), -2, 4)),
+                            Box::new(EInt(Env::new(), 8, 4)),
+                            Box::new(BTimes(Int(-2), Int(8), Int(-16), 4)),
+                            3
+                        )),
+                        Box::new(BLt(Int(-23), Int(-16), Bool(true), 3)),
+                        2
+                    )),
+                    Box::new(EInt(Env::new(), 8, 2)),
+                    1
+                )),
+                Box::new(BPlus(Int(3), Int(8), Int(11), 1)),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve6() {
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &Plus(
+                    Box::new(Plus(
+                        Box::new(Int(3)),
+                        Box::new(If(
+                            Box::new(Lt(
+                                Box::new(Int(-23)),
+                                Box::new(Times(Box::new(Int(-2)), Box::new(Int(8))))
+                            )),
+                            Box::new(Int(8)),
+                            Box::new(Int(2))
+                        ))
+                    )),
+                    Box::new(Int(4))
+                ),
+                0
+            )
+            .unwrap(),
+            EPlus(
+                Env::new(),
+                Plus(
+                    Box::new(Int(3)),
+                    Box::new(If(
+                        Box::new(Lt(
+                            Box::new(Int(-23)),
+                            Box::new(Times(Box::new(Int(-2)), Box::new(Int(8))))
+                        )),
+                        Box::new(Int(8)),
+                        Box::new(Int(2))
+                    ))
+                ),
+                Int(4),
+                Box::new(EPlus(
+                    Env::new(),
+                    Int(3),
+                    If(
+                        Box::new(Lt(
+                            Box::new(Int(-23)),
+                            Box::new(Times(Box::new(Int(-2)), Box::new(Int(8))))
+                        )),
+                        Box::new(Int(8)),
+                        Box::new(Int(2))
+                    ),
+                    Box::new(EInt(Env::new(), 3, 2)),
+                    Box::new(EIfT(
+                        Env::new(),
+                        Lt(
+                            Box::new(Int(-23)),
+                            Box::new(Times(Box::new(Int(-2)), Box::new(Int(8))))
+                        ),
+                        Int(8),
+                        Int(2),
+                        Box::new(ELt(
+                            Env::new(),
+                            Int(-23),
+                            Times(Box::new(Int(-2)), Box::new(Int(8))),
+                            Box::new(EInt(Env::new(), -23, 4)),
+                            Box::new(ETimes(
+                                Env::new(),
+                                Int(-2),
+                                Int(8),
+                                Box::new(EInt(Env::new(), -2, 5)),
+                                Box::new(EInt(Env::new(), 8, 5)),
+                                Box::new(BTimes(Int(-2), Int(8), Int(-16), 5)),
+                                4
+                            )),
+                            Box::new(BLt(Int(-23), Int(-16), Bool(true), 4)),
+                            3
+                        )),
+                        Box::new(EInt(Env::new(), 8, 3)),
+                        2
+                    )),
+                    Box::new(BPlus(Int(3), Int(8), Int(11), 2)),
+                    1
+                )),
+                Box::new(EInt(Env::new(), 4, 1)),
+                Box::new(BPlus(Int(11), Int(4), Int(15), 1)),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve7() {
+        assert_eq!(
+            solve(
+                &Env::from(vec![
+                    (Var("x".to_string()), Int(3)),
+                    (Var("y".to_string()), Int(2))
+                ]),
+                &Var("x".to_string()),
+                0
+            )
+            .unwrap(),
+            EVar(
+                Env::from(vec![
+                    (Var("x".to_string()), Int(3)),
+                    (Var("y".to_string()), Int(2))
+                ]),
+                Var("x".to_string()),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve8() {
+        assert_eq!(
+            solve(
+                &Env::from(vec![
+                    (Var("x".to_string()), Bool(true)),
+                    (Var("y".to_string()), Int(4))
+                ]),
+                &If(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Plus(Box::new(Var("y".to_string())), Box::new(Int(1)))),
+                    Box::new(Var("y".to_string()))
+                ),
+                0
+            )
+            .unwrap(),
+            EIfT(
+                Env::from(vec![
+                    (Var("x".to_string()), Bool(true)),
+                    (Var("y".to_string()), Int(4))
+                ]),
+                Var("x".to_string()),
+                Plus(Box::new(Var("y".to_string())), Box::new(Int(1))),
+                Var("y".to_string()),
+                Box::new(EVar(
+                    Env::from(vec![
+                        (Var("x".to_string()), Bool(true)),
+                        (Var("y".to_string()), Int(4))
+                    ]),
+                    Var("x".to_string()),
+                    1
+                )),
+                Box::new(EPlus(
+                    Env::from(vec![
+                        (Var("x".to_string()), Bool(true)),
+                        (Var("y".to_string()), Int(4))
+                    ]),
+                    Var("y".to_string()),
+                    Int(1),
+                    Box::new(EVar(
+                        Env::from(vec![
+                            (Var("x".to_string()), Bool(true)),
+                            (Var("y".to_string()), Int(4))
+                        ]),
+                        Var("y".to_string()),
+                        2
+                    )),
+                    Box::new(EInt(
+                        Env::from(vec![
+                            (Var("x".to_string()), Bool(true)),
+                            (Var("y".to_string()), Int(4))
+                        ]),
+                        1,
+                        2
+                    )),
+                    Box::new(BPlus(Int(4), Int(1), Int(5), 2)),
+                    1
+                )),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve9() {
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &Let(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Plus(Box::new(Int(1)), Box::new(Int(2)))),
+                    Box::new(Times(Box::new(Var("x".to_string())), Box::new(Int(4))))
+                ),
+                0
+            )
+            .unwrap(),
+            ELet(
+                Env::new(),
+                Var("x".to_string()),
+                Plus(Box::new(Int(1)), Box::new(Int(2))),
+                Times(Box::new(Var("x".to_string())), Box::new(Int(4))),
+                Box::new(EPlus(
+                    Env::new(),
+                    Int(1),
+                    Int(2),
+                    Box::new(EInt(Env::new(), 1, 2)),
+                    Box::new(EInt(Env::new(), 2, 2)),
+                    Box::new(BPlus(Int(1), Int(2), Int(3), 2)),
+                    1
+                )),
+                Box::new(ETimes(
+                    Env::from(vec![(Var("x".to_string()), Int(3))]),
+                    Var("x".to_string()),
+                    Int(4),
+                    Box::new(EVar(
+                        Env::from(vec![(Var("x".to_string()), Int(3))]),
+                        Var("x".to_string()),
+                        2
+                    )),
+                    Box::new(EInt(Env::from(vec![(Var("x".to_string()), Int(3))]), 4, 2)),
+                    Box::new(BTimes(Int(3), Int(4), Int(12), 2)),
+                    1
+                )),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve10() {
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &Let(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Times(Box::new(Int(3)), Box::new(Int(3)))),
+                    Box::new(Let(
+                        Box::new(Var("y".to_string())),
+                        Box::new(Times(Box::new(Int(4)), Box::new(Var("x".to_string())))),
+                        Box::new(Plus(
+                            Box::new(Var("x".to_string())),
+                            Box::new(Var("y".to_string()))
+                        ))
+                    ))
+                ),
+                0
+            )
+            .unwrap(),
+            ELet(
+                Env::new(),
+                Var("x".to_string()),
+                Times(Box::new(Int(3)), Box::new(Int(3))),
+                Let(
+                    Box::new(Var("y".to_string())),
+                    Box::new(Times(Box::new(Int(4)), Box::new(Var("x".to_string())))),
+                    Box::new(Plus(
+                        Box::new(Var("x".to_string())),
+                        Box::new(Var("y".to_string()))
+                    ))
+                ),
+                Box::new(ETimes(
+                    Env::new(),
+                    Int(3),
+                    Int(3),
+                    Box::new(EInt(Env::new(), 3, 2)),
+                    Box::new(EInt(Env::new(), 3, 2)),
+                    Box::new(BTimes(Int(3), Int(3), Int(9), 2)),
+                    1
+                )),
+                Box::new(ELet(
+                    Env::from(vec![(Var("x".to_string()), Int(9))]),
+                    Var("y".to_string()),
+                    Times(Box::new(Int(4)), Box::new(Var("x".to_string()))),
+                    Plus(
+                        Box::new(Var("x".to_string())),
+                        Box::new(Var("y".to_string()))
+                    ),
+                    Box::new(ETimes(
+                        Env::from(vec![(Var("x".to_string()), Int(9))]),
+                        Int(4),
+                        Var("x".to_string()),
+                        Box::new(EInt(Env::from(vec![(Var("x".to_string()), Int(9))]), 4, 3)),
+                        Box::new(EVar(
+                            Env::from(vec![(Var("x".to_string()), Int(9))]),
+                            Var("x".to_string()),
+                            3
+                        )),
+                        Box::new(BTimes(Int(4), Int(9), Int(36), 3)),
+                        2
+                    )),
+                    Box::new(EPlus(
+                        Env::from(vec![
+                            (Var("x".to_string()), Int(9)),
+                            (Var("y".to_string()), Int(36))
+                        ]),
+                        Var("x".to_string()),
+                        Var("y".to_string()),
+                        Box::new(EVar(
+                            Env::from(vec![
+                                (Var("x".to_string()), Int(9)),
+                                (Var("y".to_string()), Int(36))
+                            ]),
+                            Var("x".to_string()),
+                            3
+                        )),
+                        Box::new(EVar(
+                            Env::from(vec![
+                                (Var("x".to_string()), Int(9)),
+                                (Var("y".to_string()), Int(36))
+                            ]),
+                            Var("y".to_string()),
+                            3
+                        )),
+                        Box::new(BPlus(Int(9), Int(36), Int(45), 3)),
+                        2
+                    )),
+                    1
+                )),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve11() {
+        assert_eq!(
+            solve(
+                &Env::from(vec![(Var("x".to_string()), Int(3))]),
+                &Let(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Times(Box::new(Var("x".to_string())), Box::new(Int(2)))),
+                    Box::new(Plus(
+                        Box::new(Var("x".to_string())),
+                        Box::new(Var("x".to_string()))
+                    )),
+                ),
+                0
+            )
+            .unwrap(),
+            ELet(
+                Env::from(vec![(Var("x".to_string()), Int(3))]),
+                Var("x".to_string()),
+                Times(Box::new(Var("x".to_string())), Box::new(Int(2))),
+                Plus(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Var("x".to_string()))
+                ),
+                Box::new(ETimes(
+                    Env::from(vec![(Var("x".to_string()), Int(3))]),
+                    Var("x".to_string()),
+                    Int(2),
+                    Box::new(EVar(
+                        Env::from(vec![(Var("x".to_string()), Int(3))]),
+                        Var("x".to_string()),
+                        2
+                    )),
+                    Box::new(EInt(Env::from(vec![(Var("x".to_string()), Int(3))]), 2, 2)),
+                    Box::new(BTimes(Int(3), Int(2), Int(6), 2)),
+                    1
+                )),
+                Box::new(EPlus(
+                    Env::from(vec![
+                        (Var("x".to_string()), Int(3)),
+                        (Var("x".to_string()), Int(6))
+                    ]),
+                    Var("x".to_string()),
+                    Var("x".to_string()),
+                    Box::new(EVar(
+                        Env::from(vec![
+                            (Var("x".to_string()), Int(3)),
+                            (Var("x".to_string()), Int(6))
+                        ]),
+                        Var("x".to_string()),
+                        2
+                    )),
+                    Box::new(EVar(
+                        Env::from(vec![
+                            (Var("x".to_string()), Int(3)),
+                            (Var("x".to_string()), Int(6))
+                        ]),
+                        Var("x".to_string()),
+                        2
+                    )),
+                    Box::new(BPlus(Int(6), Int(6), Int(12), 2)),
+                    1
+                )),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve12() {
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &App(
+                    Box::new(Fun(
+                        Box::new(Var("x".to_string())),
+                        Box::new(Var("x".to_string()))
+                    )),
+                    Box::new(Int(1))
+                ),
+                0
+            )
+            .unwrap(),
+            EApp(
+                Env::new(),
+                Fun(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Var("x".to_string()))
+                ),
+                Int(1),
+                Box::new(EFun(
+                    Env::new(),
+                    Var("x".to_string()),
+                    Var("x".to_string()),
+                    1
+                )),
+                Box::new(EInt(Env::new(), 1, 1)),
+                Box::new(EVar(
+                    Env::from(vec![(Var("x".to_string()), Int(1))]),
+                    Var("x".to_string()),
+                    1
+                )),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve13() {
+        let closure = RecClosure(
+            Env::new(),
+            Box::new(Var("f".to_string())),
+            Box::new(Var("x".to_string())),
+            Box::new(Var("x".to_string())),
+        );
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &LetRec(
+                    Box::new(Var("f".to_string())),
+                    Box::new(Var("x".to_string())),
+                    Box::new(Var("x".to_string())),
+                    Box::new(App(Box::new(Var("f".to_string())), Box::new(Int(2))))
+                ),
+                0
+            )
+            .unwrap(),
+            ELetRec(
+                Env::new(),
+                Var("f".to_string()),
+                Var("x".to_string()),
+                Var("x".to_string()),
+                App(Box::new(Var("f".to_string())), Box::new(Int(2))),
+                Box::new(EAppRec(
+                    Env::from(vec![(Var("f".to_string()), closure.clone())]),
+                    Var("f".to_string()),
+                    Int(2),
+                    Box::new(EVar(
+                        Env::from(vec![(Var("f".to_string()), closure.clone())]),
+                        Var("f".to_string()),
+                        2
+                    )),
+                    Box::new(EInt(
+                        Env::from(vec![(Var("f".to_string()), closure.clone())]),
+                        2,
+                        2
+                    )),
+                    Box::new(EVar(
+                        Env::from(vec![
+                            (Var("f".to_string()), closure.clone()),
+                            (Var("x".to_string()), Int(2))
+                        ]),
+                        Var("x".to_string()),
+                        2
+                    )),
+                    1
+                )),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve14() {
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &App(
+                    Box::new(Fun(
+                        Box::new(Var("x".to_string())),
+                        Box::new(Var("x".to_string()))
+                    )),
+                    Box::new(Int(1))
+                ),
+                0
+            )
+            .unwrap()
+            .to_string(),
+            "|- (fun x -> x) 1 evalto 1 by E-App {
+    |- fun x -> x evalto ()[fun x -> x] by E-Fun {};
+    |- 1 evalto 1 by E-Int {};
+    x = 1 |- x evalto 1 by E-Var {}
+}"
+        );
+    }
+
+    #[test]
+    fn test_solve15() {
+        let (env, expr) = parse("|- match 1 :: [] with [] -> 0 | a :: _ -> a")
+            .unwrap()
+            .1;
+        assert_eq!(solve(&env, &expr, 0).unwrap().evaluated(), Int(1));
+    }
+
+    #[test]
+    fn test_solve16() {
+        let (env, expr) = parse(
+            "|- let rec max = fun l -> match l with x :: [] -> x | x :: y :: z -> if x < y then max (y :: z) else max (x :: z) in max (9 :: 2 :: 3 :: [])",
+        )
+        .unwrap()
+        .1;
+        assert_eq!(solve(&env, &expr, 0).unwrap().evaluated(), Int(9));
+    }
+
+    #[test]
+    fn test_solve17() {
+        assert_eq!(
+            solve_match(
+                &Pattern::Cons(
+                    Box::new(Pattern::Var("x".to_string())),
+                    Box::new(Pattern::Nil)
+                ),
+                &Cons(Box::new(Int(1)), Box::new(Nil)),
+                0
+            )
+            .unwrap(),
+            Ok((
+                Env::from(vec![(Var("x".to_string()), Int(1))]),
+                MCons(
+                    Pattern::Var("x".to_string()),
+                    Pattern::Nil,
+                    Int(1),
+                    Nil,
+                    Box::new(MVar("x".to_string(), Int(1), 1)),
+                    Box::new(MNil(1)),
+                    0
+                )
+            ))
+        );
+        assert_eq!(
+            solve_match(
+                &Pattern::Cons(Box::new(Pattern::Wild), Box::new(Pattern::Nil)),
+                &Cons(
+                    Box::new(Int(1)),
+                    Box::new(Cons(Box::new(Int(2)), Box::new(Nil)))
+                ),
+                0
+            )
+            .unwrap(),
+            Err(Box::new(NMConsConsR(
+                Pattern::Wild,
+                Pattern::Nil,
+                Int(1),
+                Cons(Box::new(Int(2)), Box::new(Nil)),
+                Box::new(NMNilCons(Int(2), Nil, 1)),
+                0
+            )))
+        );
+    }
+
+    #[test]
+    fn test_solve18() {
+        let (env, expr) = parse("|- match [] with x :: y -> x | _ -> 0").unwrap().1;
+        assert_eq!(
+            solve(&env, &expr, 0).unwrap().to_string(),
+            "|- match [] with x :: y -> x | _ -> 0 evalto 0 by E-MatchN {
+    |- [] evalto [] by E-Nil {};
+    x :: y doesn't match [] by NM-ConsNil {};
+    |- match [] with _ -> 0 evalto 0 by E-MatchM1 {
+        |- [] evalto [] by E-Nil {};
+        _ matches [] when () by M-Wild {};
+        |- 0 evalto 0 by E-Int {}
+    }
+}"
+        );
+    }
+
+    #[test]
+    fn test_solve19() {
+        let (env, expr) = parse("|- match 1 :: [] with x :: y -> x | _ -> 0")
+            .unwrap()
+            .1;
+        assert_eq!(
+            solve(&env, &expr, 0).unwrap().to_string(),
+            "|- match 1 :: [] with x :: y -> x | _ -> 0 evalto 1 by E-MatchM2 {
+    |- 1 :: [] evalto 1 :: [] by E-Cons {
+        |- 1 evalto 1 by E-Int {};
+        |- [] evalto [] by E-Nil {}
+    };
+    x :: y matches 1 :: [] when (x = 1, y = []) by M-Cons {
+        x matches 1 when (x = 1) by M-Var {};
+        y matches [] when (y = []) by M-Var {}
+    };
+    x = 1, y = [] |- x evalto 1 by E-Var {}
+}"
+        );
+    }
+
+    #[test]
+    fn test_display_lt() {
+        let (env, expr) = parse("|- 3 < 4").unwrap().1;
+        assert_eq!(
+            solve(&env, &expr, 0).unwrap().to_string(),
+            "|- 3 < 4 evalto true by E-Lt {
+    |- 3 evalto 3 by E-Int {};
+    |- 4 evalto 4 by E-Int {};
+    3 less than 4 is true by B-Lt {}
+}"
+        );
+    }
+}
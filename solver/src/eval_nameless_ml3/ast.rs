@@ -0,0 +1,188 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Expr {
+    Int(i64),
+    Bool(bool),
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+    Plus(Box<Expr>, Box<Expr>),
+    Minus(Box<Expr>, Box<Expr>),
+    Times(Box<Expr>, Box<Expr>),
+    Lt(Box<Expr>, Box<Expr>),
+    Index(usize),
+    Let(Box<Expr>, Box<Expr>),
+    Fun(Box<Expr>),
+    App(Box<Expr>, Box<Expr>),
+    LetRec(Box<Expr>, Box<Expr>),
+    Closure(Env, Box<Expr>),
+    RecClosure(Env, Box<Expr>),
+}
+
+impl Expr {
+    // 結合の強さ (大きいほど強い)
+    fn precedence(&self) -> usize {
+        use crate::eval_nameless_ml3::ast::Expr::*;
+
+        match self {
+            If(_, _, _) | Let(_, _) | Fun(_) | LetRec(_, _) => 0,
+            Lt(_, _) => 1,
+            Plus(_, _) | Minus(_, _) => 3,
+            Times(_, _) => 4,
+            App(_, _) => 5,
+            Int(i) if *i < 0 => 5,
+            _ => 6,
+        }
+    }
+
+    // 右にできるだけ伸びる式 (右端にあれば括弧がいらない)
+    fn is_open(&self) -> bool {
+        use crate::eval_nameless_ml3::ast::Expr::*;
+
+        matches!(self, If(_, _, _) | Let(_, _) | Fun(_) | LetRec(_, _))
+    }
+
+    fn paren(&self, paren: bool) -> String {
+        if paren {
+            format!("({})", self)
+        } else {
+            self.to_string()
+        }
+    }
+
+    // tail は後ろに何も続かないこと
+    fn show(&self, tail: bool) -> String {
+        use crate::eval_nameless_ml3::ast::Expr::*;
+
+        let p = self.precedence();
+        let left = |expr: &Expr, paren: bool| {
+            if paren {
+                expr.paren(true)
+            } else {
+                expr.show(false)
+            }
+        };
+        let right = |expr: &Expr, paren: bool| {
+            if paren && !(tail && expr.is_open()) {
+                expr.paren(true)
+            } else {
+                expr.show(tail)
+            }
+        };
+        match self {
+            Int(i) => i.to_string(),
+            Bool(b) => b.to_string(),
+            If(expr1, expr2, expr3) => {
+                format!("if {} then {} else {}", expr1, expr2, expr3.show(tail))
+            }
+            Plus(expr1, expr2) => format!(
+                "{} + {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Minus(expr1, expr2) => format!(
+                "{} - {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Times(expr1, expr2) => format!(
+                "{} * {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Lt(expr1, expr2) => format!(
+                "{} < {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Index(n) => format!("#{}", n),
+            Let(expr1, expr2) => format!("let . = {} in {}", expr1, expr2.show(tail)),
+            Fun(expr) => format!("fun . -> {}", expr.show(tail)),
+            App(expr1, expr2) => format!(
+                "{} {}",
+                left(expr1, expr1.precedence() < p),
+                expr2.paren(expr2.precedence() <= p)
+            ),
+            LetRec(expr1, expr2) => {
+                format!("let rec . = fun . -> {} in {}", expr1, expr2.show(tail))
+            }
+            Closure(env, expr) => format!("({})[fun . -> {}]", env, expr),
+            RecClosure(env, expr) => format!("({})[rec . = fun . -> {}]", env, expr),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.show(true))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Env(Vec<Expr>);
+
+impl Env {
+    pub fn new() -> Env {
+        Env(vec![])
+    }
+
+    pub fn from(exprs: Vec<Expr>) -> Env {
+        Env(exprs)
+    }
+
+    pub fn put(&mut self, expr: Expr) {
+        self.0.push(expr)
+    }
+
+    pub fn get(&self, n: usize) -> Expr {
+        self.0[self.0.len() - n].clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for Env {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let exprs = self
+            .0
+            .iter()
+            .map(|expr| expr.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}", exprs.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_nameless_ml3::ast::{Env, Expr::*};
+
+    #[test]
+    fn test_env() {
+        let mut env = Env::new();
+        env.put(Int(1));
+        env.put(Bool(true));
+        env.put(Int(3));
+        assert_eq!("1, true, 3", env.to_string());
+        assert_eq!(Int(3), env.get(1));
+        assert_eq!(Bool(true), env.get(2));
+        assert_eq!(Int(1), env.get(3));
+        assert_eq!("1, true", Env::from(vec![Int(1), Bool(true)]).to_string());
+    }
+
+    #[test]
+    fn test_display1() {
+        assert_eq!(
+            RecClosure(
+                Env::from(vec![Int(1)]),
+                Box::new(App(Box::new(Index(2)), Box::new(Index(1))))
+            )
+            .to_string(),
+            "(1)[rec . = fun . -> #2 #1]"
+        );
+    }
+}
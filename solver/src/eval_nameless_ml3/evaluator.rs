@@ -0,0 +1,111 @@
+use crate::eval_nameless_ml3::ast::{Env, Expr, Expr::*};
+
+pub fn eval(env: &Env, expr: &Expr) -> Expr {
+    match expr {
+        Int(i) => Int(*i),
+        Bool(b) => Bool(*b),
+        If(expr1, expr2, expr3) => match eval(env, expr1) {
+            Bool(true) => eval(env, expr2),
+            Bool(false) => eval(env, expr3),
+            _ => unreachable!(),
+        },
+        Plus(expr1, expr2) => match (eval(env, expr1), eval(env, expr2)) {
+            (Int(i1), Int(i2)) => Int(i1 + i2),
+            _ => unreachable!(),
+        },
+        Minus(expr1, expr2) => match (eval(env, expr1), eval(env, expr2)) {
+            (Int(i1), Int(i2)) => Int(i1 - i2),
+            _ => unreachable!(),
+        },
+        Times(expr1, expr2) => match (eval(env, expr1), eval(env, expr2)) {
+            (Int(i1), Int(i2)) => Int(i1 * i2),
+            _ => unreachable!(),
+        },
+        Lt(expr1, expr2) => match (eval(env, expr1), eval(env, expr2)) {
+            (Int(i1), Int(i2)) => Bool(i1 < i2),
+            _ => unreachable!(),
+        },
+        Index(n) => env.get(*n),
+        Let(expr1, expr2) => {
+            let expr1 = eval(env, expr1);
+            let mut env = env.clone();
+            env.put(expr1);
+            eval(&env, expr2)
+        }
+        Fun(expr) => Closure(env.clone(), expr.clone()),
+        App(expr1, expr2) => {
+            let expr1 = eval(env, expr1);
+            let expr2 = eval(env, expr2);
+            match expr1.clone() {
+                Closure(env, expr3) => {
+                    let mut env = env;
+                    env.put(expr2);
+                    eval(&env, &expr3)
+                }
+                RecClosure(env, expr3) => {
+                    let mut env = env;
+                    env.put(expr1);
+                    env.put(expr2);
+                    eval(&env, &expr3)
+                }
+                _ => unreachable!(),
+            }
+        }
+        LetRec(expr1, expr2) => {
+            let closure = RecClosure(env.clone(), expr1.clone());
+            let mut env = env.clone();
+            env.put(closure);
+            eval(&env, expr2)
+        }
+        Closure(_, _) => expr.clone(),
+        RecClosure(_, _) => expr.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_nameless_ml3::{
+        ast::{Env, Expr::*},
+        evaluator::eval,
+    };
+
+    #[test]
+    fn test_eval1() {
+        assert_eq!(
+            eval(
+                &Env::from(vec![Int(3), Bool(true)]),
+                &If(
+                    Box::new(Index(1)),
+                    Box::new(Plus(Box::new(Index(2)), Box::new(Int(1)))),
+                    Box::new(Int(0))
+                )
+            ),
+            Int(4)
+        );
+    }
+
+    #[test]
+    fn test_eval2() {
+        // let rec . = fun . -> if #1 < 2 then 1 else #1 * #2 (#1 - 1) in #1 3
+        assert_eq!(
+            eval(
+                &Env::new(),
+                &LetRec(
+                    Box::new(If(
+                        Box::new(Lt(Box::new(Index(1)), Box::new(Int(2)))),
+                        Box::new(Int(1)),
+                        Box::new(Times(
+                            Box::new(Index(1)),
+                            Box::new(App(
+                                Box::new(Index(2)),
+                                Box::new(Minus(Box::new(Index(1)), Box::new(Int(1))))
+                            ))
+                        ))
+                    )),
+                    Box::new(App(Box::new(Index(1)), Box::new(Int(3))))
+                )
+            ),
+            Int(6)
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use crate::{
+    eval_nameless_ml3::ast::{Env, Expr, Expr::*},
+    syntax::lexer::keyword,
+    util::ws,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, digit1},
+    combinator::{map_res, opt, recognize},
+    multi::{many0, separated_list0},
+    sequence::{delimited, tuple},
+    IResult,
+};
+
+// <expr> ::= <term1> [ <' <term1> ]
+// <term1> ::= <term2> [ ( '+' | '-' ) <term2> ]*
+// <term2> ::= <app> [ '*' <app> ]*
+// <app> ::= <factor> [ <arg> ]*
+// <factor> ::= <value> | <paren> | <if> | <let_rec> | <let> | <fun> | <index>
+// <arg> ::= <pos_int> | <bool> | <paren> | <index>
+// <value> ::= <int> | <bool>
+// <int> ::= 数値
+// <bool> ::= 'true' | 'false'
+// <paren> ::= '(' <expr> ')'
+// <if> ::= 'if' <expr> 'then' <expr> 'else' <expr>
+// <let_rec> ::= 'let' 'rec' '.' '=' 'fun' '.' '->' <expr> 'in' <expr>
+// <let> ::= 'let' '.' '=' <expr> 'in' <expr>
+// <fun> ::= 'fun' '.' '->' <expr>
+// <index> ::= '#' 数値
+// <closure> ::= '(' <env> ')' '[' 'fun' '.' '->' <expr> ']'
+// <rec_closure> ::= '(' <env> ')' '[' 'rec' '.' '=' 'fun' '.' '->' <expr> ']'
+
+pub fn parse(input: &str) -> IResult<&str, (Env, Expr)> {
+    match input.find("|-") {
+        Some(i) => {
+            let (input1, input2) = input.split_at(i);
+            let (_, env) = parse_env(input1)?;
+            let (input, expr) = parse_expr(&input2[2..])?;
+            Ok((input, (env, expr)))
+        }
+        None => {
+            let (input, expr) = parse_expr(input)?;
+            Ok((input, (Env::new(), expr)))
+        }
+    }
+}
+
+// 評価結果 (クロージャを含む)
+pub fn parse_result(input: &str) -> IResult<&str, Expr> {
+    alt((parse_closure, parse_rec_closure, parse_expr))(input)
+}
+
+fn parse_env(input: &str) -> IResult<&str, Env> {
+    let parse_value = alt((parse_closure, parse_rec_closure, parse_expr));
+    let (input, exprs) = separated_list0(ws(char(',')), parse_value)(input)?;
+    Ok((input, Env::from(exprs)))
+}
+
+pub fn parse_expr(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr1, expr2)) = tuple((parse_term1, opt(parse_lt)))(input)?;
+    let expr = match expr2 {
+        Some(expr2) => Lt(Box::new(expr1), Box::new(expr2)),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+fn parse_lt(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr)) = tuple((ws(char('<')), parse_term1))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_term1(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_term2, parse_plus_minus))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
+        '+' => Plus(Box::new(expr1), Box::new(expr2.clone())),
+        '-' => Minus(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+fn parse_plus_minus(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
+    let parse_plus = ws(char('+'));
+    let parse_minus = ws(char('-'));
+    let parse_op = alt((parse_plus, parse_minus));
+    let (input, exprs) = many0(tuple((parse_op, parse_term2)))(input)?;
+    Ok((input, exprs))
+}
+
+fn parse_term2(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_app, parse_times))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
+        '*' => Times(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+fn parse_times(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
+    let parse_times = ws(char('*'));
+    let (input, exprs) = many0(tuple((parse_times, parse_app)))(input)?;
+    Ok((input, exprs))
+}
+
+fn parse_app(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_factor, many0(parse_arg)))(input)?;
+    let expr = exprs
+        .into_iter()
+        .fold(expr, |expr1, expr2| App(Box::new(expr1), Box::new(expr2)));
+    Ok((input, expr))
+}
+
+fn parse_factor(input: &str) -> IResult<&str, Expr> {
+    let (input, expr) = alt((
+        parse_value,
+        parse_paren,
+        parse_if,
+        parse_let_rec,
+        parse_let,
+        parse_fun,
+        parse_index,
+    ))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_arg(input: &str) -> IResult<&str, Expr> {
+    let parse_pos_int = |input| {
+        let (input, i) = ws(parse_pos_number)(input)?;
+        Ok((input, Int(i)))
+    };
+    let (input, expr) = alt((parse_pos_int, parse_bool, parse_paren, parse_index))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_value(input: &str) -> IResult<&str, Expr> {
+    let (input, expr) = alt((parse_int, parse_bool))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_int(input: &str) -> IResult<&str, Expr> {
+    let (input, i) = alt((ws(parse_pos_number), ws(parse_neg_number)))(input)?;
+    let expr = Int(i);
+    Ok((input, expr))
+}
+
+fn parse_pos_number(input: &str) -> IResult<&str, i64> {
+    map_res(digit1, |i: &str| i.parse::<i64>())(input)
+}
+
+fn parse_neg_number(input: &str) -> IResult<&str, i64> {
+    map_res(recognize(tuple((char('-'), digit1))), |i: &str| {
+        i.parse::<i64>()
+    })(input)
+}
+
+fn parse_bool(input: &str) -> IResult<&str, Expr> {
+    let (input, b) = alt((parse_true, parse_false))(input)?;
+    let expr = Bool(b);
+    Ok((input, expr))
+}
+
+fn parse_true(input: &str) -> IResult<&str, bool> {
+    let (input, _) = ws(keyword("true"))(input)?;
+    Ok((input, true))
+}
+
+fn parse_false(input: &str) -> IResult<&str, bool> {
+    let (input, _) = ws(keyword("false"))(input)?;
+    Ok((input, false))
+}
+
+fn parse_paren(input: &str) -> IResult<&str, Expr> {
+    let parse_lparen = ws(char('('));
+    let parse_rparen = ws(char(')'));
+    let (input, expr) = delimited(parse_lparen, parse_expr, parse_rparen)(input)?;
+    Ok((input, expr))
+}
+
+fn parse_if(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr1, _, expr2, _, expr3)) = tuple((
+        ws(keyword("if")),
+        parse_expr,
+        ws(keyword("then")),
+        parse_expr,
+        ws(keyword("else")),
+        parse_expr,
+    ))(input)?;
+    let expr = If(Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+fn parse_let(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, _, _, expr1, _, expr2)) = tuple((
+        ws(keyword("let")),
+        ws(char('.')),
+        ws(char('=')),
+        parse_expr,
+        ws(keyword("in")),
+        parse_expr,
+    ))(input)?;
+    let expr = Let(Box::new(expr1), Box::new(expr2));
+    Ok((input, expr))
+}
+
+fn parse_let_rec(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, _, _, _, _, _, _, expr1, _, expr2)) = tuple((
+        ws(keyword("let")),
+        ws(keyword("rec")),
+        ws(char('.')),
+        ws(char('=')),
+        ws(keyword("fun")),
+        ws(char('.')),
+        ws(tag("->")),
+        parse_expr,
+        ws(keyword("in")),
+        parse_expr,
+    ))(input)?;
+    let expr = LetRec(Box::new(expr1), Box::new(expr2));
+    Ok((input, expr))
+}
+
+fn parse_fun(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, _, _, expr)) =
+        tuple((ws(keyword("fun")), ws(char('.')), ws(tag("->")), parse_expr))(input)?;
+    let expr = Fun(Box::new(expr));
+    Ok((input, expr))
+}
+
+fn parse_closure(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, env, _, _, _, _, _, expr, _)) = tuple((
+        ws(char('(')),
+        parse_env,
+        ws(char(')')),
+        ws(char('[')),
+        ws(keyword("fun")),
+        ws(char('.')),
+        ws(tag("->")),
+        parse_expr,
+        ws(char(']')),
+    ))(input)?;
+    let expr = Closure(env, Box::new(expr));
+    Ok((input, expr))
+}
+
+fn parse_rec_closure(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, env, _, _, _, _, _, _, _, _, expr, _)) = tuple((
+        ws(char('(')),
+        parse_env,
+        ws(char(')')),
+        ws(char('[')),
+        ws(keyword("rec")),
+        ws(char('.')),
+        ws(char('=')),
+        ws(keyword("fun")),
+        ws(char('.')),
+        ws(tag("->")),
+        parse_expr,
+        ws(char(']')),
+    ))(input)?;
+    let expr = RecClosure(env, Box::new(expr));
+    Ok((input, expr))
+}
+
+fn parse_index(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, n)) = tuple((ws(char('#')), parse_pos_number))(input)?;
+    let expr = Index(n as usize);
+    Ok((input, expr))
+}
+#[cfg(test)]
+mod tests {
+    use crate::eval_nameless_ml3::{
+        ast::{Env, Expr::*},
+        parser::parse,
+    };
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(
+            parse("3, true |- if #1 then #2 + 1 else 0").unwrap().1,
+            (
+                Env::from(vec![Int(3), Bool(true)]),
+                If(
+                    Box::new(Index(1)),
+                    Box::new(Plus(Box::new(Index(2)), Box::new(Int(1)))),
+                    Box::new(Int(0))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse("|- let . = fun . -> #1 * 2 in #1 3").unwrap().1,
+            (
+                Env::new(),
+                Let(
+                    Box::new(Fun(Box::new(Times(Box::new(Index(1)), Box::new(Int(2)))))),
+                    Box::new(App(Box::new(Index(1)), Box::new(Int(3))))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse3() {
+        assert_eq!(
+            parse("(1)[rec . = fun . -> #2 #1], ()[fun . -> #1] |- let rec . = fun . -> #1 in #1")
+                .unwrap()
+                .1,
+            (
+                Env::from(vec![
+                    RecClosure(
+                        Env::from(vec![Int(1)]),
+                        Box::new(App(Box::new(Index(2)), Box::new(Index(1))))
+                    ),
+                    Closure(Env::new(), Box::new(Index(1)))
+                ]),
+                LetRec(Box::new(Index(1)), Box::new(Index(1)))
+            )
+        );
+    }
+}
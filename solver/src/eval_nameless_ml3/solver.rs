@@ -0,0 +1,288 @@
+use crate::eval_error::{check_depth, checked, EvalError};
+use crate::eval_nameless_ml3::{
+    ast::{Env, Expr, Expr::*},
+    rule::{Rule, Rule::*},
+};
+
+pub fn solve(env: &Env, expr: &Expr, depth: usize) -> Result<Rule, EvalError> {
+    check_depth(depth)?;
+    let rule = match expr {
+        Int(i) => EInt(env.clone(), *i, depth),
+        Bool(b) => EBool(env.clone(), *b, depth),
+        If(expr1, expr2, expr3) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            match rule1.evaluated() {
+                Bool(true) => {
+                    let rule2 = solve(env, expr2, depth + 1)?;
+                    EIfT(
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        *expr3.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+                Bool(false) => {
+                    let rule2 = solve(env, expr3, depth + 1)?;
+                    EIfF(
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        *expr3.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            }
+        }
+        Plus(expr1, expr2) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_add(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BPlus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            EPlus(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Minus(expr1, expr2) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_sub(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BMinus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            EMinus(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Times(expr1, expr2) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_mul(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BTimes(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            ETimes(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Lt(expr1, expr2) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Bool(i1 < i2),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BLt(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            ELt(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Index(n) => {
+            if *n == 0 || *n > env.len() {
+                return Err(EvalError::Unbound(expr.to_string()));
+            }
+            EVar(env.clone(), *n, depth)
+        }
+        Let(expr1, expr2) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let mut new_env = env.clone();
+            new_env.put(rule1.evaluated());
+            let rule2 = solve(&new_env, expr2, depth + 1)?;
+            ELet(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            )
+        }
+        Fun(expr) => EFun(env.clone(), *expr.clone(), depth),
+        App(expr1, expr2) => {
+            let rule1 = solve(env, expr1, depth + 1)?;
+            let rule2 = solve(env, expr2, depth + 1)?;
+            match rule1.evaluated() {
+                Closure(env2, expr3) => {
+                    let mut new_env = env2;
+                    new_env.put(rule2.evaluated());
+                    let rule3 = solve(&new_env, &expr3, depth + 1)?;
+                    EApp(
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        Box::new(rule3),
+                        depth,
+                    )
+                }
+                RecClosure(env2, expr3) => {
+                    let mut new_env = env2;
+                    new_env.put(rule1.evaluated());
+                    new_env.put(rule2.evaluated());
+                    let rule3 = solve(&new_env, &expr3, depth + 1)?;
+                    EAppRec(
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        Box::new(rule3),
+                        depth,
+                    )
+                }
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            }
+        }
+        LetRec(expr1, expr2) => {
+            let closure = RecClosure(env.clone(), expr1.clone());
+            let mut new_env = env.clone();
+            new_env.put(closure);
+            let rule = solve(&new_env, expr2, depth + 1)?;
+            ELetRec(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+        Closure(_, _) => return Err(EvalError::Stuck(expr.to_string())),
+        RecClosure(_, _) => return Err(EvalError::Stuck(expr.to_string())),
+    };
+    Ok(rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_nameless_ml3::{
+        ast::{Env, Expr::*},
+        parser::parse,
+        rule::Rule::*,
+        solver::solve,
+    };
+
+    #[test]
+    fn test_solve1() {
+        let env = Env::from(vec![Int(3), Bool(true)]);
+        assert_eq!(solve(&env, &Index(2), 0).unwrap(), EVar(env.clone(), 2, 0));
+        assert_eq!(solve(&env, &Index(2), 0).unwrap().evaluated(), Int(3));
+    }
+
+    #[test]
+    fn test_solve2() {
+        let env = Env::new();
+        assert_eq!(
+            solve(
+                &env,
+                &Let(
+                    Box::new(Int(3)),
+                    Box::new(Times(Box::new(Index(1)), Box::new(Int(2))))
+                ),
+                0
+            )
+            .unwrap(),
+            ELet(
+                env.clone(),
+                Int(3),
+                Times(Box::new(Index(1)), Box::new(Int(2))),
+                Box::new(EInt(env.clone(), 3, 1)),
+                Box::new(ETimes(
+                    Env::from(vec![Int(3)]),
+                    Index(1),
+                    Int(2),
+                    Box::new(EVar(Env::from(vec![Int(3)]), 1, 2)),
+                    Box::new(EInt(Env::from(vec![Int(3)]), 2, 2)),
+                    Box::new(BTimes(Int(3), Int(2), Int(6), 2)),
+                    1
+                )),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve3() {
+        let (env, expr) =
+            parse("|- let rec . = fun . -> if #1 < 2 then 1 else #1 * #2 (#1 - 1) in #1 3")
+                .unwrap()
+                .1;
+        assert_eq!(solve(&env, &expr, 0).unwrap().evaluated(), Int(6));
+    }
+
+    #[test]
+    fn test_display1() {
+        let (env, expr) = parse("|- let . = fun . -> #1 + #2 in 1").unwrap().1;
+        assert_eq!(
+            solve(&env, &expr, 0).unwrap().to_string(),
+            r#"|- let . = fun . -> #1 + #2 in 1 evalto 1 by E-Let {
+    |- fun . -> #1 + #2 evalto ()[fun . -> #1 + #2] by E-Fun {};
+    ()[fun . -> #1 + #2] |- 1 evalto 1 by E-Int {}
+}"#
+        );
+    }
+
+    #[test]
+    fn test_display2() {
+        let (env, expr) = parse("3 |- (fun . -> #1 + #2) 4").unwrap().1;
+        assert_eq!(
+            solve(&env, &expr, 0).unwrap().to_string(),
+            r#"3 |- (fun . -> #1 + #2) 4 evalto 7 by E-App {
+    3 |- fun . -> #1 + #2 evalto (3)[fun . -> #1 + #2] by E-Fun {};
+    3 |- 4 evalto 4 by E-Int {};
+    3, 4 |- #1 + #2 evalto 7 by E-Plus {
+        3, 4 |- #1 evalto 4 by E-Var {};
+        3, 4 |- #2 evalto 3 by E-Var {};
+        4 plus 3 is 7 by B-Plus {}
+    }
+}"#
+        );
+    }
+
+    #[test]
+    fn test_display_lt() {
+        let (env, expr) = parse("|- 3 < 4").unwrap().1;
+        assert_eq!(
+            solve(&env, &expr, 0).unwrap().to_string(),
+            "|- 3 < 4 evalto true by E-Lt {
+    |- 3 evalto 3 by E-Int {};
+    |- 4 evalto 4 by E-Int {};
+    3 less than 4 is true by B-Lt {}
+}"
+        );
+    }
+}
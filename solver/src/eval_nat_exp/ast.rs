@@ -0,0 +1,53 @@
+use crate::nat::ast::Nat;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Expr {
+    Nat(Nat),
+    Plus(Box<Expr>, Box<Expr>),
+    Times(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    // 結合の強さ (大きいほど強い)
+    fn precedence(&self) -> usize {
+        use crate::eval_nat_exp::ast::Expr::*;
+
+        match self {
+            Plus(_, _) => 3,
+            Times(_, _) => 4,
+            Nat(_) => 6,
+        }
+    }
+
+    fn paren(&self, paren: bool) -> String {
+        if paren {
+            format!("({})", self)
+        } else {
+            self.to_string()
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_nat_exp::ast::Expr::*;
+
+        let p = self.precedence();
+        match self {
+            Nat(nat) => write!(f, "{}", nat),
+            Plus(expr1, expr2) => write!(
+                f,
+                "{} + {}",
+                expr1.paren(expr1.precedence() < p),
+                expr2.paren(expr2.precedence() <= p)
+            ),
+            Times(expr1, expr2) => write!(
+                f,
+                "{} * {}",
+                expr1.paren(expr1.precedence() < p),
+                expr2.paren(expr2.precedence() <= p)
+            ),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    eval_nat_exp::ast::{Expr, Expr::*},
+    nat::ast::Nat::{self, S, Z},
+};
+
+pub fn eval(expr: &Expr) -> Nat {
+    match expr {
+        Nat(nat) => nat.clone(),
+        Plus(expr1, expr2) => plus(&eval(expr1), &eval(expr2)),
+        Times(expr1, expr2) => times(&eval(expr1), &eval(expr2)),
+    }
+}
+
+fn plus(nat1: &Nat, nat2: &Nat) -> Nat {
+    match nat1 {
+        Z => nat2.clone(),
+        S(nat) => S(Box::new(plus(nat, nat2))),
+    }
+}
+
+fn times(nat1: &Nat, nat2: &Nat) -> Nat {
+    match nat1 {
+        Z => Z,
+        S(nat) => plus(nat2, &times(nat, nat2)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eval_nat_exp::{ast::Expr::*, evaluator::eval},
+        nat::ast::Nat::{S, Z},
+    };
+
+    #[test]
+    fn test_eval1() {
+        assert_eq!(
+            eval(&Plus(
+                Box::new(Nat(Z)),
+                Box::new(Nat(S(Box::new(S(Box::new(Z))))))
+            )),
+            S(Box::new(S(Box::new(Z))))
+        );
+    }
+
+    #[test]
+    fn test_eval2() {
+        assert_eq!(
+            eval(&Plus(
+                Box::new(Nat(S(Box::new(S(Box::new(S(Box::new(Z)))))))),
+                Box::new(Times(
+                    Box::new(Nat(S(Box::new(S(Box::new(Z)))))),
+                    Box::new(Nat(S(Box::new(Z))))
+                ))
+            )),
+            S(Box::new(S(Box::new(S(Box::new(S(Box::new(S(
+                Box::new(Z)
+            )))))))))
+        );
+    }
+
+    #[test]
+    fn test_eval3() {
+        assert_eq!(
+            eval(&Times(
+                Box::new(Plus(
+                    Box::new(Nat(S(Box::new(S(Box::new(Z)))))),
+                    Box::new(Nat(S(Box::new(S(Box::new(Z))))))
+                )),
+                Box::new(Nat(Z))
+            )),
+            Z
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{
+    eval_nat_exp::ast::{Expr, Expr::*},
+    nat::parser::parse_nat,
+    util::ws,
+};
+use nom::{
+    branch::alt,
+    character::complete::char,
+    multi::many0,
+    sequence::{delimited, tuple},
+    IResult,
+};
+
+// <expr> ::= <term> [ '+' <term> ]*
+// <term> ::= <factor> [ '*' <factor> ]*
+// <factor> ::= <nat> | <paren>
+// <nat> ::= 'Z' | 'S' '(' <nat> ')'
+// <paren> ::= '(' <expr> ')'
+
+pub fn parse(input: &str) -> IResult<&str, Expr> {
+    parse_expr(input)
+}
+
+pub fn parse_expr(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_term, parse_plus))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
+        '+' => Plus(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+fn parse_plus(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
+    let parse_plus = ws(char('+'));
+    let (input, exprs) = many0(tuple((parse_plus, parse_term)))(input)?;
+    Ok((input, exprs))
+}
+
+fn parse_term(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_factor, parse_times))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
+        '*' => Times(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+fn parse_times(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
+    let parse_times = ws(char('*'));
+    let (input, exprs) = many0(tuple((parse_times, parse_factor)))(input)?;
+    Ok((input, exprs))
+}
+
+fn parse_factor(input: &str) -> IResult<&str, Expr> {
+    let (input, expr) = alt((parse_value, parse_paren))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_value(input: &str) -> IResult<&str, Expr> {
+    let (input, nat) = parse_nat(input)?;
+    let expr = Nat(nat);
+    Ok((input, expr))
+}
+
+fn parse_paren(input: &str) -> IResult<&str, Expr> {
+    let parse_lparen = ws(char('('));
+    let parse_rparen = ws(char(')'));
+    let (input, expr) = delimited(parse_lparen, parse_expr, parse_rparen)(input)?;
+    Ok((input, expr))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eval_nat_exp::{ast::Expr::*, parser::parse},
+        nat::ast::Nat::{S, Z},
+    };
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(
+            parse("Z + S(S(Z))").unwrap().1,
+            Plus(Box::new(Nat(Z)), Box::new(Nat(S(Box::new(S(Box::new(Z)))))))
+        );
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse("S(Z) + S(Z) + S(Z)").unwrap().1,
+            Plus(
+                Box::new(Plus(
+                    Box::new(Nat(S(Box::new(Z)))),
+                    Box::new(Nat(S(Box::new(Z))))
+                )),
+                Box::new(Nat(S(Box::new(Z))))
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse3() {
+        assert_eq!(
+            parse("S(S(S(Z))) + S(S(Z)) * S(Z)").unwrap().1,
+            Plus(
+                Box::new(Nat(S(Box::new(S(Box::new(S(Box::new(Z)))))))),
+                Box::new(Times(
+                    Box::new(Nat(S(Box::new(S(Box::new(Z)))))),
+                    Box::new(Nat(S(Box::new(Z))))
+                ))
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse4() {
+        assert_eq!(
+            parse("(S(S(Z)) + S(S(Z))) * Z").unwrap().1,
+            Times(
+                Box::new(Plus(
+                    Box::new(Nat(S(Box::new(S(Box::new(Z)))))),
+                    Box::new(Nat(S(Box::new(S(Box::new(Z))))))
+                )),
+                Box::new(Nat(Z))
+            )
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    eval_nat_exp::ast::{Expr, Expr::*},
+    nat::{ast::Nat, rule::Rule as NatRule},
+    util::ident,
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    EConst(Nat, usize),
+    EPlus(Expr, Expr, Box<Rule>, Box<Rule>, Box<NatRule>, usize),
+    ETimes(Expr, Expr, Box<Rule>, Box<Rule>, Box<NatRule>, usize),
+}
+
+impl Rule {
+    pub fn evaluated(&self) -> Nat {
+        use crate::eval_nat_exp::rule::Rule::*;
+
+        match self {
+            EConst(nat, _) => nat.clone(),
+            EPlus(_, _, _, _, rule3, _) => rule3.evaluated(),
+            ETimes(_, _, _, _, rule3, _) => rule3.evaluated(),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_nat_exp::rule::Rule::*;
+
+        match self {
+            EConst(nat, depth) => {
+                write!(f, "{}{} evalto {} by E-Const {{}}", ident(*depth), nat, nat)
+            }
+            EPlus(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Plus {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ETimes(expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} evalto {} by E-Times {{",
+                    ident(*depth),
+                    expr,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{
+    eval_nat_exp::{
+        ast::{Expr, Expr::*},
+        rule::{Rule, Rule::*},
+    },
+    nat::solver::{solve_plus, solve_times},
+};
+
+pub fn solve(expr: &Expr, depth: usize) -> Rule {
+    match expr {
+        Nat(nat) => EConst(nat.clone(), depth),
+        Plus(expr1, expr2) => {
+            let rule1 = solve(expr1, depth + 1);
+            let rule2 = solve(expr2, depth + 1);
+            let rule3 = solve_plus(&rule1.evaluated(), &rule2.evaluated(), depth + 1);
+            EPlus(
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Times(expr1, expr2) => {
+            let rule1 = solve(expr1, depth + 1);
+            let rule2 = solve(expr2, depth + 1);
+            let rule3 = solve_times(&rule1.evaluated(), &rule2.evaluated(), depth + 1);
+            ETimes(
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eval_nat_exp::{ast::Expr::*, rule::Rule::*, solver::solve},
+        nat::{
+            ast::Nat::{S, Z},
+            rule::Rule::{PSucc, PZero, TZero},
+        },
+    };
+
+    #[test]
+    fn test_solve1() {
+        assert_eq!(
+            solve(
+                &Plus(Box::new(Nat(Z)), Box::new(Nat(S(Box::new(S(Box::new(Z))))))),
+                0
+            ),
+            EPlus(
+                Nat(Z),
+                Nat(S(Box::new(S(Box::new(Z))))),
+                Box::new(EConst(Z, 1)),
+                Box::new(EConst(S(Box::new(S(Box::new(Z)))), 1)),
+                Box::new(PZero(S(Box::new(S(Box::new(Z)))), 1)),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve2() {
+        assert_eq!(
+            solve(
+                &Times(
+                    Box::new(Nat(Z)),
+                    Box::new(Plus(Box::new(Nat(S(Box::new(Z)))), Box::new(Nat(Z))))
+                ),
+                0
+            ),
+            ETimes(
+                Nat(Z),
+                Plus(Box::new(Nat(S(Box::new(Z)))), Box::new(Nat(Z))),
+                Box::new(EConst(Z, 1)),
+                Box::new(EPlus(
+                    Nat(S(Box::new(Z))),
+                    Nat(Z),
+                    Box::new(EConst(S(Box::new(Z)), 2)),
+                    Box::new(EConst(Z, 2)),
+                    Box::new(PSucc(S(Box::new(Z)), Z, Box::new(PZero(Z, 3)), 2)),
+                    1
+                )),
+                Box::new(TZero(S(Box::new(Z)), 1)),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve3() {
+        assert_eq!(
+            solve(
+                &Times(
+                    Box::new(Plus(
+                        Box::new(Nat(S(Box::new(S(Box::new(Z)))))),
+                        Box::new(Nat(S(Box::new(S(Box::new(Z))))))
+                    )),
+                    Box::new(Nat(Z))
+                ),
+                0
+            )
+            .to_string(),
+            "(S(S(Z)) + S(S(Z))) * Z evalto Z by E-Times {
+    S(S(Z)) + S(S(Z)) evalto S(S(S(S(Z)))) by E-Plus {
+        S(S(Z)) evalto S(S(Z)) by E-Const {};
+        S(S(Z)) evalto S(S(Z)) by E-Const {};
+        S(S(Z)) plus S(S(Z)) is S(S(S(S(Z)))) by P-Succ {
+            S(Z) plus S(S(Z)) is S(S(S(Z))) by P-Succ {
+                Z plus S(S(Z)) is S(S(Z)) by P-Zero {}
+            }
+        }
+    };
+    Z evalto Z by E-Const {};
+    S(S(S(S(Z)))) times Z is Z by T-Succ {
+        S(S(S(Z))) times Z is Z by T-Succ {
+            S(S(Z)) times Z is Z by T-Succ {
+                S(Z) times Z is Z by T-Succ {
+                    Z times Z is Z by T-Zero {};
+                    Z plus Z is Z by P-Zero {}
+                };
+                Z plus Z is Z by P-Zero {}
+            };
+            Z plus Z is Z by P-Zero {}
+        };
+        Z plus Z is Z by P-Zero {}
+    }
+}"
+        );
+    }
+}
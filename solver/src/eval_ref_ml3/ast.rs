@@ -0,0 +1,276 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Expr {
+    Int(i64),
+    Bool(bool),
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+    Plus(Box<Expr>, Box<Expr>),
+    Minus(Box<Expr>, Box<Expr>),
+    Times(Box<Expr>, Box<Expr>),
+    Lt(Box<Expr>, Box<Expr>),
+    Let(Box<Expr>, Box<Expr>, Box<Expr>),
+    Var(String),
+    Fun(Box<Expr>, Box<Expr>),
+    App(Box<Expr>, Box<Expr>),
+    LetRec(Box<Expr>, Box<Expr>, Box<Expr>, Box<Expr>),
+    Closure(Env, Box<Expr>, Box<Expr>),
+    RecClosure(Env, Box<Expr>, Box<Expr>, Box<Expr>),
+    Ref(Box<Expr>),
+    Deref(Box<Expr>),
+    Assign(Box<Expr>, Box<Expr>),
+    Loc(String),
+}
+
+impl Expr {
+    // 結合の強さ (大きいほど強い)
+    fn precedence(&self) -> usize {
+        use crate::eval_ref_ml3::ast::Expr::*;
+
+        match self {
+            If(_, _, _) | Let(_, _, _) | Fun(_, _) | LetRec(_, _, _, _) | Assign(_, _) => 0,
+            Lt(_, _) => 1,
+            Plus(_, _) | Minus(_, _) => 3,
+            Times(_, _) => 4,
+            App(_, _) | Ref(_) => 5,
+            Int(i) if *i < 0 => 5,
+            _ => 6,
+        }
+    }
+
+    // 右にできるだけ伸びる式 (右端にあれば括弧がいらない)
+    fn is_open(&self) -> bool {
+        use crate::eval_ref_ml3::ast::Expr::*;
+
+        matches!(
+            self,
+            If(_, _, _) | Let(_, _, _) | Fun(_, _) | LetRec(_, _, _, _)
+        )
+    }
+
+    fn paren(&self, paren: bool) -> String {
+        if paren {
+            format!("({})", self)
+        } else {
+            self.to_string()
+        }
+    }
+
+    // tail は後ろに何も続かないこと
+    fn show(&self, tail: bool) -> String {
+        use crate::eval_ref_ml3::ast::Expr::*;
+
+        let p = self.precedence();
+        let left = |expr: &Expr, paren: bool| {
+            if paren {
+                expr.paren(true)
+            } else {
+                expr.show(false)
+            }
+        };
+        let right = |expr: &Expr, paren: bool| {
+            if paren && !(tail && expr.is_open()) {
+                expr.paren(true)
+            } else {
+                expr.show(tail)
+            }
+        };
+        match self {
+            Int(i) => i.to_string(),
+            Bool(b) => b.to_string(),
+            If(expr1, expr2, expr3) => {
+                format!("if {} then {} else {}", expr1, expr2, expr3.show(tail))
+            }
+            Plus(expr1, expr2) => format!(
+                "{} + {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Minus(expr1, expr2) => format!(
+                "{} - {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Times(expr1, expr2) => format!(
+                "{} * {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Lt(expr1, expr2) => format!(
+                "{} < {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Let(expr1, expr2, expr3) => {
+                format!("let {} = {} in {}", expr1, expr2, expr3.show(tail))
+            }
+            Var(s) => s.to_string(),
+            Fun(expr1, expr2) => format!("fun {} -> {}", expr1, expr2.show(tail)),
+            App(expr1, expr2) => format!(
+                "{} {}",
+                left(expr1, expr1.precedence() < p),
+                expr2.paren(expr2.precedence() <= p)
+            ),
+            LetRec(expr1, expr2, expr3, expr4) => format!(
+                "let rec {} = fun {} -> {} in {}",
+                expr1,
+                expr2,
+                expr3,
+                expr4.show(tail)
+            ),
+            Closure(env, expr1, expr2) => format!("({})[fun {} -> {}]", env, expr1, expr2),
+            RecClosure(env, expr1, expr2, expr3) => {
+                format!("({})[rec {} = fun {} -> {}]", env, expr1, expr2, expr3)
+            }
+            Ref(expr) => format!("ref {}", expr.paren(expr.precedence() <= p)),
+            Deref(expr) => format!("!{}", expr.paren(expr.precedence() < p)),
+            Assign(expr1, expr2) => format!(
+                "{} := {}",
+                left(expr1, expr1.precedence() <= p),
+                expr2.show(tail)
+            ),
+            Loc(s) => format!("@{}", s),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.show(true))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Env(Vec<(Expr, Expr)>);
+
+impl Env {
+    pub fn new() -> Env {
+        Env(vec![])
+    }
+
+    pub fn from(pairs: Vec<(Expr, Expr)>) -> Env {
+        Env(pairs)
+    }
+
+    pub fn put(&mut self, expr1: Expr, expr2: Expr) {
+        self.0.push((expr1, expr2))
+    }
+
+    pub fn get(&self, expr: &Expr) -> Expr {
+        self.0
+            .iter()
+            .rev()
+            .find(|(expr1, _)| expr1 == expr)
+            .unwrap()
+            .1
+            .clone()
+    }
+
+    pub fn contains(&self, expr: &Expr) -> bool {
+        self.0.iter().any(|(expr1, _)| expr1 == expr)
+    }
+}
+
+impl fmt::Display for Env {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs = self
+            .0
+            .iter()
+            .map(|(expr1, expr2)| format!("{} = {}", expr1, expr2))
+            .collect::<Vec<_>>();
+        write!(f, "{}", pairs.join(", "))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Store(Vec<(Expr, Expr)>);
+
+impl Store {
+    pub fn new() -> Store {
+        Store(vec![])
+    }
+
+    pub fn from(pairs: Vec<(Expr, Expr)>) -> Store {
+        Store(pairs)
+    }
+
+    // 既にある場所は値を書き換える
+    pub fn put(&mut self, expr1: Expr, expr2: Expr) {
+        match self.0.iter_mut().find(|(expr, _)| *expr == expr1) {
+            Some(pair) => pair.1 = expr2,
+            None => self.0.push((expr1, expr2)),
+        }
+    }
+
+    pub fn get(&self, expr: &Expr) -> Expr {
+        self.0
+            .iter()
+            .find(|(expr1, _)| expr1 == expr)
+            .unwrap()
+            .1
+            .clone()
+    }
+
+    pub fn contains(&self, expr: &Expr) -> bool {
+        self.0.iter().any(|(expr1, _)| expr1 == expr)
+    }
+
+    // @l1, @l2, ... のうち使われていない最初の場所
+    pub fn fresh(&self) -> Expr {
+        (1..)
+            .map(|n| Expr::Loc(format!("l{}", n)))
+            .find(|expr| self.0.iter().all(|(expr1, _)| expr1 != expr))
+            .unwrap()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for Store {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs = self
+            .0
+            .iter()
+            .map(|(expr1, expr2)| format!("{} = {}", expr1, expr2))
+            .collect::<Vec<_>>();
+        write!(f, "{}", pairs.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_ref_ml3::ast::{Expr::*, Store};
+
+    #[test]
+    fn test_store() {
+        let mut store = Store::new();
+        assert_eq!(Loc("l1".to_string()), store.fresh());
+        store.put(Loc("l1".to_string()), Int(1));
+        store.put(Loc("l3".to_string()), Bool(true));
+        assert_eq!(Loc("l2".to_string()), store.fresh());
+        store.put(Loc("l1".to_string()), Int(2));
+        assert_eq!("@l1 = 2, @l3 = true", store.to_string());
+        assert_eq!(Int(2), store.get(&Loc("l1".to_string())));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "x := !x + 1",
+            Assign(
+                Box::new(Var("x".to_string())),
+                Box::new(Plus(
+                    Box::new(Deref(Box::new(Var("x".to_string())))),
+                    Box::new(Int(1))
+                ))
+            )
+            .to_string()
+        );
+        assert_eq!(
+            "ref (1 + 2)",
+            Ref(Box::new(Plus(Box::new(Int(1)), Box::new(Int(2))))).to_string()
+        );
+    }
+}
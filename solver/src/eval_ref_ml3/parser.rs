@@ -0,0 +1,464 @@
+use crate::{
+    eval_ref_ml3::ast::{Env, Expr, Expr::*, Store},
+    syntax::lexer::{keyword, name},
+    util::ws,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, char, digit1},
+    combinator::{map_res, opt, recognize, verify},
+    multi::{many0, separated_list0},
+    sequence::{delimited, tuple},
+    IResult,
+};
+
+// <judgment> ::= [ <store> '/' ] [ <env> ] '|-' <expr>
+// <store> ::= <loc> '=' <value> [ ',' <loc> '=' <value> ]*
+// <expr> ::= <compare> [ ':=' <expr> ]
+// <compare> ::= <term1> [ '<' <term1> ]
+// <term1> ::= <term2> [ ( '+' | '-' ) <term2> ]*
+// <term2> ::= <app> [ '*' <app> ]*
+// <app> ::= <factor> [ <arg> ]*
+// <factor> ::= <value> | <paren> | <if> | <let_rec> | <let> | <fun> | <ref> | <deref> | <loc> | <var>
+// <arg> ::= <pos_int> | <bool> | <paren> | <deref> | <loc> | <var>
+// <value> ::= <int> | <bool>
+// <int> ::= 数値
+// <bool> ::= 'true' | 'false'
+// <paren> ::= '(' <expr> ')'
+// <if> ::= 'if' <expr> 'then' <expr> 'else' <expr>
+// <let_rec> ::= 'let' 'rec' <var> '=' 'fun' <var> '->' <expr> 'in' <expr>
+// <let> ::= 'let' <var> '=' <expr> 'in' <expr>
+// <fun> ::= 'fun' <var> '->' <expr>
+// <ref> ::= 'ref' <arg>
+// <deref> ::= '!' <arg>
+// <loc> ::= '@' 文字列
+// <var> ::= 英字か '_' で始まる英数字と '_', '\'' の列 (予約語を除く)
+// <closure> ::= '(' <env> ')' '[' 'fun' <var> '->' <expr> ']'
+// <rec_closure> ::= '(' <env> ')' '[' 'rec' <var> '=' 'fun' <var> '->' <expr> ']'
+
+const KEYWORDS: [&str; 10] = [
+    "if", "then", "else", "let", "rec", "in", "fun", "ref", "true", "false",
+];
+
+pub fn parse(input: &str) -> IResult<&str, (Store, Env, Expr)> {
+    match input.find("|-") {
+        Some(i) => {
+            let (input1, input2) = input.split_at(i);
+            let (store, env) = match input1.find('/') {
+                Some(j) => {
+                    let (_, store) = parse_store(&input1[..j])?;
+                    let (_, env) = parse_env(&input1[j + 1..])?;
+                    (store, env)
+                }
+                None => {
+                    let (_, env) = parse_env(input1)?;
+                    (Store::new(), env)
+                }
+            };
+            let (input, expr) = parse_expr(&input2[2..])?;
+            Ok((input, (store, env, expr)))
+        }
+        None => {
+            let (input, expr) = parse_expr(input)?;
+            Ok((input, (Store::new(), Env::new(), expr)))
+        }
+    }
+}
+
+// 評価結果とストア (ストアが空なら '/' 以降は省く)
+pub fn parse_result(input: &str) -> IResult<&str, (Expr, Store)> {
+    let parse_value = alt((parse_closure, parse_rec_closure, parse_expr));
+    let parse_slash_store = tuple((ws(char('/')), parse_store));
+    let (input, (expr, store)) = tuple((parse_value, opt(parse_slash_store)))(input)?;
+    let store = store.map(|(_, store)| store).unwrap_or_default();
+    Ok((input, (expr, store)))
+}
+
+fn parse_store(input: &str) -> IResult<&str, Store> {
+    let parse_value = alt((parse_closure, parse_rec_closure, parse_expr));
+    let parse_pair = tuple((parse_loc, ws(char('=')), parse_value));
+    let (input, pairs) = separated_list0(ws(char(',')), parse_pair)(input)?;
+    let pairs = pairs
+        .into_iter()
+        .map(|(expr1, _, expr2)| (expr1, expr2))
+        .collect();
+    Ok((input, Store::from(pairs)))
+}
+
+fn parse_env(input: &str) -> IResult<&str, Env> {
+    let mut env = Env::new();
+    let (input, pair) = opt(parse_pair)(input)?;
+    match pair {
+        Some((expr1, expr2)) => env.put(expr1, expr2),
+        None => return Ok((input, env)),
+    };
+    let (input, pairs) = opt(parse_pairs)(input)?;
+    if let Some(pairs) = pairs {
+        pairs
+            .into_iter()
+            .for_each(|(expr1, expr2)| env.put(expr1, expr2));
+    }
+    Ok((input, env))
+}
+
+fn parse_pair(input: &str) -> IResult<&str, (Expr, Expr)> {
+    let parse_value = alt((parse_closure, parse_rec_closure, parse_expr));
+    let (input, (expr1, _, expr2)) = tuple((parse_var, ws(char('=')), parse_value))(input)?;
+    let pair = (expr1, expr2);
+    Ok((input, pair))
+}
+
+fn parse_pairs(input: &str) -> IResult<&str, Vec<(Expr, Expr)>> {
+    let (input, pairs) = many0(tuple((ws(char(',')), parse_pair)))(input)?;
+    let pairs = pairs.into_iter().map(|(_, pair)| pair).collect();
+    Ok((input, pairs))
+}
+
+pub fn parse_expr(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr1, expr2)) = tuple((parse_compare, opt(parse_assign)))(input)?;
+    let expr = match expr2 {
+        Some(expr2) => Assign(Box::new(expr1), Box::new(expr2)),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+fn parse_assign(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr)) = tuple((ws(tag(":=")), parse_expr))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_compare(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr1, expr2)) = tuple((parse_term1, opt(parse_lt)))(input)?;
+    let expr = match expr2 {
+        Some(expr2) => Lt(Box::new(expr1), Box::new(expr2)),
+        None => expr1,
+    };
+    Ok((input, expr))
+}
+
+fn parse_lt(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr)) = tuple((ws(char('<')), parse_term1))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_term1(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_term2, parse_plus_minus))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
+        '+' => Plus(Box::new(expr1), Box::new(expr2.clone())),
+        '-' => Minus(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+fn parse_plus_minus(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
+    let parse_plus = ws(char('+'));
+    let parse_minus = ws(char('-'));
+    let parse_op = alt((parse_plus, parse_minus));
+    let (input, exprs) = many0(tuple((parse_op, parse_term2)))(input)?;
+    Ok((input, exprs))
+}
+
+fn parse_term2(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_app, parse_times))(input)?;
+    let expr = exprs.iter().fold(expr, |expr1, (op, expr2)| match op {
+        '*' => Times(Box::new(expr1), Box::new(expr2.clone())),
+        _ => unreachable!(),
+    });
+    Ok((input, expr))
+}
+
+fn parse_times(input: &str) -> IResult<&str, Vec<(char, Expr)>> {
+    let parse_times = ws(char('*'));
+    let (input, exprs) = many0(tuple((parse_times, parse_app)))(input)?;
+    Ok((input, exprs))
+}
+
+fn parse_app(input: &str) -> IResult<&str, Expr> {
+    let (input, (expr, exprs)) = tuple((parse_factor, many0(parse_arg)))(input)?;
+    let expr = exprs
+        .into_iter()
+        .fold(expr, |expr1, expr2| App(Box::new(expr1), Box::new(expr2)));
+    Ok((input, expr))
+}
+
+fn parse_factor(input: &str) -> IResult<&str, Expr> {
+    let (input, expr) = alt((
+        parse_value,
+        parse_paren,
+        parse_if,
+        parse_let_rec,
+        parse_let,
+        parse_fun,
+        parse_ref,
+        parse_deref,
+        parse_loc,
+        parse_var,
+    ))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_arg(input: &str) -> IResult<&str, Expr> {
+    let parse_pos_int = |input| {
+        let (input, i) = ws(parse_pos_number)(input)?;
+        Ok((input, Int(i)))
+    };
+    let (input, expr) = alt((
+        parse_pos_int,
+        parse_bool,
+        parse_paren,
+        parse_deref,
+        parse_loc,
+        parse_var,
+    ))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_value(input: &str) -> IResult<&str, Expr> {
+    let (input, expr) = alt((parse_int, parse_bool))(input)?;
+    Ok((input, expr))
+}
+
+fn parse_int(input: &str) -> IResult<&str, Expr> {
+    let (input, i) = alt((ws(parse_pos_number), ws(parse_neg_number)))(input)?;
+    let expr = Int(i);
+    Ok((input, expr))
+}
+
+fn parse_pos_number(input: &str) -> IResult<&str, i64> {
+    map_res(digit1, |i: &str| i.parse::<i64>())(input)
+}
+
+fn parse_neg_number(input: &str) -> IResult<&str, i64> {
+    map_res(recognize(tuple((char('-'), digit1))), |i: &str| {
+        i.parse::<i64>()
+    })(input)
+}
+
+fn parse_bool(input: &str) -> IResult<&str, Expr> {
+    let (input, b) = alt((parse_true, parse_false))(input)?;
+    let expr = Bool(b);
+    Ok((input, expr))
+}
+
+fn parse_true(input: &str) -> IResult<&str, bool> {
+    let (input, _) = ws(keyword("true"))(input)?;
+    Ok((input, true))
+}
+
+fn parse_false(input: &str) -> IResult<&str, bool> {
+    let (input, _) = ws(keyword("false"))(input)?;
+    Ok((input, false))
+}
+
+fn parse_paren(input: &str) -> IResult<&str, Expr> {
+    let parse_lparen = ws(char('('));
+    let parse_rparen = ws(char(')'));
+    let (input, expr) = delimited(parse_lparen, parse_expr, parse_rparen)(input)?;
+    Ok((input, expr))
+}
+
+fn parse_if(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr1, _, expr2, _, expr3)) = tuple((
+        ws(keyword("if")),
+        parse_expr,
+        ws(keyword("then")),
+        parse_expr,
+        ws(keyword("else")),
+        parse_expr,
+    ))(input)?;
+    let expr = If(Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+fn parse_let(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr1, _, expr2, _, expr3)) = tuple((
+        ws(keyword("let")),
+        parse_var,
+        ws(char('=')),
+        parse_expr,
+        ws(keyword("in")),
+        parse_expr,
+    ))(input)?;
+    let expr = Let(Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+fn parse_let_rec(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, _, expr1, _, _, expr2, _, expr3, _, expr4)) = tuple((
+        ws(keyword("let")),
+        ws(keyword("rec")),
+        parse_var,
+        ws(char('=')),
+        ws(keyword("fun")),
+        parse_var,
+        ws(tag("->")),
+        parse_expr,
+        ws(keyword("in")),
+        parse_expr,
+    ))(input)?;
+    let expr = LetRec(
+        Box::new(expr1),
+        Box::new(expr2),
+        Box::new(expr3),
+        Box::new(expr4),
+    );
+    Ok((input, expr))
+}
+
+fn parse_fun(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr1, _, expr2)) =
+        tuple((ws(keyword("fun")), parse_var, ws(tag("->")), parse_expr))(input)?;
+    let expr = Fun(Box::new(expr1), Box::new(expr2));
+    Ok((input, expr))
+}
+
+fn parse_ref(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr)) = tuple((ws(keyword("ref")), parse_arg))(input)?;
+    let expr = Ref(Box::new(expr));
+    Ok((input, expr))
+}
+
+fn parse_deref(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, expr)) = tuple((ws(char('!')), parse_arg))(input)?;
+    let expr = Deref(Box::new(expr));
+    Ok((input, expr))
+}
+
+fn parse_loc(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, s)) = tuple((ws(char('@')), alphanumeric1))(input)?;
+    let expr = Loc(s.to_string());
+    Ok((input, expr))
+}
+
+fn parse_closure(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, env, _, _, _, expr1, _, expr2, _)) = tuple((
+        ws(char('(')),
+        parse_env,
+        ws(char(')')),
+        ws(char('[')),
+        ws(keyword("fun")),
+        parse_var,
+        ws(tag("->")),
+        parse_expr,
+        ws(char(']')),
+    ))(input)?;
+    let expr = Closure(env, Box::new(expr1), Box::new(expr2));
+    Ok((input, expr))
+}
+
+fn parse_rec_closure(input: &str) -> IResult<&str, Expr> {
+    let (input, (_, env, _, _, _, expr1, _, _, expr2, _, expr3, _)) = tuple((
+        ws(char('(')),
+        parse_env,
+        ws(char(')')),
+        ws(char('[')),
+        ws(keyword("rec")),
+        parse_var,
+        ws(char('=')),
+        ws(keyword("fun")),
+        parse_var,
+        ws(tag("->")),
+        parse_expr,
+        ws(char(']')),
+    ))(input)?;
+    let expr = RecClosure(env, Box::new(expr1), Box::new(expr2), Box::new(expr3));
+    Ok((input, expr))
+}
+
+pub fn parse_var(input: &str) -> IResult<&str, Expr> {
+    let (input, s) = ws(parse_ident)(input)?;
+    let expr = Var(s.to_string());
+    Ok((input, expr))
+}
+
+fn parse_ident(input: &str) -> IResult<&str, &str> {
+    verify(name, |s: &str| !KEYWORDS.contains(&s))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_ref_ml3::{
+        ast::{Env, Expr::*, Store},
+        parser::{parse, parse_expr, parse_var},
+    };
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(
+            parse("|- let r = ref 2 in !r").unwrap().1,
+            (
+                Store::new(),
+                Env::new(),
+                Let(
+                    Box::new(Var("r".to_string())),
+                    Box::new(Ref(Box::new(Int(2)))),
+                    Box::new(Deref(Box::new(Var("r".to_string()))))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse("@l1 = 2 / x = @l1 |- x := !x + 1").unwrap().1,
+            (
+                Store::from(vec![(Loc("l1".to_string()), Int(2))]),
+                Env::from(vec![(Var("x".to_string()), Loc("l1".to_string()))]),
+                Assign(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Plus(
+                        Box::new(Deref(Box::new(Var("x".to_string())))),
+                        Box::new(Int(1))
+                    ))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse3() {
+        assert_eq!(
+            parse("@l1 = 1, @l2 = ()[fun x -> x] / |- f !r (ref 3)")
+                .unwrap()
+                .1,
+            (
+                Store::from(vec![
+                    (Loc("l1".to_string()), Int(1)),
+                    (
+                        Loc("l2".to_string()),
+                        Closure(
+                            Env::new(),
+                            Box::new(Var("x".to_string())),
+                            Box::new(Var("x".to_string()))
+                        )
+                    ),
+                ]),
+                Env::new(),
+                App(
+                    Box::new(App(
+                        Box::new(Var("f".to_string())),
+                        Box::new(Deref(Box::new(Var("r".to_string()))))
+                    )),
+                    Box::new(Ref(Box::new(Int(3))))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_ident() {
+        assert_eq!(
+            parse_expr("f truex").unwrap().1,
+            App(
+                Box::new(Var("f".to_string())),
+                Box::new(Var("truex".to_string()))
+            )
+        );
+        assert_eq!(parse_var("_x1").unwrap().1, Var("_x1".to_string()));
+        assert!(parse_var("123").is_err());
+    }
+}
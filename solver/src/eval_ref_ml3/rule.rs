@@ -0,0 +1,466 @@
+use crate::{
+    eval_ref_ml3::ast::{Env, Expr, Expr::*, Store},
+    util::{env_prefix, ident},
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    EInt(Store, Env, i64, usize),
+    EBool(Store, Env, bool, usize),
+    EIfT(Store, Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EIfF(Store, Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EPlus(
+        Store,
+        Env,
+        Expr,
+        Expr,
+        Box<Rule>,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+    EMinus(
+        Store,
+        Env,
+        Expr,
+        Expr,
+        Box<Rule>,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+    ETimes(
+        Store,
+        Env,
+        Expr,
+        Expr,
+        Box<Rule>,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+    ELt(
+        Store,
+        Env,
+        Expr,
+        Expr,
+        Box<Rule>,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+    EVar(Store, Env, Expr, usize),
+    ELet(Store, Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    EFun(Store, Env, Expr, Expr, usize),
+    EApp(
+        Store,
+        Env,
+        Expr,
+        Expr,
+        Box<Rule>,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+    ELetRec(Store, Env, Expr, Expr, Expr, Expr, Box<Rule>, usize),
+    EAppRec(
+        Store,
+        Env,
+        Expr,
+        Expr,
+        Box<Rule>,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+    ERef(Store, Env, Expr, Expr, Box<Rule>, usize),
+    EDeref(Store, Env, Expr, Box<Rule>, usize),
+    EAssign(Store, Env, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    BPlus(Expr, Expr, Expr, usize),
+    BMinus(Expr, Expr, Expr, usize),
+    BTimes(Expr, Expr, Expr, usize),
+    BLt(Expr, Expr, Expr, usize),
+}
+
+impl Rule {
+    pub fn evaluated(&self) -> Expr {
+        use crate::eval_ref_ml3::rule::Rule::*;
+
+        match self {
+            EInt(_, _, i, _) => Int(*i),
+            EBool(_, _, b, _) => Bool(*b),
+            EIfT(_, _, _, _, _, _, rule2, _) => rule2.evaluated(),
+            EIfF(_, _, _, _, _, _, rule2, _) => rule2.evaluated(),
+            EPlus(_, _, _, _, _, _, rule3, _) => rule3.evaluated(),
+            EMinus(_, _, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ETimes(_, _, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ELt(_, _, _, _, _, _, rule3, _) => rule3.evaluated(),
+            EVar(_, env, expr, _) => env.get(expr),
+            ELet(_, _, _, _, _, _, rule2, _) => rule2.evaluated(),
+            EFun(_, env, expr1, expr2, _) => Closure(
+                env.clone(),
+                Box::new(expr1.clone()),
+                Box::new(expr2.clone()),
+            ),
+            EApp(_, _, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ELetRec(_, _, _, _, _, _, rule, _) => rule.evaluated(),
+            EAppRec(_, _, _, _, _, _, rule3, _) => rule3.evaluated(),
+            ERef(_, _, _, expr2, _, _) => expr2.clone(),
+            EDeref(_, _, _, rule, _) => rule.stored().get(&rule.evaluated()),
+            EAssign(_, _, _, _, _, rule2, _) => rule2.evaluated(),
+            BPlus(_, _, expr3, _) => expr3.clone(),
+            BMinus(_, _, expr3, _) => expr3.clone(),
+            BTimes(_, _, expr3, _) => expr3.clone(),
+            BLt(_, _, expr3, _) => expr3.clone(),
+        }
+    }
+
+    // 評価後のストア
+    pub fn stored(&self) -> Store {
+        use crate::eval_ref_ml3::rule::Rule::*;
+
+        match self {
+            EInt(store, _, _, _) => store.clone(),
+            EBool(store, _, _, _) => store.clone(),
+            EIfT(_, _, _, _, _, _, rule2, _) => rule2.stored(),
+            EIfF(_, _, _, _, _, _, rule2, _) => rule2.stored(),
+            EPlus(_, _, _, _, _, rule2, _, _) => rule2.stored(),
+            EMinus(_, _, _, _, _, rule2, _, _) => rule2.stored(),
+            ETimes(_, _, _, _, _, rule2, _, _) => rule2.stored(),
+            ELt(_, _, _, _, _, rule2, _, _) => rule2.stored(),
+            EVar(store, _, _, _) => store.clone(),
+            ELet(_, _, _, _, _, _, rule2, _) => rule2.stored(),
+            EFun(store, _, _, _, _) => store.clone(),
+            EApp(_, _, _, _, _, _, rule3, _) => rule3.stored(),
+            ELetRec(_, _, _, _, _, _, rule, _) => rule.stored(),
+            EAppRec(_, _, _, _, _, _, rule3, _) => rule3.stored(),
+            ERef(_, _, _, expr2, rule, _) => {
+                let mut store = rule.stored();
+                store.put(expr2.clone(), rule.evaluated());
+                store
+            }
+            EDeref(_, _, _, rule, _) => rule.stored(),
+            EAssign(_, _, _, _, rule1, rule2, _) => {
+                let mut store = rule2.stored();
+                store.put(rule1.evaluated(), rule2.evaluated());
+                store
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::eval_ref_ml3::rule::Rule::*;
+
+        match self {
+            EInt(store, env, i, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} evalto {}{} by E-Int {{}}",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    i,
+                    self.evaluated(),
+                    result(&self.stored())
+                )
+            }
+            EBool(store, env, b, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} evalto {}{} by E-Bool {{}}",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    b,
+                    self.evaluated(),
+                    result(&self.stored())
+                )
+            }
+            EIfT(store, env, expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- if {} then {} else {} evalto {}{} by E-IfT {{",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated(),
+                    result(&self.stored())
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EIfF(store, env, expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- if {} then {} else {} evalto {}{} by E-IfF {{",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated(),
+                    result(&self.stored())
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EPlus(store, env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {}{} by E-Plus {{",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr,
+                    self.evaluated(),
+                    result(&self.stored())
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EMinus(store, env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {}{} by E-Minus {{",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr,
+                    self.evaluated(),
+                    result(&self.stored())
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ETimes(store, env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {}{} by E-Times {{",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr,
+                    self.evaluated(),
+                    result(&self.stored())
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELt(store, env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {}{} by E-Lt {{",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr,
+                    self.evaluated(),
+                    result(&self.stored())
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EVar(store, env, expr, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} evalto {}{} by E-Var {{}}",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr,
+                    self.evaluated(),
+                    result(&self.stored())
+                )
+            }
+            ELet(store, env, expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- let {} = {} in {} evalto {}{} by E-Let {{",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr1,
+                    expr2,
+                    expr3,
+                    self.evaluated(),
+                    result(&self.stored())
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EFun(store, env, expr1, expr2, depth) => {
+                write!(
+                    f,
+                    "{}{}|- fun {} -> {} evalto {}{} by E-Fun {{}}",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr1,
+                    expr2,
+                    self.evaluated(),
+                    result(&self.stored())
+                )
+            }
+            EApp(store, env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = App(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {}{} by E-App {{",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr,
+                    self.evaluated(),
+                    result(&self.stored())
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ELetRec(store, env, expr1, expr2, expr3, expr4, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- let rec {} = fun {} -> {} in {} evalto {}{} by E-LetRec {{",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr1,
+                    expr2,
+                    expr3,
+                    expr4,
+                    self.evaluated(),
+                    result(&self.stored())
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EAppRec(store, env, expr1, expr2, rule1, rule2, rule3, depth) => {
+                let expr = App(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {}{} by E-AppRec {{",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr,
+                    self.evaluated(),
+                    result(&self.stored())
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            ERef(store, env, expr1, _, rule, depth) => {
+                let expr = Ref(Box::new(expr1.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {}{} by E-Ref {{",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr,
+                    self.evaluated(),
+                    result(&self.stored())
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EDeref(store, env, expr, rule, depth) => {
+                let expr = Deref(Box::new(expr.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {}{} by E-Deref {{",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr,
+                    self.evaluated(),
+                    result(&self.stored())
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            EAssign(store, env, expr1, expr2, rule1, rule2, depth) => {
+                let expr = Assign(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} evalto {}{} by E-Assign {{",
+                    ident(*depth),
+                    env_prefix(&context(store, env)),
+                    expr,
+                    self.evaluated(),
+                    result(&self.stored())
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            BPlus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} plus {} is {} by B-Plus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BMinus(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} minus {} is {} by B-Minus {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BTimes(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} times {} is {} by B-Times {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+            BLt(expr1, expr2, expr3, depth) => {
+                write!(
+                    f,
+                    "{}{} less than {} is {} by B-Lt {{}}",
+                    ident(*depth),
+                    expr1,
+                    expr2,
+                    expr3
+                )
+            }
+        }
+    }
+}
+
+// 空のストアは書かない
+fn context(store: &Store, env: &Env) -> String {
+    match (store.is_empty(), env.to_string()) {
+        (true, env) => env,
+        (false, env) if env.is_empty() => format!("{} /", store),
+        (false, env) => format!("{} / {}", store, env),
+    }
+}
+
+fn result(store: &Store) -> String {
+    if store.is_empty() {
+        String::new()
+    } else {
+        format!(" / {}", store)
+    }
+}
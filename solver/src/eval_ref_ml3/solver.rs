@@ -0,0 +1,324 @@
+use crate::eval_error::{check_depth, checked, EvalError};
+use crate::eval_ref_ml3::{
+    ast::{Env, Expr, Expr::*, Store},
+    rule::{Rule, Rule::*},
+};
+
+pub fn solve(store: &Store, env: &Env, expr: &Expr, depth: usize) -> Result<Rule, EvalError> {
+    check_depth(depth)?;
+    let rule = match expr {
+        Int(i) => EInt(store.clone(), env.clone(), *i, depth),
+        Bool(b) => EBool(store.clone(), env.clone(), *b, depth),
+        If(expr1, expr2, expr3) => {
+            let rule1 = solve(store, env, expr1, depth + 1)?;
+            match rule1.evaluated() {
+                Bool(true) => {
+                    let rule2 = solve(&rule1.stored(), env, expr2, depth + 1)?;
+                    EIfT(
+                        store.clone(),
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        *expr3.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+                Bool(false) => {
+                    let rule2 = solve(&rule1.stored(), env, expr3, depth + 1)?;
+                    EIfF(
+                        store.clone(),
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        *expr3.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        depth,
+                    )
+                }
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            }
+        }
+        Plus(expr1, expr2) => {
+            let rule1 = solve(store, env, expr1, depth + 1)?;
+            let rule2 = solve(&rule1.stored(), env, expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_add(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BPlus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            EPlus(
+                store.clone(),
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Minus(expr1, expr2) => {
+            let rule1 = solve(store, env, expr1, depth + 1)?;
+            let rule2 = solve(&rule1.stored(), env, expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_sub(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BMinus(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            EMinus(
+                store.clone(),
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Times(expr1, expr2) => {
+            let rule1 = solve(store, env, expr1, depth + 1)?;
+            let rule2 = solve(&rule1.stored(), env, expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Int(checked(expr, i1.checked_mul(i2))?),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BTimes(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            ETimes(
+                store.clone(),
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Lt(expr1, expr2) => {
+            let rule1 = solve(store, env, expr1, depth + 1)?;
+            let rule2 = solve(&rule1.stored(), env, expr2, depth + 1)?;
+            let expr3 = match (rule1.evaluated(), rule2.evaluated()) {
+                (Int(i1), Int(i2)) => Bool(i1 < i2),
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            };
+            let rule3 = BLt(rule1.evaluated(), rule2.evaluated(), expr3, depth + 1);
+            ELt(
+                store.clone(),
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Var(_) => {
+            if !env.contains(expr) {
+                return Err(EvalError::Unbound(expr.to_string()));
+            }
+            EVar(store.clone(), env.clone(), expr.clone(), depth)
+        }
+        Let(expr1, expr2, expr3) => {
+            let rule1 = solve(store, env, expr2, depth + 1)?;
+            let mut new_env = env.clone();
+            new_env.put(*expr1.clone(), rule1.evaluated());
+            let rule2 = solve(&rule1.stored(), &new_env, expr3, depth + 1)?;
+            ELet(
+                store.clone(),
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            )
+        }
+        Fun(expr1, expr2) => EFun(
+            store.clone(),
+            env.clone(),
+            *expr1.clone(),
+            *expr2.clone(),
+            depth,
+        ),
+        App(expr1, expr2) => {
+            let rule1 = solve(store, env, expr1, depth + 1)?;
+            let rule2 = solve(&rule1.stored(), env, expr2, depth + 1)?;
+            match rule1.evaluated() {
+                Closure(env2, expr3, expr4) => {
+                    let mut new_env = env2;
+                    new_env.put(*expr3, rule2.evaluated());
+                    let rule3 = solve(&rule2.stored(), &new_env, &expr4, depth + 1)?;
+                    EApp(
+                        store.clone(),
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        Box::new(rule3),
+                        depth,
+                    )
+                }
+                RecClosure(env2, expr3, expr4, expr5) => {
+                    let mut new_env = env2;
+                    new_env.put(*expr3, rule1.evaluated());
+                    new_env.put(*expr4, rule2.evaluated());
+                    let rule3 = solve(&rule2.stored(), &new_env, &expr5, depth + 1)?;
+                    EAppRec(
+                        store.clone(),
+                        env.clone(),
+                        *expr1.clone(),
+                        *expr2.clone(),
+                        Box::new(rule1),
+                        Box::new(rule2),
+                        Box::new(rule3),
+                        depth,
+                    )
+                }
+                _ => return Err(EvalError::Stuck(expr.to_string())),
+            }
+        }
+        LetRec(expr1, expr2, expr3, expr4) => {
+            let closure = RecClosure(env.clone(), expr1.clone(), expr2.clone(), expr3.clone());
+            let mut new_env = env.clone();
+            new_env.put(*expr1.clone(), closure);
+            let rule = solve(store, &new_env, expr4, depth + 1)?;
+            ELetRec(
+                store.clone(),
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                *expr4.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+        Ref(expr1) => {
+            let rule = solve(store, env, expr1, depth + 1)?;
+            let loc = rule.stored().fresh();
+            ERef(
+                store.clone(),
+                env.clone(),
+                *expr1.clone(),
+                loc,
+                Box::new(rule),
+                depth,
+            )
+        }
+        Deref(expr1) => {
+            let rule = solve(store, env, expr1, depth + 1)?;
+            if !rule.stored().contains(&rule.evaluated()) {
+                return Err(EvalError::Stuck(expr.to_string()));
+            }
+            EDeref(
+                store.clone(),
+                env.clone(),
+                *expr1.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+        Assign(expr1, expr2) => {
+            let rule1 = solve(store, env, expr1, depth + 1)?;
+            let rule2 = solve(&rule1.stored(), env, expr2, depth + 1)?;
+            if !rule2.stored().contains(&rule1.evaluated()) {
+                return Err(EvalError::Stuck(expr.to_string()));
+            }
+            EAssign(
+                store.clone(),
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            )
+        }
+        Closure(_, _, _) => return Err(EvalError::Stuck(expr.to_string())),
+        RecClosure(_, _, _, _) => return Err(EvalError::Stuck(expr.to_string())),
+        Loc(_) => return Err(EvalError::Stuck(expr.to_string())),
+    };
+    Ok(rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval_ref_ml3::{
+        ast::{Env, Expr::*, Store},
+        parser::parse,
+        rule::Rule::*,
+        solver::solve,
+    };
+
+    #[test]
+    fn test_solve1() {
+        let store = Store::from(vec![(Loc("l1".to_string()), Int(2))]);
+        let env = Env::from(vec![(Var("x".to_string()), Loc("l1".to_string()))]);
+        assert_eq!(
+            solve(&store, &env, &Deref(Box::new(Var("x".to_string()))), 0).unwrap(),
+            EDeref(
+                store.clone(),
+                env.clone(),
+                Var("x".to_string()),
+                Box::new(EVar(store, env, Var("x".to_string()), 1)),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve2() {
+        let (store, env, expr) = parse(
+            "|- let c = let x = ref 0 in fun y -> if y then x := !x + 1 else !x in let y = c true in let y = c true in c false",
+        )
+        .unwrap()
+        .1;
+        let rule = solve(&store, &env, &expr, 0).unwrap();
+        assert_eq!(rule.evaluated(), Int(2));
+        assert_eq!(
+            rule.stored(),
+            Store::from(vec![(Loc("l1".to_string()), Int(2))])
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let (store, env, expr) = parse("@l1 = 2 / x = @l1 |- x := !x + 1").unwrap().1;
+        assert_eq!(
+            solve(&store, &env, &expr, 0).unwrap().to_string(),
+            "@l1 = 2 / x = @l1 |- x := !x + 1 evalto 3 / @l1 = 3 by E-Assign {
+    @l1 = 2 / x = @l1 |- x evalto @l1 / @l1 = 2 by E-Var {};
+    @l1 = 2 / x = @l1 |- !x + 1 evalto 3 / @l1 = 2 by E-Plus {
+        @l1 = 2 / x = @l1 |- !x evalto 2 / @l1 = 2 by E-Deref {
+            @l1 = 2 / x = @l1 |- x evalto @l1 / @l1 = 2 by E-Var {}
+        };
+        @l1 = 2 / x = @l1 |- 1 evalto 1 / @l1 = 2 by E-Int {};
+        2 plus 1 is 3 by B-Plus {}
+    }
+}"
+        );
+    }
+
+    #[test]
+    fn test_display_ref() {
+        let (store, env, expr) = parse("|- let r = ref 2 in !r").unwrap().1;
+        assert_eq!(
+            solve(&store, &env, &expr, 0).unwrap().to_string(),
+            "|- let r = ref 2 in !r evalto 2 / @l1 = 2 by E-Let {
+    |- ref 2 evalto @l1 / @l1 = 2 by E-Ref {
+        |- 2 evalto 2 by E-Int {}
+    };
+    @l1 = 2 / r = @l1 |- !r evalto 2 / @l1 = 2 by E-Deref {
+        @l1 = 2 / r = @l1 |- r evalto @l1 / @l1 = 2 by E-Var {}
+    }
+}"
+        );
+    }
+}
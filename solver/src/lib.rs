@@ -1,4 +1,25 @@
+pub mod checker;
+pub mod compare_nat;
+pub mod eval_cont_ml1;
+pub mod eval_cont_ml4;
+pub mod eval_error;
 pub mod eval_ml1;
+pub mod eval_ml1_err;
+pub mod eval_ml1_ext;
 pub mod eval_ml2;
+pub mod eval_ml2_ext;
 pub mod eval_ml3;
+pub mod eval_ml4;
+pub mod eval_ml5;
+pub mod eval_nameless_ml3;
+pub mod eval_nat_exp;
+pub mod eval_ref_ml3;
+pub mod nameless_ml3;
+pub mod nat;
+pub mod poly_typing_ml4;
+pub mod reduce_nat_exp;
+pub mod repl;
+pub mod syntax;
+pub mod system;
+pub mod typing_ml4;
 mod util;
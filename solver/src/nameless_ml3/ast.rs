@@ -0,0 +1,186 @@
+use crate::eval_ml3::ast::Expr as NamedExpr;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Expr {
+    Int(i64),
+    Bool(bool),
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+    Plus(Box<Expr>, Box<Expr>),
+    Minus(Box<Expr>, Box<Expr>),
+    Times(Box<Expr>, Box<Expr>),
+    Lt(Box<Expr>, Box<Expr>),
+    Index(usize),
+    Let(Box<Expr>, Box<Expr>),
+    Fun(Box<Expr>),
+    App(Box<Expr>, Box<Expr>),
+    LetRec(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    // 結合の強さ (大きいほど強い)
+    fn precedence(&self) -> usize {
+        use crate::nameless_ml3::ast::Expr::*;
+
+        match self {
+            If(_, _, _) | Let(_, _) | Fun(_) | LetRec(_, _) => 0,
+            Lt(_, _) => 1,
+            Plus(_, _) | Minus(_, _) => 3,
+            Times(_, _) => 4,
+            App(_, _) => 5,
+            Int(i) if *i < 0 => 5,
+            _ => 6,
+        }
+    }
+
+    // 右にできるだけ伸びる式 (右端にあれば括弧がいらない)
+    fn is_open(&self) -> bool {
+        use crate::nameless_ml3::ast::Expr::*;
+
+        matches!(self, If(_, _, _) | Let(_, _) | Fun(_) | LetRec(_, _))
+    }
+
+    fn paren(&self, paren: bool) -> String {
+        if paren {
+            format!("({})", self)
+        } else {
+            self.to_string()
+        }
+    }
+
+    // tail は後ろに何も続かないこと
+    fn show(&self, tail: bool) -> String {
+        use crate::nameless_ml3::ast::Expr::*;
+
+        let p = self.precedence();
+        let left = |expr: &Expr, paren: bool| {
+            if paren {
+                expr.paren(true)
+            } else {
+                expr.show(false)
+            }
+        };
+        let right = |expr: &Expr, paren: bool| {
+            if paren && !(tail && expr.is_open()) {
+                expr.paren(true)
+            } else {
+                expr.show(tail)
+            }
+        };
+        match self {
+            Int(i) => i.to_string(),
+            Bool(b) => b.to_string(),
+            If(expr1, expr2, expr3) => {
+                format!("if {} then {} else {}", expr1, expr2, expr3.show(tail))
+            }
+            Plus(expr1, expr2) => format!(
+                "{} + {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Minus(expr1, expr2) => format!(
+                "{} - {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Times(expr1, expr2) => format!(
+                "{} * {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Lt(expr1, expr2) => format!(
+                "{} < {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Index(n) => format!("#{}", n),
+            Let(expr1, expr2) => format!("let . = {} in {}", expr1, expr2.show(tail)),
+            Fun(expr) => format!("fun . -> {}", expr.show(tail)),
+            App(expr1, expr2) => format!(
+                "{} {}",
+                left(expr1, expr1.precedence() < p),
+                expr2.paren(expr2.precedence() <= p)
+            ),
+            LetRec(expr1, expr2) => {
+                format!("let rec . = fun . -> {} in {}", expr1, expr2.show(tail))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.show(true))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Vars(Vec<NamedExpr>);
+
+impl Vars {
+    pub fn new() -> Vars {
+        Vars(vec![])
+    }
+
+    pub fn from(vars: Vec<NamedExpr>) -> Vars {
+        Vars(vars)
+    }
+
+    pub fn put(&mut self, expr: NamedExpr) {
+        self.0.push(expr)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn last(&self) -> NamedExpr {
+        self.0.last().cloned().unwrap()
+    }
+
+    pub fn butlast(&self) -> Vars {
+        let vars = self.0[..self.0.len() - 1].to_vec();
+        Vars(vars)
+    }
+}
+
+impl fmt::Display for Vars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let vars = self
+            .0
+            .iter()
+            .map(|expr| expr.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}", vars.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eval_ml3::ast::Expr::Var,
+        nameless_ml3::ast::{Expr::*, Vars},
+    };
+
+    #[test]
+    fn test_display1() {
+        assert_eq!(
+            Let(
+                Box::new(Index(1)),
+                Box::new(Plus(Box::new(Index(1)), Box::new(Index(2))))
+            )
+            .to_string(),
+            "let . = #1 in #1 + #2"
+        );
+    }
+
+    #[test]
+    fn test_vars() {
+        let mut vars = Vars::new();
+        vars.put(Var("x".to_string()));
+        vars.put(Var("y".to_string()));
+        assert_eq!("x, y", vars.to_string());
+        assert_eq!(Var("y".to_string()), vars.last());
+        assert_eq!(Vars::from(vec![Var("x".to_string())]), vars.butlast());
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    eval_ml3::{
+        ast::Expr,
+        parser::{parse_expr, parse_var},
+    },
+    nameless_ml3::ast::Vars,
+    util::ws,
+};
+use nom::{character::complete::char, multi::separated_list0, IResult};
+
+// <judgement> ::= <vars> '|-' <expr>
+// <vars> ::= [ <var> [ ',' <var> ]* ]
+// <expr> は EvalML3 と同じ
+
+pub fn parse(input: &str) -> IResult<&str, (Vars, Expr)> {
+    match input.find("|-") {
+        Some(i) => {
+            let (input1, input2) = input.split_at(i);
+            let (_, vars) = parse_vars(input1)?;
+            let (input, expr) = parse_expr(&input2[2..])?;
+            Ok((input, (vars, expr)))
+        }
+        None => {
+            let (input, expr) = parse_expr(input)?;
+            Ok((input, (Vars::new(), expr)))
+        }
+    }
+}
+
+fn parse_vars(input: &str) -> IResult<&str, Vars> {
+    let (input, vars) = separated_list0(ws(char(',')), parse_var)(input)?;
+    Ok((input, Vars::from(vars)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eval_ml3::ast::Expr::*,
+        nameless_ml3::{ast::Vars, parser::parse},
+    };
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(
+            parse("x, y |- let z = x in z + y").unwrap().1,
+            (
+                Vars::from(vec![Var("x".to_string()), Var("y".to_string())]),
+                Let(
+                    Box::new(Var("z".to_string())),
+                    Box::new(Var("x".to_string())),
+                    Box::new(Plus(
+                        Box::new(Var("z".to_string())),
+                        Box::new(Var("y".to_string()))
+                    ))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse("|- fun x -> x").unwrap().1,
+            (
+                Vars::new(),
+                Fun(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Var("x".to_string()))
+                )
+            )
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{
+    eval_ml3::ast::Expr as NamedExpr,
+    nameless_ml3::ast::{Expr, Expr::*, Vars},
+    util::{env_prefix, ident},
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    TrInt(Vars, i64, usize),
+    TrBool(Vars, bool, usize),
+    TrIf(
+        Vars,
+        NamedExpr,
+        NamedExpr,
+        NamedExpr,
+        Box<Rule>,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+    TrPlus(Vars, NamedExpr, NamedExpr, Box<Rule>, Box<Rule>, usize),
+    TrMinus(Vars, NamedExpr, NamedExpr, Box<Rule>, Box<Rule>, usize),
+    TrTimes(Vars, NamedExpr, NamedExpr, Box<Rule>, Box<Rule>, usize),
+    TrLt(Vars, NamedExpr, NamedExpr, Box<Rule>, Box<Rule>, usize),
+    TrVar1(Vars, NamedExpr, usize),
+    TrVar2(Vars, NamedExpr, Box<Rule>, usize),
+    TrLet(
+        Vars,
+        NamedExpr,
+        NamedExpr,
+        NamedExpr,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+    TrFun(Vars, NamedExpr, NamedExpr, Box<Rule>, usize),
+    TrApp(Vars, NamedExpr, NamedExpr, Box<Rule>, Box<Rule>, usize),
+    TrLetRec(
+        Vars,
+        NamedExpr,
+        NamedExpr,
+        NamedExpr,
+        NamedExpr,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+}
+
+impl Rule {
+    pub fn translated(&self) -> Expr {
+        use crate::nameless_ml3::rule::Rule::*;
+
+        match self {
+            TrInt(_, i, _) => Int(*i),
+            TrBool(_, b, _) => Bool(*b),
+            TrIf(_, _, _, _, rule1, rule2, rule3, _) => If(
+                Box::new(rule1.translated()),
+                Box::new(rule2.translated()),
+                Box::new(rule3.translated()),
+            ),
+            TrPlus(_, _, _, rule1, rule2, _) => {
+                Plus(Box::new(rule1.translated()), Box::new(rule2.translated()))
+            }
+            TrMinus(_, _, _, rule1, rule2, _) => {
+                Minus(Box::new(rule1.translated()), Box::new(rule2.translated()))
+            }
+            TrTimes(_, _, _, rule1, rule2, _) => {
+                Times(Box::new(rule1.translated()), Box::new(rule2.translated()))
+            }
+            TrLt(_, _, _, rule1, rule2, _) => {
+                Lt(Box::new(rule1.translated()), Box::new(rule2.translated()))
+            }
+            TrVar1(_, _, _) => Index(1),
+            TrVar2(_, _, rule, _) => match rule.translated() {
+                Index(n) => Index(n + 1),
+                _ => unreachable!(),
+            },
+            TrLet(_, _, _, _, rule1, rule2, _) => {
+                Let(Box::new(rule1.translated()), Box::new(rule2.translated()))
+            }
+            TrFun(_, _, _, rule, _) => Fun(Box::new(rule.translated())),
+            TrApp(_, _, _, rule1, rule2, _) => {
+                App(Box::new(rule1.translated()), Box::new(rule2.translated()))
+            }
+            TrLetRec(_, _, _, _, _, rule1, rule2, _) => {
+                LetRec(Box::new(rule1.translated()), Box::new(rule2.translated()))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::nameless_ml3::rule::Rule::*;
+
+        match self {
+            TrInt(vars, i, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} ==> {} by Tr-Int {{}}",
+                    ident(*depth),
+                    env_prefix(vars),
+                    i,
+                    i
+                )
+            }
+            TrBool(vars, b, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} ==> {} by Tr-Bool {{}}",
+                    ident(*depth),
+                    env_prefix(vars),
+                    b,
+                    b
+                )
+            }
+            TrIf(vars, expr1, expr2, expr3, rule1, rule2, rule3, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- if {} then {} else {} ==> if {} then {} else {} by Tr-If {{",
+                    ident(*depth),
+                    env_prefix(vars),
+                    expr1,
+                    expr2,
+                    expr3,
+                    rule1.translated(),
+                    rule2.translated(),
+                    rule3.translated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TrPlus(vars, expr1, expr2, rule1, rule2, depth) => {
+                let expr = NamedExpr::Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} ==> {} by Tr-Plus {{",
+                    ident(*depth),
+                    env_prefix(vars),
+                    expr,
+                    self.translated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TrMinus(vars, expr1, expr2, rule1, rule2, depth) => {
+                let expr = NamedExpr::Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} ==> {} by Tr-Minus {{",
+                    ident(*depth),
+                    env_prefix(vars),
+                    expr,
+                    self.translated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TrTimes(vars, expr1, expr2, rule1, rule2, depth) => {
+                let expr = NamedExpr::Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} ==> {} by Tr-Times {{",
+                    ident(*depth),
+                    env_prefix(vars),
+                    expr,
+                    self.translated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TrLt(vars, expr1, expr2, rule1, rule2, depth) => {
+                let expr = NamedExpr::Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} ==> {} by Tr-Lt {{",
+                    ident(*depth),
+                    env_prefix(vars),
+                    expr,
+                    self.translated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TrVar1(vars, expr, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} ==> {} by Tr-Var1 {{}}",
+                    ident(*depth),
+                    env_prefix(vars),
+                    expr,
+                    self.translated()
+                )
+            }
+            TrVar2(vars, expr, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- {} ==> {} by Tr-Var2 {{",
+                    ident(*depth),
+                    env_prefix(vars),
+                    expr,
+                    self.translated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TrLet(vars, expr1, expr2, expr3, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- let {} = {} in {} ==> let . = {} in {} by Tr-Let {{",
+                    ident(*depth),
+                    env_prefix(vars),
+                    expr1,
+                    expr2,
+                    expr3,
+                    rule1.translated(),
+                    rule2.translated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TrFun(vars, expr1, expr2, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- fun {} -> {} ==> fun . -> {} by Tr-Fun {{",
+                    ident(*depth),
+                    env_prefix(vars),
+                    expr1,
+                    expr2,
+                    rule.translated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TrApp(vars, expr1, expr2, rule1, rule2, depth) => {
+                let expr = NamedExpr::App(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} ==> {} by Tr-App {{",
+                    ident(*depth),
+                    env_prefix(vars),
+                    expr,
+                    self.translated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TrLetRec(vars, expr1, expr2, expr3, expr4, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{}|- let rec {} = fun {} -> {} in {} ==> let rec . = fun . -> {} in {} by Tr-LetRec {{",
+                    ident(*depth),
+                    env_prefix(vars),
+                    expr1,
+                    expr2,
+                    expr3,
+                    expr4,
+                    rule1.translated(),
+                    rule2.translated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+        }
+    }
+}
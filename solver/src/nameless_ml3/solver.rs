@@ -0,0 +1,215 @@
+use crate::{
+    eval_error::{check_depth, EvalError},
+    eval_ml3::ast::{Expr, Expr::*},
+    nameless_ml3::{
+        ast::Vars,
+        rule::{Rule, Rule::*},
+    },
+};
+
+pub fn solve(vars: &Vars, expr: &Expr, depth: usize) -> Result<Rule, EvalError> {
+    check_depth(depth)?;
+    let rule = match expr {
+        Int(i) => TrInt(vars.clone(), *i, depth),
+        Bool(b) => TrBool(vars.clone(), *b, depth),
+        If(expr1, expr2, expr3) => {
+            let rule1 = solve(vars, expr1, depth + 1)?;
+            let rule2 = solve(vars, expr2, depth + 1)?;
+            let rule3 = solve(vars, expr3, depth + 1)?;
+            TrIf(
+                vars.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            )
+        }
+        Plus(expr1, expr2) => {
+            let rule1 = solve(vars, expr1, depth + 1)?;
+            let rule2 = solve(vars, expr2, depth + 1)?;
+            TrPlus(
+                vars.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            )
+        }
+        Minus(expr1, expr2) => {
+            let rule1 = solve(vars, expr1, depth + 1)?;
+            let rule2 = solve(vars, expr2, depth + 1)?;
+            TrMinus(
+                vars.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            )
+        }
+        Times(expr1, expr2) => {
+            let rule1 = solve(vars, expr1, depth + 1)?;
+            let rule2 = solve(vars, expr2, depth + 1)?;
+            TrTimes(
+                vars.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            )
+        }
+        Lt(expr1, expr2) => {
+            let rule1 = solve(vars, expr1, depth + 1)?;
+            let rule2 = solve(vars, expr2, depth + 1)?;
+            TrLt(
+                vars.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            )
+        }
+        Var(_) => {
+            if vars.is_empty() {
+                return Err(EvalError::Unbound(expr.to_string()));
+            }
+            if vars.last() == expr.clone() {
+                TrVar1(vars.clone(), expr.clone(), depth)
+            } else {
+                let rule = solve(&vars.butlast(), expr, depth + 1)?;
+                TrVar2(vars.clone(), expr.clone(), Box::new(rule), depth)
+            }
+        }
+        Let(expr1, expr2, expr3) => {
+            let rule1 = solve(vars, expr2, depth + 1)?;
+            let mut new_vars = vars.clone();
+            new_vars.put(*expr1.clone());
+            let rule2 = solve(&new_vars, expr3, depth + 1)?;
+            TrLet(
+                vars.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            )
+        }
+        Fun(expr1, expr2) => {
+            let mut new_vars = vars.clone();
+            new_vars.put(*expr1.clone());
+            let rule = solve(&new_vars, expr2, depth + 1)?;
+            TrFun(
+                vars.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule),
+                depth,
+            )
+        }
+        App(expr1, expr2) => {
+            let rule1 = solve(vars, expr1, depth + 1)?;
+            let rule2 = solve(vars, expr2, depth + 1)?;
+            TrApp(
+                vars.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            )
+        }
+        LetRec(expr1, expr2, expr3, expr4) => {
+            let mut new_vars1 = vars.clone();
+            new_vars1.put(*expr1.clone());
+            new_vars1.put(*expr2.clone());
+            let rule1 = solve(&new_vars1, expr3, depth + 1)?;
+            let mut new_vars2 = vars.clone();
+            new_vars2.put(*expr1.clone());
+            let rule2 = solve(&new_vars2, expr4, depth + 1)?;
+            TrLetRec(
+                vars.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                *expr4.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            )
+        }
+        Closure(_, _, _) | RecClosure(_, _, _, _) => {
+            return Err(EvalError::Stuck(expr.to_string()))
+        }
+    };
+    Ok(rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eval_ml3::ast::Expr::*,
+        nameless_ml3::{ast::Vars, parser::parse, rule::Rule::*, solver::solve},
+    };
+
+    #[test]
+    fn test_solve1() {
+        let vars = Vars::from(vec![Var("x".to_string()), Var("y".to_string())]);
+        assert_eq!(
+            solve(&vars, &Var("x".to_string()), 0).unwrap(),
+            TrVar2(
+                vars.clone(),
+                Var("x".to_string()),
+                Box::new(TrVar1(
+                    Vars::from(vec![Var("x".to_string())]),
+                    Var("x".to_string()),
+                    1
+                )),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve2() {
+        let (vars, expr) = parse("x, y |- let z = x in z + y").unwrap().1;
+        assert_eq!(
+            solve(&vars, &expr, 0).unwrap().translated().to_string(),
+            "let . = #2 in #1 + #2"
+        );
+    }
+
+    #[test]
+    fn test_solve3() {
+        let (vars, expr) =
+            parse("|- let rec fact = fun n -> if n < 2 then 1 else n * fact (n - 1) in fact 3")
+                .unwrap()
+                .1;
+        assert_eq!(
+            solve(&vars, &expr, 0).unwrap().translated().to_string(),
+            "let rec . = fun . -> if #1 < 2 then 1 else #1 * #2 (#1 - 1) in #1 3"
+        );
+    }
+
+    #[test]
+    fn test_display1() {
+        let (vars, expr) = parse("x |- fun y -> x + y").unwrap().1;
+        assert_eq!(
+            solve(&vars, &expr, 0).unwrap().to_string(),
+            r#"x |- fun y -> x + y ==> fun . -> #2 + #1 by Tr-Fun {
+    x, y |- x + y ==> #2 + #1 by Tr-Plus {
+        x, y |- x ==> #2 by Tr-Var2 {
+            x |- x ==> #1 by Tr-Var1 {}
+        };
+        x, y |- y ==> #1 by Tr-Var1 {}
+    }
+}"#
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Nat {
+    Z,
+    S(Box<Nat>),
+}
+
+impl fmt::Display for Nat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::nat::ast::Nat::*;
+
+        match self {
+            Z => write!(f, "Z"),
+            S(nat) => write!(f, "S({})", nat),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Judgement {
+    Plus(Nat, Nat, Nat),
+    Times(Nat, Nat, Nat),
+}
+
+impl fmt::Display for Judgement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::nat::ast::Judgement::*;
+
+        match self {
+            Plus(nat1, nat2, nat3) => write!(f, "{} plus {} is {}", nat1, nat2, nat3),
+            Times(nat1, nat2, nat3) => write!(f, "{} times {} is {}", nat1, nat2, nat3),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{
+    nat::ast::{Judgement, Judgement::*, Nat, Nat::*},
+    util::ws,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::char,
+    sequence::{delimited, tuple},
+    IResult,
+};
+
+// <judgement> ::= <nat> ( 'plus' | 'times' ) <nat> 'is' <nat>
+// <nat> ::= <zero> | <succ>
+// <zero> ::= 'Z'
+// <succ> ::= 'S' '(' <nat> ')'
+
+pub fn parse(input: &str) -> IResult<&str, Judgement> {
+    let (input, (nat1, op, nat2, _, nat3)) = tuple((
+        parse_nat,
+        alt((ws(tag("plus")), ws(tag("times")))),
+        parse_nat,
+        ws(tag("is")),
+        parse_nat,
+    ))(input)?;
+    let judgement = match op {
+        "plus" => Plus(nat1, nat2, nat3),
+        "times" => Times(nat1, nat2, nat3),
+        _ => unreachable!(),
+    };
+    Ok((input, judgement))
+}
+
+pub fn parse_nat(input: &str) -> IResult<&str, Nat> {
+    let (input, nat) = alt((parse_zero, parse_succ))(input)?;
+    Ok((input, nat))
+}
+
+fn parse_zero(input: &str) -> IResult<&str, Nat> {
+    let (input, _) = ws(char('Z'))(input)?;
+    Ok((input, Z))
+}
+
+fn parse_succ(input: &str) -> IResult<&str, Nat> {
+    let parse_lparen = ws(char('('));
+    let parse_rparen = ws(char(')'));
+    let (input, (_, nat)) = tuple((
+        ws(char('S')),
+        delimited(parse_lparen, parse_nat, parse_rparen),
+    ))(input)?;
+    let nat = S(Box::new(nat));
+    Ok((input, nat))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nat::{
+        ast::{Judgement::*, Nat::*},
+        parser::parse,
+    };
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(parse("Z plus Z is Z").unwrap().1, Plus(Z, Z, Z));
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse("Z plus S(S(Z)) is S(S(Z))").unwrap().1,
+            Plus(Z, S(Box::new(S(Box::new(Z)))), S(Box::new(S(Box::new(Z)))))
+        );
+    }
+
+    #[test]
+    fn test_parse3() {
+        assert_eq!(
+            parse("S(S(Z)) times S(Z) is S(S(Z))").unwrap().1,
+            Times(
+                S(Box::new(S(Box::new(Z)))),
+                S(Box::new(Z)),
+                S(Box::new(S(Box::new(Z))))
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse4() {
+        assert_eq!(
+            parse("S( Z ) times Z is Z").unwrap().1,
+            Times(S(Box::new(Z)), Z, Z)
+        );
+    }
+}
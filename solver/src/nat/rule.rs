@@ -0,0 +1,72 @@
+use crate::{
+    nat::ast::{Nat, Nat::*},
+    util::ident,
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    PZero(Nat, usize),
+    PSucc(Nat, Nat, Box<Rule>, usize),
+    TZero(Nat, usize),
+    TSucc(Nat, Nat, Box<Rule>, Box<Rule>, usize),
+}
+
+impl Rule {
+    pub fn evaluated(&self) -> Nat {
+        use crate::nat::rule::Rule::*;
+
+        match self {
+            PZero(nat, _) => nat.clone(),
+            PSucc(_, _, rule, _) => S(Box::new(rule.evaluated())),
+            TZero(_, _) => Z,
+            TSucc(_, _, _, rule2, _) => rule2.evaluated(),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::nat::rule::Rule::*;
+
+        match self {
+            PZero(nat, depth) => {
+                write!(
+                    f,
+                    "{}Z plus {} is {} by P-Zero {{}}",
+                    ident(*depth),
+                    nat,
+                    nat
+                )
+            }
+            PSucc(nat1, nat2, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} plus {} is {} by P-Succ {{",
+                    ident(*depth),
+                    nat1,
+                    nat2,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TZero(nat, depth) => {
+                write!(f, "{}Z times {} is Z by T-Zero {{}}", ident(*depth), nat)
+            }
+            TSucc(nat1, nat2, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{} times {} is {} by T-Succ {{",
+                    ident(*depth),
+                    nat1,
+                    nat2,
+                    self.evaluated()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::nat::{
+    ast::{Judgement, Judgement::*, Nat, Nat::*},
+    rule::{Rule, Rule::*},
+};
+
+pub fn solve(judgement: &Judgement, depth: usize) -> Option<Rule> {
+    let (rule, nat) = match judgement {
+        Plus(nat1, nat2, nat3) => (solve_plus(nat1, nat2, depth), nat3),
+        Times(nat1, nat2, nat3) => (solve_times(nat1, nat2, depth), nat3),
+    };
+    if rule.evaluated() == *nat {
+        Some(rule)
+    } else {
+        None
+    }
+}
+
+pub fn solve_plus(nat1: &Nat, nat2: &Nat, depth: usize) -> Rule {
+    match nat1 {
+        Z => PZero(nat2.clone(), depth),
+        S(nat) => {
+            let rule = solve_plus(nat, nat2, depth + 1);
+            PSucc(nat1.clone(), nat2.clone(), Box::new(rule), depth)
+        }
+    }
+}
+
+pub fn solve_times(nat1: &Nat, nat2: &Nat, depth: usize) -> Rule {
+    match nat1 {
+        Z => TZero(nat2.clone(), depth),
+        S(nat) => {
+            let rule1 = solve_times(nat, nat2, depth + 1);
+            let rule2 = solve_plus(nat2, &rule1.evaluated(), depth + 1);
+            TSucc(
+                nat1.clone(),
+                nat2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nat::{
+        ast::{Judgement::*, Nat::*},
+        rule::Rule::*,
+        solver::solve,
+    };
+
+    #[test]
+    fn test_solve1() {
+        assert_eq!(solve(&Plus(Z, Z, Z), 0), Some(PZero(Z, 0)));
+    }
+
+    #[test]
+    fn test_solve2() {
+        assert_eq!(
+            solve(
+                &Plus(S(Box::new(S(Box::new(Z)))), Z, S(Box::new(S(Box::new(Z))))),
+                0
+            ),
+            Some(PSucc(
+                S(Box::new(S(Box::new(Z)))),
+                Z,
+                Box::new(PSucc(S(Box::new(Z)), Z, Box::new(PZero(Z, 2)), 1)),
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve3() {
+        assert_eq!(
+            solve(&Times(Z, S(Box::new(S(Box::new(Z)))), Z), 0),
+            Some(TZero(S(Box::new(S(Box::new(Z)))), 0))
+        );
+    }
+
+    #[test]
+    fn test_solve4() {
+        assert_eq!(
+            solve(
+                &Times(
+                    S(Box::new(S(Box::new(Z)))),
+                    S(Box::new(Z)),
+                    S(Box::new(S(Box::new(Z))))
+                ),
+                0
+            ),
+            Some(TSucc(
+                S(Box::new(S(Box::new(Z)))),
+                S(Box::new(Z)),
+                Box::new(TSucc(
+                    S(Box::new(Z)),
+                    S(Box::new(Z)),
+                    Box::new(TZero(S(Box::new(Z)), 2)),
+                    Box::new(PSucc(S(Box::new(Z)), Z, Box::new(PZero(Z, 3)), 2)),
+                    1
+                )),
+                Box::new(PSucc(
+                    S(Box::new(Z)),
+                    S(Box::new(Z)),
+                    Box::new(PZero(S(Box::new(Z)), 2)),
+                    1
+                )),
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve5() {
+        assert_eq!(solve(&Plus(Z, Z, S(Box::new(Z))), 0), None);
+    }
+
+    #[test]
+    fn test_solve6() {
+        let judgement = Times(
+            S(Box::new(S(Box::new(Z)))),
+            S(Box::new(Z)),
+            S(Box::new(S(Box::new(Z)))),
+        );
+        assert_eq!(
+            solve(&judgement, 0).unwrap().to_string(),
+            "S(S(Z)) times S(Z) is S(S(Z)) by T-Succ {
+    S(Z) times S(Z) is S(Z) by T-Succ {
+        Z times S(Z) is Z by T-Zero {};
+        S(Z) plus Z is S(Z) by P-Succ {
+            Z plus Z is Z by P-Zero {}
+        }
+    };
+    S(Z) plus S(Z) is S(S(Z)) by P-Succ {
+        Z plus S(Z) is S(Z) by P-Zero {}
+    }
+}"
+        );
+    }
+}
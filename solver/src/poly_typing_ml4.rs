@@ -0,0 +1,5 @@
+pub mod ast;
+pub mod parser;
+pub mod rule;
+pub mod solver;
+pub mod unifier;
@@ -0,0 +1,192 @@
+use crate::typing_ml4::ast::Expr;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Type {
+    Int,
+    Bool,
+    List(Box<Type>),
+    Fun(Box<Type>, Box<Type>),
+    Param(String),
+    Var(usize),
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::poly_typing_ml4::ast::Type::*;
+
+        match self {
+            Int => write!(f, "int"),
+            Bool => write!(f, "bool"),
+            List(ty) => match **ty {
+                Fun(_, _) => write!(f, "({}) list", ty),
+                _ => write!(f, "{} list", ty),
+            },
+            Fun(ty1, ty2) => match **ty1 {
+                Fun(_, _) => write!(f, "({}) -> {}", ty1, ty2),
+                _ => write!(f, "{} -> {}", ty1, ty2),
+            },
+            Param(s) => write!(f, "'{}", s),
+            Var(n) => write!(f, "'_{}", n),
+        }
+    }
+}
+
+impl Type {
+    pub fn params(&self) -> Vec<String> {
+        use crate::poly_typing_ml4::ast::Type::*;
+
+        match self {
+            Int | Bool | Var(_) => vec![],
+            List(ty) => ty.params(),
+            Fun(ty1, ty2) => {
+                let mut params = ty1.params();
+                params.extend(ty2.params());
+                params
+            }
+            Param(s) => vec![s.clone()],
+        }
+    }
+
+    pub fn vars(&self) -> Vec<usize> {
+        use crate::poly_typing_ml4::ast::Type::*;
+
+        match self {
+            Int | Bool | Param(_) => vec![],
+            List(ty) => ty.vars(),
+            Fun(ty1, ty2) => {
+                let mut vars = ty1.vars();
+                vars.extend(ty2.vars());
+                vars
+            }
+            Var(n) => vec![*n],
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Scheme(Vec<String>, Type);
+
+impl Scheme {
+    pub fn new(params: Vec<String>, ty: Type) -> Scheme {
+        Scheme(params, ty)
+    }
+
+    pub fn mono(ty: Type) -> Scheme {
+        Scheme(vec![], ty)
+    }
+
+    pub fn params(&self) -> &Vec<String> {
+        &self.0
+    }
+
+    pub fn body(&self) -> &Type {
+        &self.1
+    }
+}
+
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            write!(f, "{}", self.1)
+        } else {
+            let params = self.0.iter().map(|s| format!("'{}", s)).collect::<Vec<_>>();
+            write!(f, "{}.{}", params.join(" "), self.1)
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Env(Vec<(Expr, Scheme)>);
+
+impl Env {
+    pub fn new() -> Env {
+        Env(vec![])
+    }
+
+    pub fn from(pairs: Vec<(Expr, Scheme)>) -> Env {
+        Env(pairs)
+    }
+
+    pub fn put(&mut self, expr: Expr, scheme: Scheme) {
+        self.0.push((expr, scheme))
+    }
+
+    pub fn get(&self, expr: &Expr) -> Option<Scheme> {
+        self.0
+            .iter()
+            .rev()
+            .find(|(expr1, _)| expr1 == expr)
+            .map(|(_, scheme)| scheme.clone())
+    }
+
+    pub fn schemes(&self) -> Vec<Scheme> {
+        self.0.iter().map(|(_, scheme)| scheme.clone()).collect()
+    }
+
+    pub fn map<F: Fn(&Type) -> Type>(&self, f: &F) -> Env {
+        let pairs = self
+            .0
+            .iter()
+            .map(|(expr, Scheme(params, ty))| (expr.clone(), Scheme(params.clone(), f(ty))))
+            .collect();
+        Env(pairs)
+    }
+}
+
+impl fmt::Display for Env {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs = self
+            .0
+            .iter()
+            .map(|(expr, scheme)| format!("{} : {}", expr, scheme))
+            .collect::<Vec<_>>();
+        write!(f, "{}", pairs.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        poly_typing_ml4::ast::{Env, Scheme, Type},
+        typing_ml4::ast::Expr::*,
+    };
+
+    #[test]
+    fn test_scheme() {
+        let scheme = Scheme::new(
+            vec!["a".to_string(), "b".to_string()],
+            Type::Fun(
+                Box::new(Type::Param("a".to_string())),
+                Box::new(Type::Param("b".to_string())),
+            ),
+        );
+        assert_eq!("'a 'b.'a -> 'b", scheme.to_string());
+        assert_eq!(
+            "int list",
+            Scheme::mono(Type::List(Box::new(Type::Int))).to_string()
+        );
+    }
+
+    #[test]
+    fn test_env() {
+        let env = Env::from(vec![
+            (
+                Var("f".to_string()),
+                Scheme::new(
+                    vec!["a".to_string()],
+                    Type::Fun(
+                        Box::new(Type::Param("a".to_string())),
+                        Box::new(Type::Param("a".to_string())),
+                    ),
+                ),
+            ),
+            (Var("x".to_string()), Scheme::mono(Type::Int)),
+        ]);
+        assert_eq!("f : 'a.'a -> 'a, x : int", env.to_string());
+        assert_eq!(
+            Some(Scheme::mono(Type::Int)),
+            env.get(&Var("x".to_string()))
+        );
+    }
+}
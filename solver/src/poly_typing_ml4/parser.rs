@@ -0,0 +1,160 @@
+use crate::{
+    poly_typing_ml4::ast::{Env, Scheme, Type},
+    syntax::lexer::keyword,
+    typing_ml4::{
+        ast::Expr,
+        parser::{parse_expr, parse_var},
+    },
+    util::ws,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, char},
+    combinator::opt,
+    error::{Error, ErrorKind},
+    multi::{many0, many1, separated_list0},
+    sequence::{delimited, tuple},
+    IResult,
+};
+
+// <judgement> ::= [ <env> ] '|-' <expr> ':' <type>
+// <env> ::= <var> ':' <scheme> [ ',' <var> ':' <scheme> ]*
+// <expr> は TypingML4 と同じ
+// <scheme> ::= [ <type_param>+ '.' ] <type>
+// <type> ::= <list_type> [ '->' <type> ]
+// <list_type> ::= <type_atom> [ 'list' ]*
+// <type_atom> ::= 'int' | 'bool' | <type_param> | '(' <type> ')'
+// <type_param> ::= '\'' 英字
+
+pub fn parse(input: &str) -> IResult<&str, (Env, Expr, Type)> {
+    let (input1, input2) = match input.find("|-") {
+        Some(i) => input.split_at(i),
+        None => return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag))),
+    };
+    let (_, env) = parse_env(input1)?;
+    let (input, (expr, _, ty)) = tuple((parse_expr, ws(char(':')), parse_type))(&input2[2..])?;
+    Ok((input, (env, expr, ty)))
+}
+
+fn parse_env(input: &str) -> IResult<&str, Env> {
+    let (input, pairs) = separated_list0(ws(char(',')), parse_pair)(input)?;
+    Ok((input, Env::from(pairs)))
+}
+
+fn parse_pair(input: &str) -> IResult<&str, (Expr, Scheme)> {
+    let (input, (expr, _, scheme)) = tuple((parse_var, ws(char(':')), parse_scheme))(input)?;
+    Ok((input, (expr, scheme)))
+}
+
+pub fn parse_scheme(input: &str) -> IResult<&str, Scheme> {
+    let (input, (params, ty)) = tuple((
+        opt(tuple((many1(parse_type_param), ws(char('.'))))),
+        parse_type,
+    ))(input)?;
+    let scheme = match params {
+        Some((params, _)) => Scheme::new(params, ty),
+        None => Scheme::mono(ty),
+    };
+    Ok((input, scheme))
+}
+
+pub fn parse_type(input: &str) -> IResult<&str, Type> {
+    let (input, (ty1, ty2)) =
+        tuple((parse_list_type, opt(tuple((ws(tag("->")), parse_type)))))(input)?;
+    let ty = match ty2 {
+        Some((_, ty2)) => Type::Fun(Box::new(ty1), Box::new(ty2)),
+        None => ty1,
+    };
+    Ok((input, ty))
+}
+
+fn parse_list_type(input: &str) -> IResult<&str, Type> {
+    let (input, (ty, lists)) = tuple((parse_type_atom, many0(ws(keyword("list")))))(input)?;
+    let ty = lists.into_iter().fold(ty, |ty, _| Type::List(Box::new(ty)));
+    Ok((input, ty))
+}
+
+fn parse_type_atom(input: &str) -> IResult<&str, Type> {
+    let parse_int = |input| {
+        let (input, _) = ws(keyword("int"))(input)?;
+        Ok((input, Type::Int))
+    };
+    let parse_bool = |input| {
+        let (input, _) = ws(keyword("bool"))(input)?;
+        Ok((input, Type::Bool))
+    };
+    let parse_param = |input| {
+        let (input, s) = parse_type_param(input)?;
+        Ok((input, Type::Param(s)))
+    };
+    let parse_paren = delimited(ws(char('(')), parse_type, ws(char(')')));
+    let (input, ty) = alt((parse_int, parse_bool, parse_param, parse_paren))(input)?;
+    Ok((input, ty))
+}
+
+fn parse_type_param(input: &str) -> IResult<&str, String> {
+    let (input, (_, s)) = tuple((ws(char('\'')), alpha1))(input)?;
+    Ok((input, s.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        poly_typing_ml4::{
+            ast::{Env, Scheme, Type},
+            parser::parse,
+        },
+        typing_ml4::ast::Expr::*,
+    };
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(
+            parse("f : 'a 'b.'a -> 'b -> 'a, x : int |- f x : bool -> int")
+                .unwrap()
+                .1,
+            (
+                Env::from(vec![
+                    (
+                        Var("f".to_string()),
+                        Scheme::new(
+                            vec!["a".to_string(), "b".to_string()],
+                            Type::Fun(
+                                Box::new(Type::Param("a".to_string())),
+                                Box::new(Type::Fun(
+                                    Box::new(Type::Param("b".to_string())),
+                                    Box::new(Type::Param("a".to_string()))
+                                ))
+                            )
+                        )
+                    ),
+                    (Var("x".to_string()), Scheme::mono(Type::Int))
+                ]),
+                App(
+                    Box::new(Var("f".to_string())),
+                    Box::new(Var("x".to_string()))
+                ),
+                Type::Fun(Box::new(Type::Bool), Box::new(Type::Int))
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse("|- fun x -> x : 'a -> 'a").unwrap().1,
+            (
+                Env::new(),
+                Fun(
+                    Box::new(Var("x".to_string())),
+                    Box::new(Var("x".to_string()))
+                ),
+                Type::Fun(
+                    Box::new(Type::Param("a".to_string())),
+                    Box::new(Type::Param("a".to_string()))
+                )
+            )
+        );
+    }
+}
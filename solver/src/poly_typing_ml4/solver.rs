@@ -0,0 +1,371 @@
+use crate::{
+    poly_typing_ml4::{
+        ast::{Env, Scheme, Type},
+        rule::{Rule, Rule::*},
+        unifier::{TypeError, Unifier},
+    },
+    typing_ml4::ast::{Expr, Expr::*},
+};
+
+pub fn solve(env: &Env, expr: &Expr, ty: &Type, depth: usize) -> Result<Rule, TypeError> {
+    let mut unifier = Unifier::new();
+    // 一般化で作る型パラメータが入力中のものと衝突しないようにする
+    let mut names = ty.params();
+    for scheme in env.schemes() {
+        names.extend(scheme.params().clone());
+        names.extend(scheme.body().params());
+    }
+    let (rule, ty1) = infer(&mut unifier, &mut names, env, expr, depth)?;
+    unifier.unify(expr, ty, &ty1)?;
+    Ok(rule.map(&|ty| unifier.default(ty)))
+}
+
+// 型変数を含んだまま導出を組み立て，制約を unifier に集める
+fn infer(
+    unifier: &mut Unifier,
+    names: &mut Vec<String>,
+    env: &Env,
+    expr: &Expr,
+    depth: usize,
+) -> Result<(Rule, Type), TypeError> {
+    match expr {
+        Int(i) => Ok((TInt(env.clone(), *i, depth), Type::Int)),
+        Bool(b) => Ok((TBool(env.clone(), *b, depth), Type::Bool)),
+        If(expr1, expr2, expr3) => {
+            let (rule1, ty1) = infer(unifier, names, env, expr1, depth + 1)?;
+            unifier.unify(expr1, &Type::Bool, &ty1)?;
+            let (rule2, ty2) = infer(unifier, names, env, expr2, depth + 1)?;
+            let (rule3, ty3) = infer(unifier, names, env, expr3, depth + 1)?;
+            unifier.unify(expr3, &ty2, &ty3)?;
+            let rule = TIf(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            );
+            Ok((rule, ty2))
+        }
+        Plus(expr1, expr2) => {
+            let (rule1, ty1) = infer(unifier, names, env, expr1, depth + 1)?;
+            unifier.unify(expr1, &Type::Int, &ty1)?;
+            let (rule2, ty2) = infer(unifier, names, env, expr2, depth + 1)?;
+            unifier.unify(expr2, &Type::Int, &ty2)?;
+            let rule = TPlus(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, Type::Int))
+        }
+        Minus(expr1, expr2) => {
+            let (rule1, ty1) = infer(unifier, names, env, expr1, depth + 1)?;
+            unifier.unify(expr1, &Type::Int, &ty1)?;
+            let (rule2, ty2) = infer(unifier, names, env, expr2, depth + 1)?;
+            unifier.unify(expr2, &Type::Int, &ty2)?;
+            let rule = TMinus(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, Type::Int))
+        }
+        Times(expr1, expr2) => {
+            let (rule1, ty1) = infer(unifier, names, env, expr1, depth + 1)?;
+            unifier.unify(expr1, &Type::Int, &ty1)?;
+            let (rule2, ty2) = infer(unifier, names, env, expr2, depth + 1)?;
+            unifier.unify(expr2, &Type::Int, &ty2)?;
+            let rule = TTimes(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, Type::Int))
+        }
+        Lt(expr1, expr2) => {
+            let (rule1, ty1) = infer(unifier, names, env, expr1, depth + 1)?;
+            unifier.unify(expr1, &Type::Int, &ty1)?;
+            let (rule2, ty2) = infer(unifier, names, env, expr2, depth + 1)?;
+            unifier.unify(expr2, &Type::Int, &ty2)?;
+            let rule = TLt(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, Type::Bool))
+        }
+        Var(_) => {
+            let scheme = env
+                .get(expr)
+                .ok_or_else(|| TypeError::Unbound(expr.clone()))?;
+            let ty = instantiate(unifier, &scheme);
+            Ok((TVar(env.clone(), expr.clone(), ty.clone(), depth), ty))
+        }
+        Let(expr1, expr2, expr3) => {
+            let (rule1, ty1) = infer(unifier, names, env, expr2, depth + 1)?;
+            let scheme = generalize(unifier, names, env, &ty1);
+            let mut new_env = env.clone();
+            new_env.put(*expr1.clone(), scheme);
+            let (rule2, ty2) = infer(unifier, names, &new_env, expr3, depth + 1)?;
+            let rule = TLet(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, ty2))
+        }
+        Fun(expr1, expr2) => {
+            let ty1 = unifier.fresh();
+            let mut new_env = env.clone();
+            new_env.put(*expr1.clone(), Scheme::mono(ty1.clone()));
+            let (rule, ty2) = infer(unifier, names, &new_env, expr2, depth + 1)?;
+            let rule = TFun(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                ty1.clone(),
+                Box::new(rule),
+                depth,
+            );
+            Ok((rule, Type::Fun(Box::new(ty1), Box::new(ty2))))
+        }
+        App(expr1, expr2) => {
+            let (rule1, ty1) = infer(unifier, names, env, expr1, depth + 1)?;
+            let (rule2, ty2) = infer(unifier, names, env, expr2, depth + 1)?;
+            let ty3 = unifier.fresh();
+            let ty4 = Type::Fun(Box::new(ty2), Box::new(ty3.clone()));
+            unifier.unify(expr1, &ty4, &ty1)?;
+            let rule = TApp(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, ty3))
+        }
+        LetRec(expr1, expr2, expr3, expr4) => {
+            let ty1 = unifier.fresh();
+            let ty2 = unifier.fresh();
+            let ty3 = Type::Fun(Box::new(ty1.clone()), Box::new(ty2.clone()));
+            let mut new_env1 = env.clone();
+            new_env1.put(*expr1.clone(), Scheme::mono(ty3.clone()));
+            new_env1.put(*expr2.clone(), Scheme::mono(ty1));
+            let (rule1, ty4) = infer(unifier, names, &new_env1, expr3, depth + 1)?;
+            unifier.unify(expr3, &ty2, &ty4)?;
+            let scheme = generalize(unifier, names, env, &ty3);
+            let mut new_env2 = env.clone();
+            new_env2.put(*expr1.clone(), scheme);
+            let (rule2, ty5) = infer(unifier, names, &new_env2, expr4, depth + 1)?;
+            let rule = TLetRec(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                *expr4.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, ty5))
+        }
+        Nil => {
+            let ty = Type::List(Box::new(unifier.fresh()));
+            Ok((TNil(env.clone(), ty.clone(), depth), ty))
+        }
+        Cons(expr1, expr2) => {
+            let (rule1, ty1) = infer(unifier, names, env, expr1, depth + 1)?;
+            let (rule2, ty2) = infer(unifier, names, env, expr2, depth + 1)?;
+            let ty3 = Type::List(Box::new(ty1));
+            unifier.unify(expr2, &ty3, &ty2)?;
+            let rule = TCons(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, ty3))
+        }
+        Match(expr1, expr2, expr3, expr4, expr5) => {
+            let (rule1, ty1) = infer(unifier, names, env, expr1, depth + 1)?;
+            let ty2 = unifier.fresh();
+            let ty3 = Type::List(Box::new(ty2.clone()));
+            unifier.unify(expr1, &ty3, &ty1)?;
+            let (rule2, ty4) = infer(unifier, names, env, expr2, depth + 1)?;
+            let mut new_env = env.clone();
+            new_env.put(*expr3.clone(), Scheme::mono(ty2));
+            new_env.put(*expr4.clone(), Scheme::mono(ty3));
+            let (rule3, ty5) = infer(unifier, names, &new_env, expr5, depth + 1)?;
+            unifier.unify(expr5, &ty4, &ty5)?;
+            let rule = TMatch(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                *expr4.clone(),
+                *expr5.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            );
+            Ok((rule, ty4))
+        }
+    }
+}
+
+// 型スキームの型パラメータを新しい型変数で置き換える
+fn instantiate(unifier: &mut Unifier, scheme: &Scheme) -> Type {
+    let vars = scheme
+        .params()
+        .iter()
+        .map(|s| (s.clone(), unifier.fresh()))
+        .collect::<Vec<_>>();
+    substitute(scheme.body(), &vars)
+}
+
+fn substitute(ty: &Type, vars: &[(String, Type)]) -> Type {
+    match ty {
+        Type::List(ty) => Type::List(Box::new(substitute(ty, vars))),
+        Type::Fun(ty1, ty2) => Type::Fun(
+            Box::new(substitute(ty1, vars)),
+            Box::new(substitute(ty2, vars)),
+        ),
+        Type::Param(s) => match vars.iter().find(|(s1, _)| s1 == s) {
+            Some((_, ty)) => ty.clone(),
+            None => ty.clone(),
+        },
+        _ => ty.clone(),
+    }
+}
+
+// 環境に現れない型変数を型パラメータにして型スキームを作る
+fn generalize(unifier: &mut Unifier, names: &mut Vec<String>, env: &Env, ty: &Type) -> Scheme {
+    let env_vars = env
+        .schemes()
+        .iter()
+        .flat_map(|scheme| unifier.resolve(scheme.body()).vars())
+        .collect::<Vec<_>>();
+    let mut params = vec![];
+    for n in unifier.resolve(ty).vars() {
+        if env_vars.contains(&n) || unifier.resolve(&Type::Var(n)) != Type::Var(n) {
+            continue;
+        }
+        let name = fresh_name(names);
+        unifier.bind(n, Type::Param(name.clone()));
+        params.push(name);
+    }
+    Scheme::new(params, unifier.resolve(ty))
+}
+
+fn fresh_name(names: &mut Vec<String>) -> String {
+    let name = (0..)
+        .map(|i| {
+            let c = (b'a' + (i % 26) as u8) as char;
+            match i / 26 {
+                0 => c.to_string(),
+                j => format!("{}{}", c, j),
+            }
+        })
+        .find(|name| !names.contains(name))
+        .unwrap();
+    names.push(name.clone());
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::poly_typing_ml4::{parser::parse, solver::solve};
+
+    #[test]
+    fn test_solve1() {
+        let (env, expr, ty) = parse("|- let id = fun x -> x in id id : bool -> bool")
+            .unwrap()
+            .1;
+        assert_eq!(
+            solve(&env, &expr, &ty, 0).unwrap().to_string(),
+            "|- let id = fun x -> x in id id : bool -> bool by T-Let {
+    |- fun x -> x : 'a -> 'a by T-Fun {
+        x : 'a |- x : 'a by T-Var {}
+    };
+    id : 'a.'a -> 'a |- id id : bool -> bool by T-App {
+        id : 'a.'a -> 'a |- id : (bool -> bool) -> bool -> bool by T-Var {};
+        id : 'a.'a -> 'a |- id : bool -> bool by T-Var {}
+    }
+}"
+        );
+    }
+
+    #[test]
+    fn test_solve2() {
+        let (env, expr, ty) = parse("f : 'a.'a -> 'a |- f 3 : int").unwrap().1;
+        assert_eq!(
+            solve(&env, &expr, &ty, 0).unwrap().to_string(),
+            "f : 'a.'a -> 'a |- f 3 : int by T-App {
+    f : 'a.'a -> 'a |- f : int -> int by T-Var {};
+    f : 'a.'a -> 'a |- 3 : int by T-Int {}
+}"
+        );
+    }
+
+    #[test]
+    fn test_solve3() {
+        let (env, expr, ty) = parse(
+            "|- let rec map = fun f -> fun l -> match l with [] -> [] | x :: y -> f x :: map f y in map (fun x -> x < 3) (map (fun x -> x * 2) (4 :: 5 :: 1 :: [])) : bool list",
+        )
+        .unwrap()
+        .1;
+        let rule = solve(&env, &expr, &ty, 0).unwrap().to_string();
+        assert!(rule
+            .lines()
+            .any(|line| line.starts_with("    map : 'a 'b.('a -> 'b) -> 'a list -> 'b list |- ")));
+    }
+
+    #[test]
+    fn test_solve4() {
+        // 入力に現れる型パラメータとは別の名前で一般化する
+        let (env, expr, ty) = parse("x : 'a |- let f = fun y -> y in f x : 'a").unwrap().1;
+        assert_eq!(
+            solve(&env, &expr, &ty, 0)
+                .unwrap()
+                .to_string()
+                .lines()
+                .nth(4)
+                .unwrap(),
+            "    x : 'a, f : 'b.'b -> 'b |- f x : 'a by T-App {"
+        );
+    }
+
+    #[test]
+    fn test_solve5() {
+        let (env, expr, ty) = parse("|- let f = fun x -> x in f 1 + f true : int")
+            .unwrap()
+            .1;
+        assert_eq!(
+            solve(&env, &expr, &ty, 0).unwrap_err().to_string(),
+            "type error at `f true`: expected int, found bool"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{poly_typing_ml4::ast::Type, typing_ml4::ast::Expr};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypeError {
+    Mismatch(Expr, Type, Type),
+    Unbound(Expr),
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::poly_typing_ml4::unifier::TypeError::*;
+
+        match self {
+            Mismatch(expr, expected, found) => write!(
+                f,
+                "type error at `{}`: expected {}, found {}",
+                expr, expected, found
+            ),
+            Unbound(expr) => write!(f, "unbound variable `{}`", expr),
+        }
+    }
+}
+
+// 型変数の代入 (添字が型変数の番号)
+#[derive(Clone, Debug, Default)]
+pub struct Unifier(Vec<Option<Type>>);
+
+impl Unifier {
+    pub fn new() -> Unifier {
+        Unifier(vec![])
+    }
+
+    pub fn fresh(&mut self) -> Type {
+        self.0.push(None);
+        Type::Var(self.0.len() - 1)
+    }
+
+    pub fn unify(&mut self, expr: &Expr, expected: &Type, found: &Type) -> Result<(), TypeError> {
+        if self.unify_types(expected, found) {
+            Ok(())
+        } else {
+            Err(TypeError::Mismatch(
+                expr.clone(),
+                self.resolve(expected),
+                self.resolve(found),
+            ))
+        }
+    }
+
+    // 型変数を型パラメータに固定する (let での一般化に使う)
+    pub fn bind(&mut self, n: usize, ty: Type) {
+        self.0[n] = Some(ty)
+    }
+
+    pub fn resolve(&self, ty: &Type) -> Type {
+        match ty {
+            Type::Int => Type::Int,
+            Type::Bool => Type::Bool,
+            Type::Param(s) => Type::Param(s.clone()),
+            Type::List(ty) => Type::List(Box::new(self.resolve(ty))),
+            Type::Fun(ty1, ty2) => {
+                Type::Fun(Box::new(self.resolve(ty1)), Box::new(self.resolve(ty2)))
+            }
+            Type::Var(n) => match &self.0[*n] {
+                Some(ty) => self.resolve(ty),
+                None => Type::Var(*n),
+            },
+        }
+    }
+
+    // 制約の残らなかった型変数は int にする
+    pub fn default(&self, ty: &Type) -> Type {
+        match self.resolve(ty) {
+            Type::List(ty) => Type::List(Box::new(self.default(&ty))),
+            Type::Fun(ty1, ty2) => {
+                Type::Fun(Box::new(self.default(&ty1)), Box::new(self.default(&ty2)))
+            }
+            Type::Var(_) => Type::Int,
+            ty => ty,
+        }
+    }
+
+    fn unify_types(&mut self, ty1: &Type, ty2: &Type) -> bool {
+        match (self.shallow(ty1), self.shallow(ty2)) {
+            (Type::Var(n1), Type::Var(n2)) if n1 == n2 => true,
+            (Type::Var(n), ty) | (ty, Type::Var(n)) => {
+                if self.occurs(n, &ty) {
+                    false
+                } else {
+                    self.0[n] = Some(ty);
+                    true
+                }
+            }
+            (Type::Int, Type::Int) | (Type::Bool, Type::Bool) => true,
+            (Type::Param(s1), Type::Param(s2)) => s1 == s2,
+            (Type::List(ty1), Type::List(ty2)) => self.unify_types(&ty1, &ty2),
+            (Type::Fun(ty1, ty2), Type::Fun(ty3, ty4)) => {
+                self.unify_types(&ty1, &ty3) && self.unify_types(&ty2, &ty4)
+            }
+            _ => false,
+        }
+    }
+
+    fn shallow(&self, ty: &Type) -> Type {
+        match ty {
+            Type::Var(n) => match &self.0[*n] {
+                Some(ty) => self.shallow(ty),
+                None => ty.clone(),
+            },
+            _ => ty.clone(),
+        }
+    }
+
+    fn occurs(&self, n: usize, ty: &Type) -> bool {
+        match self.shallow(ty) {
+            Type::Int | Type::Bool | Type::Param(_) => false,
+            Type::List(ty) => self.occurs(n, &ty),
+            Type::Fun(ty1, ty2) => self.occurs(n, &ty1) || self.occurs(n, &ty2),
+            Type::Var(n1) => n == n1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        poly_typing_ml4::{
+            ast::Type,
+            unifier::{TypeError, Unifier},
+        },
+        typing_ml4::ast::Expr::*,
+    };
+
+    #[test]
+    fn test_unify1() {
+        let mut unifier = Unifier::new();
+        let ty1 = unifier.fresh();
+        let ty2 = Type::Fun(Box::new(ty1.clone()), Box::new(ty1.clone()));
+        unifier.bind(0, Type::Param("a".to_string()));
+        assert_eq!(
+            unifier.resolve(&ty2),
+            Type::Fun(
+                Box::new(Type::Param("a".to_string())),
+                Box::new(Type::Param("a".to_string()))
+            )
+        );
+    }
+
+    #[test]
+    fn test_unify2() {
+        let mut unifier = Unifier::new();
+        let ty1 = Type::Param("a".to_string());
+        assert_eq!(
+            unifier.unify(&Var("x".to_string()), &ty1, &Type::Int),
+            Err(TypeError::Mismatch(Var("x".to_string()), ty1, Type::Int))
+        );
+    }
+}
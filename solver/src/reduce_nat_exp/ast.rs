@@ -0,0 +1,21 @@
+use crate::eval_nat_exp::ast::Expr;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Judgement {
+    Reduce(Expr, Expr),
+    DReduce(Expr, Expr),
+    MReduce(Expr, Expr),
+}
+
+impl fmt::Display for Judgement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::reduce_nat_exp::ast::Judgement::*;
+
+        match self {
+            Reduce(expr1, expr2) => write!(f, "{} ---> {}", expr1, expr2),
+            DReduce(expr1, expr2) => write!(f, "{} -d-> {}", expr1, expr2),
+            MReduce(expr1, expr2) => write!(f, "{} -*-> {}", expr1, expr2),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    eval_nat_exp::parser::parse_expr,
+    reduce_nat_exp::ast::{Judgement, Judgement::*},
+    util::ws,
+};
+use nom::{branch::alt, bytes::complete::tag, sequence::tuple, IResult};
+
+// <judgement> ::= <expr> ( '--->' | '-d->' | '-*->' ) <expr>
+
+pub fn parse(input: &str) -> IResult<&str, Judgement> {
+    let parse_arrow = alt((ws(tag("--->")), ws(tag("-d->")), ws(tag("-*->"))));
+    let (input, (expr1, arrow, expr2)) = tuple((parse_expr, parse_arrow, parse_expr))(input)?;
+    let judgement = match arrow {
+        "--->" => Reduce(expr1, expr2),
+        "-d->" => DReduce(expr1, expr2),
+        "-*->" => MReduce(expr1, expr2),
+        _ => unreachable!(),
+    };
+    Ok((input, judgement))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eval_nat_exp::ast::Expr::*,
+        nat::ast::Nat::{S, Z},
+        reduce_nat_exp::{ast::Judgement::*, parser::parse},
+    };
+
+    #[test]
+    fn test_parse1() {
+        assert_eq!(
+            parse("Z + S(S(Z)) -*-> S(S(Z))").unwrap().1,
+            MReduce(
+                Plus(Box::new(Nat(Z)), Box::new(Nat(S(Box::new(S(Box::new(Z))))))),
+                Nat(S(Box::new(S(Box::new(Z)))))
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(
+            parse("S(Z) * S(Z) + Z -d-> S(Z) + Z").unwrap().1,
+            DReduce(
+                Plus(
+                    Box::new(Times(
+                        Box::new(Nat(S(Box::new(Z)))),
+                        Box::new(Nat(S(Box::new(Z))))
+                    )),
+                    Box::new(Nat(Z))
+                ),
+                Plus(Box::new(Nat(S(Box::new(Z)))), Box::new(Nat(Z)))
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse3() {
+        assert_eq!(
+            parse("Z * S(Z) ---> Z").unwrap().1,
+            Reduce(
+                Times(Box::new(Nat(Z)), Box::new(Nat(S(Box::new(Z))))),
+                Nat(Z)
+            )
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{
+    eval_nat_exp::ast::{Expr, Expr::*},
+    nat::{ast::Nat, rule::Rule as NatRule},
+    util::ident,
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    RPlus(Nat, Nat, Box<NatRule>, usize),
+    RTimes(Nat, Nat, Box<NatRule>, usize),
+    RPlusL(Expr, Expr, Box<Rule>, usize),
+    RPlusR(Expr, Expr, Box<Rule>, usize),
+    RTimesL(Expr, Expr, Box<Rule>, usize),
+    RTimesR(Expr, Expr, Box<Rule>, usize),
+    DRPlus(Nat, Nat, Box<NatRule>, usize),
+    DRTimes(Nat, Nat, Box<NatRule>, usize),
+    DRPlusL(Expr, Expr, Box<Rule>, usize),
+    DRPlusR(Nat, Expr, Box<Rule>, usize),
+    DRTimesL(Expr, Expr, Box<Rule>, usize),
+    DRTimesR(Nat, Expr, Box<Rule>, usize),
+    MRZero(Expr, usize),
+    MRMulti(Expr, Box<Rule>, Box<Rule>, usize),
+    MROne(Expr, Box<Rule>, usize),
+}
+
+impl Rule {
+    pub fn reduced(&self) -> Expr {
+        use crate::reduce_nat_exp::rule::Rule::*;
+
+        match self {
+            RPlus(_, _, rule, _) => Nat(rule.evaluated()),
+            RTimes(_, _, rule, _) => Nat(rule.evaluated()),
+            RPlusL(_, expr2, rule, _) => Plus(Box::new(rule.reduced()), Box::new(expr2.clone())),
+            RPlusR(expr1, _, rule, _) => Plus(Box::new(expr1.clone()), Box::new(rule.reduced())),
+            RTimesL(_, expr2, rule, _) => Times(Box::new(rule.reduced()), Box::new(expr2.clone())),
+            RTimesR(expr1, _, rule, _) => Times(Box::new(expr1.clone()), Box::new(rule.reduced())),
+            DRPlus(_, _, rule, _) => Nat(rule.evaluated()),
+            DRTimes(_, _, rule, _) => Nat(rule.evaluated()),
+            DRPlusL(_, expr2, rule, _) => Plus(Box::new(rule.reduced()), Box::new(expr2.clone())),
+            DRPlusR(nat1, _, rule, _) => {
+                Plus(Box::new(Nat(nat1.clone())), Box::new(rule.reduced()))
+            }
+            DRTimesL(_, expr2, rule, _) => Times(Box::new(rule.reduced()), Box::new(expr2.clone())),
+            DRTimesR(nat1, _, rule, _) => {
+                Times(Box::new(Nat(nat1.clone())), Box::new(rule.reduced()))
+            }
+            MRZero(expr, _) => expr.clone(),
+            MRMulti(_, _, rule2, _) => rule2.reduced(),
+            MROne(_, rule, _) => rule.reduced(),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::reduce_nat_exp::rule::Rule::*;
+
+        match self {
+            RPlus(nat1, nat2, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} + {} ---> {} by R-Plus {{",
+                    ident(*depth),
+                    nat1,
+                    nat2,
+                    self.reduced()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            RTimes(nat1, nat2, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} * {} ---> {} by R-Times {{",
+                    ident(*depth),
+                    nat1,
+                    nat2,
+                    self.reduced()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            RPlusL(expr1, expr2, rule, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} ---> {} by R-PlusL {{",
+                    ident(*depth),
+                    expr,
+                    self.reduced()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            RPlusR(expr1, expr2, rule, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} ---> {} by R-PlusR {{",
+                    ident(*depth),
+                    expr,
+                    self.reduced()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            RTimesL(expr1, expr2, rule, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} ---> {} by R-TimesL {{",
+                    ident(*depth),
+                    expr,
+                    self.reduced()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            RTimesR(expr1, expr2, rule, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} ---> {} by R-TimesR {{",
+                    ident(*depth),
+                    expr,
+                    self.reduced()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            DRPlus(nat1, nat2, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} + {} -d-> {} by DR-Plus {{",
+                    ident(*depth),
+                    nat1,
+                    nat2,
+                    self.reduced()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            DRTimes(nat1, nat2, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} * {} -d-> {} by DR-Times {{",
+                    ident(*depth),
+                    nat1,
+                    nat2,
+                    self.reduced()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            DRPlusL(expr1, expr2, rule, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} -d-> {} by DR-PlusL {{",
+                    ident(*depth),
+                    expr,
+                    self.reduced()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            DRPlusR(nat1, expr2, rule, depth) => {
+                let expr = Plus(Box::new(Nat(nat1.clone())), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} -d-> {} by DR-PlusR {{",
+                    ident(*depth),
+                    expr,
+                    self.reduced()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            DRTimesL(expr1, expr2, rule, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} -d-> {} by DR-TimesL {{",
+                    ident(*depth),
+                    expr,
+                    self.reduced()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            DRTimesR(nat1, expr2, rule, depth) => {
+                let expr = Times(Box::new(Nat(nat1.clone())), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{} -d-> {} by DR-TimesR {{",
+                    ident(*depth),
+                    expr,
+                    self.reduced()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            MRZero(expr, depth) => {
+                write!(f, "{}{} -*-> {} by MR-Zero {{}}", ident(*depth), expr, expr)
+            }
+            MRMulti(expr, rule1, rule2, depth) => {
+                writeln!(
+                    f,
+                    "{}{} -*-> {} by MR-Multi {{",
+                    ident(*depth),
+                    expr,
+                    self.reduced()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            MROne(expr, rule, depth) => {
+                writeln!(
+                    f,
+                    "{}{} -*-> {} by MR-One {{",
+                    ident(*depth),
+                    expr,
+                    self.reduced()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+        }
+    }
+}
@@ -0,0 +1,399 @@
+use crate::{
+    eval_nat_exp::{
+        ast::{Expr, Expr::*},
+        evaluator::eval,
+    },
+    nat::solver::{solve_plus, solve_times},
+    reduce_nat_exp::{
+        ast::{Judgement, Judgement::*},
+        rule::{Rule, Rule::*},
+    },
+};
+
+pub fn solve(judgement: &Judgement, depth: usize) -> Option<Rule> {
+    match judgement {
+        Reduce(expr1, expr2) => solve_reduce(expr1, expr2, depth),
+        DReduce(expr1, expr2) => {
+            let rule = solve_dreduce(expr1, depth)?;
+            if rule.reduced() == *expr2 {
+                Some(rule)
+            } else {
+                None
+            }
+        }
+        MReduce(expr1, expr2) => {
+            let path = find_path(expr1, expr2, &mut vec![])?;
+            Some(solve_mreduce(&path, depth))
+        }
+    }
+}
+
+fn solve_reduce(expr1: &Expr, expr2: &Expr, depth: usize) -> Option<Rule> {
+    match (expr1, expr2) {
+        (Plus(expr11, expr12), _) => match (expr11.as_ref(), expr12.as_ref(), expr2) {
+            (Nat(nat1), Nat(nat2), Nat(nat3)) => {
+                let rule = solve_plus(nat1, nat2, depth + 1);
+                if rule.evaluated() == *nat3 {
+                    Some(RPlus(nat1.clone(), nat2.clone(), Box::new(rule), depth))
+                } else {
+                    None
+                }
+            }
+            (_, _, Plus(expr21, expr22)) if expr12 == expr22 => {
+                let rule = solve_reduce(expr11, expr21, depth + 1)?;
+                Some(RPlusL(
+                    *expr11.clone(),
+                    *expr12.clone(),
+                    Box::new(rule),
+                    depth,
+                ))
+            }
+            (_, _, Plus(expr21, expr22)) if expr11 == expr21 => {
+                let rule = solve_reduce(expr12, expr22, depth + 1)?;
+                Some(RPlusR(
+                    *expr11.clone(),
+                    *expr12.clone(),
+                    Box::new(rule),
+                    depth,
+                ))
+            }
+            _ => None,
+        },
+        (Times(expr11, expr12), _) => match (expr11.as_ref(), expr12.as_ref(), expr2) {
+            (Nat(nat1), Nat(nat2), Nat(nat3)) => {
+                let rule = solve_times(nat1, nat2, depth + 1);
+                if rule.evaluated() == *nat3 {
+                    Some(RTimes(nat1.clone(), nat2.clone(), Box::new(rule), depth))
+                } else {
+                    None
+                }
+            }
+            (_, _, Times(expr21, expr22)) if expr12 == expr22 => {
+                let rule = solve_reduce(expr11, expr21, depth + 1)?;
+                Some(RTimesL(
+                    *expr11.clone(),
+                    *expr12.clone(),
+                    Box::new(rule),
+                    depth,
+                ))
+            }
+            (_, _, Times(expr21, expr22)) if expr11 == expr21 => {
+                let rule = solve_reduce(expr12, expr22, depth + 1)?;
+                Some(RTimesR(
+                    *expr11.clone(),
+                    *expr12.clone(),
+                    Box::new(rule),
+                    depth,
+                ))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn solve_dreduce(expr: &Expr, depth: usize) -> Option<Rule> {
+    match expr {
+        Nat(_) => None,
+        Plus(expr1, expr2) => match (expr1.as_ref(), expr2.as_ref()) {
+            (Nat(nat1), Nat(nat2)) => {
+                let rule = solve_plus(nat1, nat2, depth + 1);
+                Some(DRPlus(nat1.clone(), nat2.clone(), Box::new(rule), depth))
+            }
+            (Nat(nat1), _) => {
+                let rule = solve_dreduce(expr2, depth + 1)?;
+                Some(DRPlusR(nat1.clone(), *expr2.clone(), Box::new(rule), depth))
+            }
+            _ => {
+                let rule = solve_dreduce(expr1, depth + 1)?;
+                Some(DRPlusL(
+                    *expr1.clone(),
+                    *expr2.clone(),
+                    Box::new(rule),
+                    depth,
+                ))
+            }
+        },
+        Times(expr1, expr2) => match (expr1.as_ref(), expr2.as_ref()) {
+            (Nat(nat1), Nat(nat2)) => {
+                let rule = solve_times(nat1, nat2, depth + 1);
+                Some(DRTimes(nat1.clone(), nat2.clone(), Box::new(rule), depth))
+            }
+            (Nat(nat1), _) => {
+                let rule = solve_dreduce(expr2, depth + 1)?;
+                Some(DRTimesR(
+                    nat1.clone(),
+                    *expr2.clone(),
+                    Box::new(rule),
+                    depth,
+                ))
+            }
+            _ => {
+                let rule = solve_dreduce(expr1, depth + 1)?;
+                Some(DRTimesL(
+                    *expr1.clone(),
+                    *expr2.clone(),
+                    Box::new(rule),
+                    depth,
+                ))
+            }
+        },
+    }
+}
+
+fn solve_mreduce(path: &[Expr], depth: usize) -> Rule {
+    match path {
+        [expr] => MRZero(expr.clone(), depth),
+        [expr1, expr2] => {
+            let rule = solve_reduce(expr1, expr2, depth + 1).unwrap();
+            MROne(expr1.clone(), Box::new(rule), depth)
+        }
+        [expr1, expr2, ..] => {
+            let rule = solve_reduce(expr1, expr2, depth + 2).unwrap();
+            let rule1 = MROne(expr1.clone(), Box::new(rule), depth + 1);
+            let rule2 = solve_mreduce(&path[1..], depth + 1);
+            MRMulti(expr1.clone(), Box::new(rule1), Box::new(rule2), depth)
+        }
+        [] => unreachable!(),
+    }
+}
+
+fn find_path(expr1: &Expr, expr2: &Expr, visited: &mut Vec<Expr>) -> Option<Vec<Expr>> {
+    if expr1 == expr2 {
+        return Some(vec![expr1.clone()]);
+    }
+    if visited.contains(expr1) {
+        return None;
+    }
+    visited.push(expr1.clone());
+    reducts(expr1).iter().find_map(|expr| {
+        let mut path = find_path(expr, expr2, visited)?;
+        path.insert(0, expr1.clone());
+        Some(path)
+    })
+}
+
+fn reducts(expr: &Expr) -> Vec<Expr> {
+    let mut exprs = vec![];
+    match expr {
+        Nat(_) => {}
+        Plus(expr1, expr2) | Times(expr1, expr2) => {
+            if let (Nat(_), Nat(_)) = (expr1.as_ref(), expr2.as_ref()) {
+                exprs.push(Nat(eval(expr)));
+            }
+            for expr1 in reducts(expr1) {
+                exprs.push(rebuild(expr, expr1, *expr2.clone()));
+            }
+            for expr2 in reducts(expr2) {
+                exprs.push(rebuild(expr, *expr1.clone(), expr2));
+            }
+        }
+    }
+    exprs
+}
+
+fn rebuild(expr: &Expr, expr1: Expr, expr2: Expr) -> Expr {
+    match expr {
+        Plus(_, _) => Plus(Box::new(expr1), Box::new(expr2)),
+        Times(_, _) => Times(Box::new(expr1), Box::new(expr2)),
+        Nat(_) => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eval_nat_exp::ast::Expr::*,
+        nat::{
+            ast::Nat::{S, Z},
+            rule::Rule::{PSucc, PZero, TSucc, TZero},
+        },
+        reduce_nat_exp::{ast::Judgement::*, rule::Rule::*, solver::solve},
+    };
+
+    #[test]
+    fn test_solve1() {
+        assert_eq!(
+            solve(
+                &MReduce(
+                    Plus(Box::new(Nat(Z)), Box::new(Nat(S(Box::new(S(Box::new(Z))))))),
+                    Nat(S(Box::new(S(Box::new(Z)))))
+                ),
+                0
+            ),
+            Some(MROne(
+                Plus(Box::new(Nat(Z)), Box::new(Nat(S(Box::new(S(Box::new(Z))))))),
+                Box::new(RPlus(
+                    Z,
+                    S(Box::new(S(Box::new(Z)))),
+                    Box::new(PZero(S(Box::new(S(Box::new(Z)))), 2)),
+                    1
+                )),
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve2() {
+        assert_eq!(
+            solve(
+                &Reduce(
+                    Plus(
+                        Box::new(Times(Box::new(Nat(S(Box::new(Z)))), Box::new(Nat(Z)))),
+                        Box::new(Times(Box::new(Nat(S(Box::new(Z)))), Box::new(Nat(Z))))
+                    ),
+                    Plus(
+                        Box::new(Times(Box::new(Nat(S(Box::new(Z)))), Box::new(Nat(Z)))),
+                        Box::new(Nat(Z))
+                    )
+                ),
+                0
+            ),
+            Some(RPlusR(
+                Times(Box::new(Nat(S(Box::new(Z)))), Box::new(Nat(Z))),
+                Times(Box::new(Nat(S(Box::new(Z)))), Box::new(Nat(Z))),
+                Box::new(RTimes(
+                    S(Box::new(Z)),
+                    Z,
+                    Box::new(TSucc(
+                        S(Box::new(Z)),
+                        Z,
+                        Box::new(TZero(Z, 3)),
+                        Box::new(PZero(Z, 3)),
+                        2
+                    )),
+                    1
+                )),
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve3() {
+        assert_eq!(
+            solve(
+                &DReduce(
+                    Plus(
+                        Box::new(Nat(Z)),
+                        Box::new(Plus(Box::new(Nat(S(Box::new(Z)))), Box::new(Nat(Z))))
+                    ),
+                    Plus(Box::new(Nat(Z)), Box::new(Nat(S(Box::new(Z)))))
+                ),
+                0
+            ),
+            Some(DRPlusR(
+                Z,
+                Plus(Box::new(Nat(S(Box::new(Z)))), Box::new(Nat(Z))),
+                Box::new(DRPlus(
+                    S(Box::new(Z)),
+                    Z,
+                    Box::new(PSucc(S(Box::new(Z)), Z, Box::new(PZero(Z, 3)), 2)),
+                    1
+                )),
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve4() {
+        // `-d->` always reduces the left operand first
+        assert_eq!(
+            solve(
+                &DReduce(
+                    Plus(
+                        Box::new(Times(Box::new(Nat(Z)), Box::new(Nat(Z)))),
+                        Box::new(Times(Box::new(Nat(Z)), Box::new(Nat(Z))))
+                    ),
+                    Plus(
+                        Box::new(Times(Box::new(Nat(Z)), Box::new(Nat(Z)))),
+                        Box::new(Nat(Z))
+                    )
+                ),
+                0
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_solve5() {
+        assert_eq!(
+            solve(
+                &Reduce(
+                    Plus(Box::new(Nat(Z)), Box::new(Nat(Z))),
+                    Nat(S(Box::new(Z)))
+                ),
+                0
+            ),
+            None
+        );
+        assert_eq!(
+            solve(
+                &Reduce(
+                    Plus(
+                        Box::new(Plus(Box::new(Nat(Z)), Box::new(Nat(Z)))),
+                        Box::new(Nat(Z))
+                    ),
+                    Nat(Z)
+                ),
+                0
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_solve6() {
+        assert_eq!(solve(&MReduce(Nat(Z), Nat(Z)), 0), Some(MRZero(Nat(Z), 0)));
+    }
+
+    #[test]
+    fn test_solve7() {
+        let one = || Box::new(Nat(S(Box::new(Z))));
+        let judgement = MReduce(
+            Plus(Box::new(Times(one(), one())), Box::new(Times(one(), one()))),
+            Nat(S(Box::new(S(Box::new(Z))))),
+        );
+        assert_eq!(
+            solve(&judgement, 0).unwrap().to_string(),
+            "S(Z) * S(Z) + S(Z) * S(Z) -*-> S(S(Z)) by MR-Multi {
+    S(Z) * S(Z) + S(Z) * S(Z) -*-> S(Z) + S(Z) * S(Z) by MR-One {
+        S(Z) * S(Z) + S(Z) * S(Z) ---> S(Z) + S(Z) * S(Z) by R-PlusL {
+            S(Z) * S(Z) ---> S(Z) by R-Times {
+                S(Z) times S(Z) is S(Z) by T-Succ {
+                    Z times S(Z) is Z by T-Zero {};
+                    S(Z) plus Z is S(Z) by P-Succ {
+                        Z plus Z is Z by P-Zero {}
+                    }
+                }
+            }
+        }
+    };
+    S(Z) + S(Z) * S(Z) -*-> S(S(Z)) by MR-Multi {
+        S(Z) + S(Z) * S(Z) -*-> S(Z) + S(Z) by MR-One {
+            S(Z) + S(Z) * S(Z) ---> S(Z) + S(Z) by R-PlusR {
+                S(Z) * S(Z) ---> S(Z) by R-Times {
+                    S(Z) times S(Z) is S(Z) by T-Succ {
+                        Z times S(Z) is Z by T-Zero {};
+                        S(Z) plus Z is S(Z) by P-Succ {
+                            Z plus Z is Z by P-Zero {}
+                        }
+                    }
+                }
+            }
+        };
+        S(Z) + S(Z) -*-> S(S(Z)) by MR-One {
+            S(Z) + S(Z) ---> S(S(Z)) by R-Plus {
+                S(Z) plus S(Z) is S(S(Z)) by P-Succ {
+                    Z plus S(Z) is S(Z) by P-Zero {}
+                }
+            }
+        }
+    }
+}"
+        );
+    }
+}
@@ -0,0 +1,416 @@
+use crate::{
+    checker::{ast::Derivation, parser::parse},
+    eval_ml2::{
+        self,
+        ast::{Env, Expr::*},
+        parser::parse_expr,
+    },
+    eval_ml2_ext,
+    syntax::{
+        error::complete,
+        lexer::{ext_tokens, identifier, tokens},
+    },
+    system::{solve, solve_auto, with_stack, System},
+};
+use std::collections::HashSet;
+
+const HELP: &str = "\
+<judgment>          導出を求める (前提は畳んだ状態で表示する)
+let <var> = <expr>  環境に変数を追加する
+:expand [<path>]    前提を展開する (省略時はすべて)
+:collapse [<path>]  前提を畳む (省略時はすべて)
+:show               現在の導出を表示する
+:history            入力した判断の一覧
+:env                現在の環境
+:reset              環境を空にする
+:system [<system>]  体系を指定する (auto で自動判定)
+:quit               終了する";
+
+// 環境を `x = 1, y = true |-` の形で判断の前に補える体系
+const ENV_SYSTEMS: [System; 7] = [
+    System::EvalML2,
+    System::EvalML2Ext,
+    System::EvalML3,
+    System::EvalML4,
+    System::EvalML5,
+    System::EvalContML4,
+    System::EvalRefML3,
+];
+
+// 導出の前提の位置 (1 始まりの番号を '.' でつないだもの)
+type Path = Vec<usize>;
+
+#[derive(Default)]
+pub struct Repl {
+    env: Env,
+    system: Option<System>,
+    history: Vec<String>,
+    derivation: Option<Derivation>,
+    expanded: HashSet<Path>,
+}
+
+impl Repl {
+    pub fn new() -> Repl {
+        Repl::default()
+    }
+
+    // 1 行を処理して表示する文字列を返す (終了するときは None)
+    pub fn eval(&mut self, line: &str) -> Option<String> {
+        let line = line.trim();
+        let (command, arg) = match line.find(' ') {
+            Some(i) => (&line[..i], line[i + 1..].trim()),
+            None => (line, ""),
+        };
+        let output = match command {
+            "" => String::new(),
+            ":q" | ":quit" => return None,
+            ":h" | ":help" => HELP.to_string(),
+            ":expand" => self.expand(arg),
+            ":collapse" => self.collapse(arg),
+            ":show" => self.show(),
+            ":history" => self.show_history(),
+            ":env" => self.env.to_string(),
+            ":reset" => {
+                self.env = Env::new();
+                String::new()
+            }
+            ":system" => self.set_system(arg),
+            _ if command.starts_with(':') => format!("unknown command `{}`", command),
+            _ => match self.define(line) {
+                Some(output) => output,
+                None => self.judge(line),
+            },
+        };
+        Some(output)
+    }
+
+    // let <var> = <expr> (in がないもの) は環境に追加する
+    fn define(&mut self, line: &str) -> Option<String> {
+        let rest = line.strip_prefix("let ")?;
+        let (var, expr) = rest.split_at(rest.find('=')?);
+        let var = var.trim();
+        if self.system == Some(System::EvalML2Ext) {
+            return self.define_ext(line, var, &expr[1..]);
+        }
+        if complete(var, tokens(identifier)).is_err() {
+            return None;
+        }
+        let expr = complete(&expr[1..], parse_expr).ok()?;
+        self.history.push(line.to_string());
+        let env = self.env.clone();
+        let value = match with_stack(|| eval_ml2::solver::solve(&env, &expr, 0)) {
+            Ok(rule) => rule.evaluated(),
+            Err(e) => return Some(e.to_string()),
+        };
+        self.env.put(Var(var.to_string()), value.clone());
+        Some(format!("{} = {}", var, value))
+    }
+
+    // 拡張体系では環境ごと EvalML2Ext の判断として読み直して評価する
+    fn define_ext(&mut self, line: &str, var: &str, expr: &str) -> Option<String> {
+        use crate::eval_ml2_ext::ast::Expr as ExtExpr;
+
+        if complete(var, ext_tokens(identifier)).is_err() {
+            return None;
+        }
+        let judgment = format!("{} |- {}", self.env, expr);
+        let (env, expr) = eval_ml2_ext::parser::parse(&judgment).ok()?;
+        self.history.push(line.to_string());
+        let value = match with_stack(|| eval_ml2_ext::solver::solve(&env, &expr, 0)) {
+            Ok(rule) => match rule.evaluated() {
+                ExtExpr::Int(i) => Int(i),
+                ExtExpr::Bool(b) => Bool(b),
+                _ => return Some(format!("cannot evaluate `{}`", expr)),
+            },
+            Err(e) => return Some(e.to_string()),
+        };
+        self.env.put(Var(var.to_string()), value.clone());
+        Some(format!("{} = {}", var, value))
+    }
+
+    fn judge(&mut self, line: &str) -> String {
+        self.history.push(line.to_string());
+        // 環境が空でなければ環境を持つ体系の判断として環境を補う
+        let with_env = match self.system {
+            Some(system) => ENV_SYSTEMS.contains(&system),
+            None => true,
+        };
+        let judgment = if with_env && !self.env.is_empty() && !line.contains("|-") {
+            format!("{} |- {}", self.env, line)
+        } else {
+            line.to_string()
+        };
+        let result = match self.system {
+            Some(system) => solve(system, &judgment).map(|rule| (system, rule)),
+            None => solve_auto(&judgment),
+        };
+        let (system, rule) = match result {
+            Ok(result) => result,
+            Err(e) => return e.to_string(),
+        };
+        match parse(&rule) {
+            Ok((_, derivation)) => {
+                self.derivation = Some(derivation);
+                self.expanded = HashSet::new();
+                self.expanded.insert(vec![]);
+                format!("({})\n{}", system, self.show())
+            }
+            Err(_) => rule,
+        }
+    }
+
+    fn expand(&mut self, arg: &str) -> String {
+        let derivation = match &self.derivation {
+            Some(derivation) => derivation,
+            None => return "no derivation".to_string(),
+        };
+        if arg.is_empty() {
+            let mut paths = vec![];
+            collect_paths(derivation, &mut vec![], &mut paths);
+            self.expanded.extend(paths);
+            return self.show();
+        }
+        match parse_path(derivation, arg) {
+            Some(path) => {
+                // 親もすべて展開する
+                for i in 0..=path.len() {
+                    self.expanded.insert(path[..i].to_vec());
+                }
+                self.show()
+            }
+            None => format!("no premise `{}`", arg),
+        }
+    }
+
+    fn collapse(&mut self, arg: &str) -> String {
+        let derivation = match &self.derivation {
+            Some(derivation) => derivation,
+            None => return "no derivation".to_string(),
+        };
+        if arg.is_empty() {
+            self.expanded.clear();
+            return self.show();
+        }
+        match parse_path(derivation, arg) {
+            Some(path) => {
+                self.expanded.retain(|p| !p.starts_with(&path));
+                self.show()
+            }
+            None => format!("no premise `{}`", arg),
+        }
+    }
+
+    fn show(&self) -> String {
+        match &self.derivation {
+            Some(derivation) => {
+                let mut output = String::new();
+                self.render(derivation, &mut vec![], &mut output);
+                output
+            }
+            None => "no derivation".to_string(),
+        }
+    }
+
+    fn render(&self, derivation: &Derivation, path: &mut Path, output: &mut String) {
+        let indent = "    ".repeat(path.len());
+        let label = if path.is_empty() {
+            String::new()
+        } else {
+            let path = path.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+            format!("[{}] ", path.join("."))
+        };
+        output.push_str(&format!(
+            "{}{}{} by {}",
+            indent, label, derivation.judgment, derivation.rule
+        ));
+        if derivation.premises.is_empty() {
+            output.push_str(" {}");
+        } else if self.expanded.contains(path) {
+            output.push_str(" {");
+            for (i, premise) in derivation.premises.iter().enumerate() {
+                output.push('\n');
+                path.push(i + 1);
+                self.render(premise, path, output);
+                path.pop();
+            }
+            output.push_str(&format!("\n{}}}", indent));
+        } else {
+            output.push_str(" { ... }");
+        }
+    }
+
+    fn show_history(&self) -> String {
+        self.history
+            .iter()
+            .enumerate()
+            .map(|(i, line)| format!("{:>3}  {}", i + 1, line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn set_system(&mut self, arg: &str) -> String {
+        match arg {
+            "" => self
+                .system
+                .map_or("auto".to_string(), |system| system.to_string()),
+            "auto" => {
+                self.system = None;
+                String::new()
+            }
+            _ => match arg.parse::<System>() {
+                Ok(system) => {
+                    self.system = Some(system);
+                    String::new()
+                }
+                Err(e) => e,
+            },
+        }
+    }
+}
+
+fn parse_path(derivation: &Derivation, arg: &str) -> Option<Path> {
+    let mut node = derivation;
+    let mut path = vec![];
+    for i in arg.split('.') {
+        let i = i.parse::<usize>().ok()?;
+        node = node.premises.get(i.checked_sub(1)?)?;
+        path.push(i);
+    }
+    Some(path)
+}
+
+fn collect_paths(derivation: &Derivation, path: &mut Path, paths: &mut Vec<Path>) {
+    paths.push(path.clone());
+    for (i, premise) in derivation.premises.iter().enumerate() {
+        path.push(i + 1);
+        collect_paths(premise, path, paths);
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    fn eval(repl: &mut Repl, line: &str) -> String {
+        repl.eval(line).unwrap()
+    }
+
+    #[test]
+    fn test_expand() {
+        let mut repl = Repl::new();
+        assert_eq!(
+            eval(&mut repl, "3 + (4 * 5) evalto 23"),
+            "(EvalML1)
+3 + 4 * 5 evalto 23 by E-Plus {
+    [1] 3 evalto 3 by E-Int {}
+    [2] 4 * 5 evalto 20 by E-Times { ... }
+    [3] 3 plus 20 is 23 by B-Plus {}
+}"
+        );
+        assert_eq!(
+            eval(&mut repl, ":expand 2"),
+            "3 + 4 * 5 evalto 23 by E-Plus {
+    [1] 3 evalto 3 by E-Int {}
+    [2] 4 * 5 evalto 20 by E-Times {
+        [2.1] 4 evalto 4 by E-Int {}
+        [2.2] 5 evalto 5 by E-Int {}
+        [2.3] 4 times 5 is 20 by B-Times {}
+    }
+    [3] 3 plus 20 is 23 by B-Plus {}
+}"
+        );
+        assert_eq!(
+            eval(&mut repl, ":collapse"),
+            "3 + 4 * 5 evalto 23 by E-Plus { ... }"
+        );
+        assert_eq!(eval(&mut repl, ":expand 4"), "no premise `4`");
+    }
+
+    #[test]
+    fn test_env() {
+        let mut repl = Repl::new();
+        assert_eq!(eval(&mut repl, "let x = 3 - 1"), "x = 2");
+        assert_eq!(eval(&mut repl, "let y = x * x"), "y = 4");
+        assert_eq!(eval(&mut repl, ":env"), "x = 2, y = 4");
+        assert_eq!(
+            eval(&mut repl, "y evalto 4"),
+            "(EvalML2)
+x = 2, y = 4 |- y evalto 4 by E-Var1 {}"
+        );
+        assert_eq!(eval(&mut repl, "let z = w"), "unbound variable `w`");
+        assert_eq!(
+            eval(&mut repl, ":history"),
+            "  1  let x = 3 - 1
+  2  let y = x * x
+  3  y evalto 4
+  4  let z = w"
+        );
+        assert_eq!(eval(&mut repl, ":reset"), "");
+        assert_eq!(eval(&mut repl, ":env"), "");
+        assert_eq!(repl.eval(":quit"), None);
+    }
+
+    #[test]
+    fn test_system() {
+        let mut repl = Repl::new();
+        assert_eq!(eval(&mut repl, ":system"), "auto");
+        assert_eq!(eval(&mut repl, ":system CompareNat3"), "");
+        assert_eq!(
+            eval(&mut repl, "Z is less than S(Z)"),
+            "(CompareNat3)
+Z is less than S(Z) by L-Succ {}"
+        );
+        assert_eq!(eval(&mut repl, ":system Foo"), "unknown system `Foo`");
+        assert_eq!(eval(&mut repl, ":system EvalML3"), "");
+        assert_eq!(eval(&mut repl, "let y = 2"), "y = 2");
+        assert_eq!(
+            eval(&mut repl, "(fun z -> z) y evalto 2"),
+            "(EvalML3)
+y = 2 |- (fun z -> z) y evalto 2 by E-App {
+    [1] y = 2 |- fun z -> z evalto (y = 2)[fun z -> z] by E-Fun {}
+    [2] y = 2 |- y evalto 2 by E-Var1 {}
+    [3] y = 2, z = 2 |- z evalto 2 by E-Var1 {}
+}"
+        );
+    }
+
+    #[test]
+    fn test_ext() {
+        let mut repl = Repl::new();
+        assert_eq!(eval(&mut repl, ":system EvalML2Ext"), "");
+        assert_eq!(eval(&mut repl, "let x = 7 / 2"), "x = 3");
+        assert_eq!(
+            eval(&mut repl, "-x evalto -3"),
+            "(EvalML2Ext)
+x = 3 |- -x evalto -3 by E-Neg {
+    [1] x = 3 |- x evalto 3 by E-Var1 {}
+    [2] minus 3 is -3 by B-Neg {}
+}"
+        );
+    }
+
+    #[test]
+    fn test_large_int() {
+        let mut repl = Repl::new();
+        assert_eq!(eval(&mut repl, "let x = 1"), "x = 1");
+        assert_eq!(
+            eval(&mut repl, "|- 99999999999999999999 evalto 0"),
+            "line 1, column 4: expected expression
+|- 99999999999999999999 evalto 0
+   ^"
+        );
+        assert_eq!(eval(&mut repl, ":system EvalML3"), "");
+        assert_eq!(
+            eval(&mut repl, "99999999999999999999 evalto 0"),
+            "cannot parse `x = 1 |- 99999999999999999999 evalto 0`"
+        );
+        assert_eq!(eval(&mut repl, ":env"), "x = 1");
+        assert_eq!(
+            eval(&mut repl, ":history"),
+            "  1  let x = 1
+  2  |- 99999999999999999999 evalto 0
+  3  99999999999999999999 evalto 0"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use nom::{error::ErrorKind, InputLength, Offset};
+use std::fmt;
+
+pub type IResult<I, O> = nom::IResult<I, O, Error<I>>;
+
+// nom のエラー型: 失敗した位置とそこで期待していたもの
+#[derive(Clone, Debug, PartialEq)]
+pub struct Error<I> {
+    pub input: I,
+    pub expected: Vec<String>,
+}
+
+impl<I> Error<I> {
+    pub fn new(input: I, expected: String) -> Self {
+        Error {
+            input,
+            expected: vec![expected],
+        }
+    }
+}
+
+impl<I: InputLength> nom::error::ParseError<I> for Error<I> {
+    fn from_error_kind(input: I, _: ErrorKind) -> Self {
+        Error {
+            input,
+            expected: vec![],
+        }
+    }
+
+    fn append(_: I, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    // alt の分岐のうち一番先まで読めたものを残し, 同じ位置なら期待するものをまとめる
+    fn or(mut self, other: Self) -> Self {
+        match self.input.input_len().cmp(&other.input.input_len()) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => {
+                for expected in other.expected {
+                    if !self.expected.contains(&expected) {
+                        self.expected.push(expected);
+                    }
+                }
+                self
+            }
+        }
+    }
+}
+
+// 読み始めの位置で失敗したときだけ期待するものを name に置き換える
+pub fn expected<I, O, F>(name: &'static str, mut parser: F) -> impl FnMut(I) -> IResult<I, O>
+where
+    I: InputLength,
+    F: FnMut(I) -> IResult<I, O>,
+{
+    move |input: I| {
+        let len = input.input_len();
+        parser(input).map_err(|e| {
+            e.map(|e| {
+                if e.input.input_len() >= len {
+                    Error::new(e.input, name.to_string())
+                } else {
+                    e
+                }
+            })
+        })
+    }
+}
+
+// 入力を最後まで読む (残りがあればそこで入力の終わりを期待したことにする)
+pub fn complete<'a, O, F>(input: &'a str, mut parser: F) -> Result<O, ParseError>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    match parser(input) {
+        Ok((rest, o)) if rest.trim().is_empty() => Ok(o),
+        Ok((rest, _)) => {
+            let offset = input.offset(rest.trim_start());
+            Err(ParseError::new(
+                input,
+                offset,
+                vec!["end of input".to_string()],
+            ))
+        }
+        Err(e) => Err(ParseError::from_nom(input, e)),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    pub expected: Vec<String>,
+    pub snippet: String,
+}
+
+impl ParseError {
+    pub fn new(source: &str, offset: usize, expected: Vec<String>) -> Self {
+        let before = &source[..offset];
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        let end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |i| offset + i);
+        ParseError {
+            offset,
+            line: before.matches('\n').count() + 1,
+            column: before[start..].chars().count() + 1,
+            expected,
+            snippet: source[start..end].to_string(),
+        }
+    }
+
+    // source を読んでいた parser が返した nom のエラーから作る
+    pub fn from_nom(source: &str, e: nom::Err<Error<&str>>) -> Self {
+        match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                ParseError::new(source, source.offset(e.input), e.expected)
+            }
+            nom::Err::Incomplete(_) => ParseError::new(source, source.len(), vec![]),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match self.expected.split_last() {
+            None => write!(f, "syntax error")?,
+            Some((last, [])) => write!(f, "expected {}", last)?,
+            Some((last, init)) => write!(f, "expected {} or {}", init.join(", "), last)?,
+        }
+        // タブはそのまま残して ^ の位置を合わせる
+        let indent: String = self
+            .snippet
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        writeln!(f)?;
+        writeln!(f, "{}", self.snippet)?;
+        write!(f, "{}^", indent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::error::ParseError;
+
+    #[test]
+    fn test_position() {
+        let e = ParseError::new("let x = 3 in\n  x +", 18, vec!["expression".to_string()]);
+        assert_eq!((e.offset, e.line, e.column), (18, 2, 6));
+        assert_eq!(e.snippet, "  x +");
+    }
+
+    #[test]
+    fn test_display() {
+        let e = ParseError::new(
+            "if 3 < 4 tehn 1 else 2",
+            9,
+            vec!["`then`".to_string(), "`<`".to_string(), "`+`".to_string()],
+        );
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 10: expected `then`, `<` or `+`\nif 3 < 4 tehn 1 else 2\n         ^"
+        );
+    }
+}
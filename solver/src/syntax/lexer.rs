@@ -0,0 +1,337 @@
+use crate::syntax::error::{Error, IResult};
+use nom::{
+    bytes::complete::{tag, take_while},
+    character::complete::satisfy,
+    combinator::{not, recognize},
+    error::ParseError,
+    sequence::{pair, terminated},
+};
+
+pub const KEYWORDS: [&str; 7] = ["if", "then", "else", "let", "in", "true", "false"];
+
+// 長いものから順に試す
+const SYMBOLS: [&str; 9] = ["|-", "(", ")", "+", "-", "*", "<", "=", ","];
+
+// 拡張体系 (EvalML1Ext, EvalML2Ext) だけで使う予約語と記号を足したもの
+pub const EXT_KEYWORDS: [&str; 8] = ["if", "then", "else", "let", "in", "true", "false", "mod"];
+
+const EXT_SYMBOLS: [&str; 13] = [
+    "|-", "&&", "||", "(", ")", "+", "-", "*", "/", "<", ">", "=", ",",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenKind {
+    Int(i64),
+    Ident(String),
+    Keyword(&'static str),
+    Symbol(&'static str),
+}
+
+// 字句を読んだ文字列の中での位置 (バイト単位)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+// 読めるところまで字句に分け, 読めなかった残りと一緒に返す
+pub fn tokenize(input: &str) -> (Vec<Token>, &str) {
+    tokenize_with(input, &KEYWORDS, &SYMBOLS)
+}
+
+pub fn tokenize_ext(input: &str) -> (Vec<Token>, &str) {
+    tokenize_with(input, &EXT_KEYWORDS, &EXT_SYMBOLS)
+}
+
+fn tokenize_with<'a>(
+    input: &'a str,
+    keywords: &[&'static str],
+    symbols: &[&'static str],
+) -> (Vec<Token>, &'a str) {
+    let mut tokens = vec![];
+    let mut rest = skip(input);
+    while let Some((kind, len)) = lex(rest, keywords, symbols) {
+        let start = input.len() - rest.len();
+        let span = Span {
+            start,
+            end: start + len,
+        };
+        tokens.push(Token { kind, span });
+        rest = skip(&rest[len..]);
+    }
+    (tokens, rest)
+}
+
+// 空白とコメントを読み飛ばす
+fn skip(input: &str) -> &str {
+    let mut rest = input.trim_start();
+    while let Some(end) = comment_end(rest) {
+        rest = rest[end..].trim_start();
+    }
+    rest
+}
+
+// '(*' で始まるコメントの終わりの位置 (OCaml と同じく入れ子にできる)
+fn comment_end(input: &str) -> Option<usize> {
+    if !input.starts_with("(*") {
+        return None;
+    }
+    let mut depth = 0;
+    let mut i = 0;
+    while i < input.len() {
+        if input[i..].starts_with("(*") {
+            depth += 1;
+            i += 2;
+        } else if input[i..].starts_with("*)") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return Some(i);
+            }
+        } else {
+            i += input[i..].chars().next().unwrap().len_utf8();
+        }
+    }
+    None
+}
+
+fn lex(
+    input: &str,
+    keywords: &[&'static str],
+    symbols: &[&'static str],
+) -> Option<(TokenKind, usize)> {
+    use crate::syntax::lexer::TokenKind::*;
+
+    let c = input.chars().next()?;
+    if c.is_ascii_digit() {
+        let len = input
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.len());
+        let i = input[..len].parse::<i64>().ok()?;
+        return Some((Int(i), len));
+    }
+    if is_ident_start(c) {
+        let len = input
+            .find(|c: char| !is_ident_char(c))
+            .unwrap_or(input.len());
+        let kind = match keywords.iter().find(|k| **k == &input[..len]) {
+            Some(k) => Keyword(k),
+            None => Ident(input[..len].to_string()),
+        };
+        return Some((kind, len));
+    }
+    // 閉じていないコメントは '(' と '*' にしない
+    if input.starts_with("(*") {
+        return None;
+    }
+    symbols
+        .iter()
+        .find(|s| input.starts_with(**s))
+        .map(|s| (Symbol(s), s.len()))
+}
+
+// 字句の列に対する parser を文字列に対する parser として使う
+pub fn tokens<'a, O, F>(parser: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: FnMut(&[Token]) -> IResult<&[Token], O>,
+{
+    tokens_with(tokenize, parser)
+}
+
+// 拡張体系の予約語と記号で字句に分ける tokens
+pub fn ext_tokens<'a, O, F>(parser: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: FnMut(&[Token]) -> IResult<&[Token], O>,
+{
+    tokens_with(tokenize_ext, parser)
+}
+
+fn tokens_with<'a, O, F>(
+    tokenize: fn(&str) -> (Vec<Token>, &str),
+    mut parser: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: FnMut(&[Token]) -> IResult<&[Token], O>,
+{
+    move |input: &'a str| {
+        let (tokens, rest) = tokenize(input);
+        let at = |tokens: &[Token]| match tokens.first() {
+            Some(token) => &input[token.span.start..],
+            None => rest,
+        };
+        match parser(&tokens) {
+            Ok((tokens, o)) => Ok((at(tokens), o)),
+            Err(e) => Err(e.map(|e| Error {
+                input: at(e.input),
+                expected: e.expected,
+            })),
+        }
+    }
+}
+
+// 予約語か記号 t
+pub fn token<'a>(t: &'static str) -> impl Fn(&'a [Token]) -> IResult<&'a [Token], &'static str> {
+    use crate::syntax::lexer::TokenKind::*;
+
+    move |input: &'a [Token]| match input {
+        [Token {
+            kind: Keyword(s) | Symbol(s),
+            ..
+        }, rest @ ..]
+            if *s == t =>
+        {
+            Ok((rest, t))
+        }
+        _ => Err(nom::Err::Error(Error::new(input, format!("`{}`", t)))),
+    }
+}
+
+pub fn int(input: &[Token]) -> IResult<&[Token], i64> {
+    use crate::syntax::lexer::TokenKind::*;
+
+    match input {
+        [Token { kind: Int(i), .. }, rest @ ..] => Ok((rest, *i)),
+        // 負の整数は '-' と数字の間を空けずに書く
+        [Token {
+            kind: Symbol("-"),
+            span: span1,
+        }, Token {
+            kind: Int(i),
+            span: span2,
+        }, rest @ ..]
+            if span1.end == span2.start =>
+        {
+            Ok((rest, -i))
+        }
+        _ => Err(nom::Err::Error(Error::new(input, "integer".to_string()))),
+    }
+}
+
+pub fn identifier(input: &[Token]) -> IResult<&[Token], String> {
+    match input {
+        [Token {
+            kind: TokenKind::Ident(s),
+            ..
+        }, rest @ ..] => Ok((rest, s.clone())),
+        _ => Err(nom::Err::Error(Error::new(input, "identifier".to_string()))),
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '\''
+}
+
+// 以下は字句に分けずに文字列のまま読む parser (EvalML3 以降) で使う
+
+// 識別子の途中では終わらない予約語 k ("truex" の "true" は読まない)
+pub fn keyword<'a, E: ParseError<&'a str>>(
+    k: &'static str,
+) -> impl Fn(&'a str) -> nom::IResult<&'a str, &'a str, E> {
+    move |input| terminated(tag(k), not(satisfy(is_ident_char)))(input)
+}
+
+// 英字か '_' で始まる名前 (予約語かどうかは体系ごとに呼び出し側で確かめる)
+pub fn name<'a, E: ParseError<&'a str>>(input: &'a str) -> nom::IResult<&'a str, &'a str, E> {
+    recognize(pair(satisfy(is_ident_start), take_while(is_ident_char)))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        error::complete,
+        lexer::{identifier, tokenize, tokenize_ext, tokens, Span, Token, TokenKind::*},
+    };
+
+    #[test]
+    fn test_tokenize() {
+        let (tokens, rest) = tokenize("let x1 = -3 in x1");
+        let kinds: Vec<_> = tokens.into_iter().map(|token| token.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Keyword("let"),
+                Ident("x1".to_string()),
+                Symbol("="),
+                Symbol("-"),
+                Int(3),
+                Keyword("in"),
+                Ident("x1".to_string()),
+            ]
+        );
+        assert_eq!(rest, "");
+        assert_eq!(
+            tokenize(" |- x").0[1],
+            Token {
+                kind: Ident("x".to_string()),
+                span: Span { start: 4, end: 5 },
+            }
+        );
+    }
+
+    #[test]
+    fn test_keyword() {
+        let (tokens, _) = tokenize("letx iftrue lets _f' if");
+        let kinds: Vec<_> = tokens.into_iter().map(|token| token.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Ident("letx".to_string()),
+                Ident("iftrue".to_string()),
+                Ident("lets".to_string()),
+                Ident("_f'".to_string()),
+                Keyword("if"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comment() {
+        let (tokens, rest) = tokenize("1 (* a (* b *) c *) + (*) *) 2 (* d *)");
+        let kinds: Vec<_> = tokens.into_iter().map(|token| token.kind).collect();
+        assert_eq!(kinds, vec![Int(1), Symbol("+"), Int(2)]);
+        assert_eq!(rest, "");
+        // 閉じていないコメントや知らない文字の手前で止まる
+        assert_eq!(tokenize("1 + (* 2").1, "(* 2");
+        assert_eq!(tokenize("1 >> _").1, ">> _");
+    }
+
+    #[test]
+    fn test_tokenize_ext() {
+        let (tokens, _) = tokenize("x mod 2 / 1");
+        assert_eq!(tokens[1].kind, Ident("mod".to_string()));
+        let (tokens, rest) = tokenize_ext("x mod 2 / 1 && y");
+        let kinds: Vec<_> = tokens.into_iter().map(|token| token.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Ident("x".to_string()),
+                Keyword("mod"),
+                Int(2),
+                Symbol("/"),
+                Int(1),
+                Symbol("&&"),
+                Ident("y".to_string()),
+            ]
+        );
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn test_identifier() {
+        assert_eq!(complete("x'", tokens(identifier)), Ok("x'".to_string()));
+        assert!(complete("let", tokens(identifier)).is_err());
+        assert!(complete("true", tokens(identifier)).is_err());
+        assert!(complete("123", tokens(identifier)).is_err());
+        assert!(complete("x y", tokens(identifier)).is_err());
+    }
+}
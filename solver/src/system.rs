@@ -0,0 +1,757 @@
+use crate::{
+    compare_nat, eval_cont_ml1, eval_cont_ml4,
+    eval_error::EvalError,
+    eval_ml1, eval_ml1_err, eval_ml1_ext, eval_ml2, eval_ml2_ext, eval_ml3, eval_ml4, eval_ml5,
+    eval_nameless_ml3, eval_nat_exp, eval_ref_ml3, nameless_ml3, nat, poly_typing_ml4,
+    reduce_nat_exp,
+    syntax::error::{self, ParseError},
+    typing_ml4,
+};
+use nom::{IResult, Offset};
+use std::{fmt, str::FromStr, thread};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum System {
+    Nat,
+    CompareNat1,
+    CompareNat2,
+    CompareNat3,
+    EvalNatExp,
+    ReduceNatExp,
+    EvalML1,
+    EvalML1Err,
+    // 比較, 論理, 除算, 単項マイナスを足した拡張体系
+    EvalML1Ext,
+    EvalML2,
+    EvalML2Ext,
+    EvalML3,
+    NamelessML3,
+    EvalNamelessML3,
+    EvalML4,
+    EvalML5,
+    TypingML4,
+    PolyTypingML4,
+    EvalContML1,
+    EvalContML4,
+    EvalRefML3,
+}
+
+const SYSTEMS: [(System, &str); 21] = [
+    (System::Nat, "Nat"),
+    (System::CompareNat1, "CompareNat1"),
+    (System::CompareNat2, "CompareNat2"),
+    (System::CompareNat3, "CompareNat3"),
+    (System::EvalNatExp, "EvalNatExp"),
+    (System::ReduceNatExp, "ReduceNatExp"),
+    (System::EvalML1, "EvalML1"),
+    (System::EvalML1Err, "EvalML1Err"),
+    (System::EvalML1Ext, "EvalML1Ext"),
+    (System::EvalML2, "EvalML2"),
+    (System::EvalML2Ext, "EvalML2Ext"),
+    (System::EvalML3, "EvalML3"),
+    (System::NamelessML3, "NamelessML3"),
+    (System::EvalNamelessML3, "EvalNamelessML3"),
+    (System::EvalML4, "EvalML4"),
+    (System::EvalML5, "EvalML5"),
+    (System::TypingML4, "TypingML4"),
+    (System::PolyTypingML4, "PolyTypingML4"),
+    (System::EvalContML1, "EvalContML1"),
+    (System::EvalContML4, "EvalContML4"),
+    (System::EvalRefML3, "EvalRefML3"),
+];
+
+impl fmt::Display for System {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, name) = SYSTEMS.iter().find(|(system, _)| system == self).unwrap();
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for System {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<System, String> {
+        SYSTEMS
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(s))
+            .map(|(system, _)| *system)
+            .ok_or_else(|| format!("unknown system `{}`", s))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SolveError {
+    Parse(String),
+    Syntax(ParseError),
+    NoDerivation(String),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Parse(input) => write!(f, "cannot parse `{}`", input),
+            SolveError::Syntax(e) => write!(f, "{}", e),
+            SolveError::NoDerivation(reason) => write!(f, "no derivation: {}", reason),
+        }
+    }
+}
+
+impl From<EvalError> for SolveError {
+    fn from(e: EvalError) -> SolveError {
+        SolveError::NoDerivation(e.to_string())
+    }
+}
+
+// 深い導出は既定のスタックに収まらない
+const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+// 大きいスタックのスレッドで f を実行する
+pub fn with_stack<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    thread::scope(|scope| {
+        thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn_scoped(scope, f)
+            .expect("failed to spawn the solver thread")
+            .join()
+            .unwrap()
+    })
+}
+
+// 判断を体系の solver に渡して導出を文字列で返す
+pub fn solve(system: System, judgment: &str) -> Result<String, SolveError> {
+    with_stack(|| solve_judgment(system, judgment))
+}
+
+fn solve_judgment(system: System, judgment: &str) -> Result<String, SolveError> {
+    let judgment = judgment.trim();
+    let rule = match system {
+        System::Nat => {
+            let judgement = complete(judgment, nat::parser::parse(judgment))?;
+            nat::solver::solve(&judgement, 0)
+                .ok_or_else(|| SolveError::NoDerivation(judgment.to_string()))?
+                .to_string()
+        }
+        System::CompareNat1 | System::CompareNat2 | System::CompareNat3 => {
+            let system = match system {
+                System::CompareNat1 => compare_nat::rule::System::CompareNat1,
+                System::CompareNat2 => compare_nat::rule::System::CompareNat2,
+                _ => compare_nat::rule::System::CompareNat3,
+            };
+            let (nat1, nat2) = complete(judgment, compare_nat::parser::parse(judgment))?;
+            compare_nat::solver::solve(system, &nat1, &nat2, 0)
+                .ok_or_else(|| SolveError::NoDerivation(judgment.to_string()))?
+                .to_string()
+        }
+        System::EvalNatExp => {
+            let (expr, value) = split(judgment, " evalto ")?;
+            let expr = complete(judgment, eval_nat_exp::parser::parse(expr))?;
+            let value = complete(judgment, nat::parser::parse_nat(value))?;
+            let rule = eval_nat_exp::solver::solve(&expr, 0);
+            conclude(rule.evaluated(), value)?;
+            rule.to_string()
+        }
+        System::ReduceNatExp => {
+            let judgement = complete(judgment, reduce_nat_exp::parser::parse(judgment))?;
+            reduce_nat_exp::solver::solve(&judgement, 0)
+                .ok_or_else(|| SolveError::NoDerivation(judgment.to_string()))?
+                .to_string()
+        }
+        System::EvalML1 => {
+            let (expr, value) = split(judgment, " evalto ")?;
+            let expr = syntax(judgment, eval_ml1::parser::parse_expr(expr))?;
+            let value = value_syntax(judgment, eval_ml1::parser::parse_value(value))?;
+            let rule = eval_ml1::solver::solve(&expr, 0)?;
+            conclude(rule.evaluated(), value)?;
+            rule.to_string()
+        }
+        System::EvalML1Ext => {
+            let (expr, value) = split(judgment, " evalto ")?;
+            let expr = syntax(judgment, eval_ml1_ext::parser::parse_expr(expr))?;
+            let value = value_syntax(judgment, eval_ml1_ext::parser::parse_value(value))?;
+            let rule = eval_ml1_ext::solver::solve(&expr, 0)?;
+            conclude(rule.evaluated(), value)?;
+            rule.to_string()
+        }
+        System::EvalML1Err => {
+            let (expr, value) = split(judgment, " evalto ")?;
+            let expr = complete(judgment, eval_ml1_err::parser::parse(expr))?;
+            let value = complete(judgment, eval_ml1_err::parser::parse_result(value))?;
+            let rule = eval_ml1_err::solver::solve(&expr, 0)?;
+            conclude(rule.evaluated(), value)?;
+            rule.to_string()
+        }
+        System::EvalML2 => {
+            let (expr, value) = split(judgment, " evalto ")?;
+            let (env, expr) = syntax(judgment, eval_ml2::parser::parse_env_expr(expr))?;
+            let value = value_syntax(judgment, eval_ml2::parser::parse_value(value))?;
+            let rule = eval_ml2::solver::solve(&env, &expr, 0)?;
+            conclude(rule.evaluated(), value)?;
+            rule.to_string()
+        }
+        System::EvalML2Ext => {
+            let (expr, value) = split(judgment, " evalto ")?;
+            let (env, expr) = syntax(judgment, eval_ml2_ext::parser::parse_env_expr(expr))?;
+            let value = value_syntax(judgment, eval_ml2_ext::parser::parse_value(value))?;
+            let rule = eval_ml2_ext::solver::solve(&env, &expr, 0)?;
+            conclude(rule.evaluated(), value)?;
+            rule.to_string()
+        }
+        System::EvalML3 => {
+            let (expr, value) = split(judgment, " evalto ")?;
+            let (env, expr) = complete(judgment, eval_ml3::parser::parse(expr))?;
+            let value = complete(judgment, eval_ml3::parser::parse_result(value))?;
+            let rule = eval_ml3::solver::solve(&env, &expr, 0)?;
+            conclude(rule.evaluated(), value)?;
+            rule.to_string()
+        }
+        System::NamelessML3 => {
+            // 変換先の式は EvalNamelessML3 の構文で読む
+            let (expr, translated) = split(judgment, " ==> ")?;
+            let (vars, expr) = complete(judgment, nameless_ml3::parser::parse(expr))?;
+            let translated = complete(judgment, eval_nameless_ml3::parser::parse_expr(translated))?;
+            let rule = nameless_ml3::solver::solve(&vars, &expr, 0)?;
+            conclude(rule.translated().to_string(), translated.to_string())?;
+            rule.to_string()
+        }
+        System::EvalNamelessML3 => {
+            let (expr, value) = split(judgment, " evalto ")?;
+            let (env, expr) = complete(judgment, eval_nameless_ml3::parser::parse(expr))?;
+            let value = complete(judgment, eval_nameless_ml3::parser::parse_result(value))?;
+            let rule = eval_nameless_ml3::solver::solve(&env, &expr, 0)?;
+            conclude(rule.evaluated(), value)?;
+            rule.to_string()
+        }
+        System::EvalML4 => {
+            let (expr, value) = split(judgment, " evalto ")?;
+            let (env, expr) = complete(judgment, eval_ml4::parser::parse(expr))?;
+            let value = complete(judgment, eval_ml4::parser::parse_result(value))?;
+            let rule = eval_ml4::solver::solve(&env, &expr, 0)?;
+            conclude(rule.evaluated(), value)?;
+            rule.to_string()
+        }
+        System::EvalML5 => {
+            let (expr, value) = split(judgment, " evalto ")?;
+            let (env, expr) = complete(judgment, eval_ml5::parser::parse(expr))?;
+            let value = complete(judgment, eval_ml5::parser::parse_result(value))?;
+            let rule = eval_ml5::solver::solve(&env, &expr, 0)?;
+            conclude(rule.evaluated(), value)?;
+            rule.to_string()
+        }
+        System::TypingML4 => {
+            let (env, expr, ty) = complete(judgment, typing_ml4::parser::parse(judgment))?;
+            typing_ml4::solver::solve(&env, &expr, &ty, 0)
+                .map_err(|e| SolveError::NoDerivation(e.to_string()))?
+                .to_string()
+        }
+        System::PolyTypingML4 => {
+            let (env, expr, ty) = complete(judgment, poly_typing_ml4::parser::parse(judgment))?;
+            poly_typing_ml4::solver::solve(&env, &expr, &ty, 0)
+                .map_err(|e| SolveError::NoDerivation(e.to_string()))?
+                .to_string()
+        }
+        System::EvalContML1 => {
+            let (expr, value) = split(judgment, " evalto ")?;
+            let (expr, cont) = syntax(judgment, eval_cont_ml1::parser::parse(expr))?;
+            let value = value_syntax(judgment, eval_ml1::parser::parse_value(value))?;
+            let rule = eval_cont_ml1::solver::solve(&expr, &cont, 0)?;
+            conclude(rule.evaluated(), value)?;
+            rule.to_string()
+        }
+        System::EvalContML4 => {
+            let (expr, value) = split(judgment, " evalto ")?;
+            let (env, expr) = complete(judgment, eval_cont_ml4::parser::parse(expr))?;
+            let value = complete(judgment, eval_cont_ml4::parser::parse_result(value))?;
+            let cont = eval_cont_ml4::ast::Cont::new();
+            let rule = eval_cont_ml4::solver::solve(&env, &expr, &cont, 0)?;
+            conclude(rule.evaluated(), value)?;
+            rule.to_string()
+        }
+        System::EvalRefML3 => {
+            let (expr, value) = split(judgment, " evalto ")?;
+            let (store, env, expr) = complete(judgment, eval_ref_ml3::parser::parse(expr))?;
+            let (value, store1) = complete(judgment, eval_ref_ml3::parser::parse_result(value))?;
+            let rule = eval_ref_ml3::solver::solve(&store, &env, &expr, 0)?;
+            // ストアが空なら '/' 以降は省いて比べる
+            let result = |value: eval_ref_ml3::ast::Expr, store: eval_ref_ml3::ast::Store| {
+                if store.is_empty() {
+                    value.to_string()
+                } else {
+                    format!("{} / {}", value, store)
+                }
+            };
+            conclude(
+                result(rule.evaluated(), rule.stored()),
+                result(value, store1),
+            )?;
+            rule.to_string()
+        }
+    };
+    Ok(rule)
+}
+
+// 判断の形から解けそうな体系を小さい順に挙げる
+pub fn candidates(judgment: &str) -> Vec<System> {
+    let judgment = judgment.trim();
+    if ["--->", "-d->", "-*->"]
+        .iter()
+        .any(|s| judgment.contains(s))
+    {
+        return vec![System::ReduceNatExp];
+    }
+    if judgment.contains(" ==> ") {
+        return vec![System::NamelessML3];
+    }
+    // plus などは変数名にも使えるので, 評価や型付けの判断でないときだけ見る
+    if !judgment.contains(" evalto ") && !judgment.contains("|-") {
+        if judgment.contains(" plus ") || judgment.contains(" times ") {
+            return vec![System::Nat];
+        }
+        if judgment.contains(" less than ") {
+            return vec![
+                System::CompareNat1,
+                System::CompareNat2,
+                System::CompareNat3,
+            ];
+        }
+    }
+    let expr = before(judgment, " evalto ");
+    if !expr.contains("|-") {
+        return if judgment.ends_with(" evalto error") {
+            vec![System::EvalML1Err]
+        } else if expr.contains('Z') {
+            vec![System::EvalNatExp]
+        } else if expr.contains(">>") {
+            vec![System::EvalContML1]
+        } else {
+            vec![System::EvalML1]
+        };
+    }
+    if !judgment.contains(" evalto ") && judgment.contains(" : ") {
+        return vec![System::TypingML4, System::PolyTypingML4];
+    }
+    if expr.contains('#') {
+        return vec![System::EvalNamelessML3];
+    }
+    if has_word(expr, "letcc") {
+        return vec![System::EvalContML4];
+    }
+    if has_word(expr, "ref") || ["!", ":=", "@"].iter().any(|s| expr.contains(s)) {
+        return vec![System::EvalRefML3];
+    }
+    if has_word(expr, "match") || expr.contains("::") || expr.contains("[]") {
+        return vec![System::EvalML4, System::EvalML5];
+    }
+    vec![
+        System::EvalML2,
+        System::EvalML3,
+        System::EvalML4,
+        System::EvalML5,
+    ]
+}
+
+// 候補の体系を順に試して最初に解けたものを返す
+// 解けなければ判断を読めた体系のエラーを優先する
+pub fn solve_auto(judgment: &str) -> Result<(System, String), SolveError> {
+    let mut errors = vec![];
+    for system in candidates(judgment) {
+        match solve(system, judgment) {
+            Ok(rule) => return Ok((system, rule)),
+            Err(e) => errors.push(e),
+        }
+    }
+    let i = errors
+        .iter()
+        .position(|e| matches!(e, SolveError::NoDerivation(_)))
+        .unwrap_or(0);
+    Err(errors.swap_remove(i))
+}
+
+// 判断を左辺と右辺に分ける
+fn split<'a>(judgment: &'a str, pat: &str) -> Result<(&'a str, &'a str), SolveError> {
+    match judgment.find(pat) {
+        Some(i) => Ok((&judgment[..i], &judgment[i + pat.len()..])),
+        None => {
+            let expected = format!("`{}`", pat.trim());
+            let e = ParseError::new(judgment, judgment.len(), vec![expected]);
+            Err(SolveError::Syntax(e))
+        }
+    }
+}
+
+// 導出した結果が判断の右辺と違えば導出はない
+fn conclude<T: PartialEq + fmt::Display>(found: T, expected: T) -> Result<(), SolveError> {
+    if found == expected {
+        Ok(())
+    } else {
+        Err(SolveError::NoDerivation(format!(
+            "expected `{}`, found `{}`",
+            expected, found
+        )))
+    }
+}
+
+fn complete<T>(judgment: &str, result: IResult<&str, T>) -> Result<T, SolveError> {
+    match result {
+        Ok((rest, t)) if rest.trim().is_empty() => Ok(t),
+        _ => Err(SolveError::Parse(judgment.to_string())),
+    }
+}
+
+// 構文エラーの位置は判断全体に対して求める
+fn syntax<'a, T>(judgment: &'a str, result: error::IResult<&'a str, T>) -> Result<T, SolveError> {
+    syntax_before(judgment, result, "`evalto`")
+}
+
+// 右辺の後には何も続かない
+fn value_syntax<'a, T>(
+    judgment: &'a str,
+    result: error::IResult<&'a str, T>,
+) -> Result<T, SolveError> {
+    syntax_before(judgment, result, "end of input")
+}
+
+fn syntax_before<'a, T>(
+    judgment: &'a str,
+    result: error::IResult<&'a str, T>,
+    expected: &str,
+) -> Result<T, SolveError> {
+    let e = match result {
+        Ok((rest, t)) if rest.trim().is_empty() => return Ok(t),
+        Ok((rest, _)) => {
+            let offset = judgment.offset(rest.trim_start());
+            ParseError::new(judgment, offset, vec![expected.to_string()])
+        }
+        Err(e) => ParseError::from_nom(judgment, e),
+    };
+    Err(SolveError::Syntax(e))
+}
+
+fn has_word(input: &str, word: &str) -> bool {
+    input
+        .split(|c: char| !c.is_alphanumeric() && c != '_' && c != '\'')
+        .any(|s| s == word)
+}
+
+fn before<'a>(input: &'a str, pat: &str) -> &'a str {
+    match input.find(pat) {
+        Some(i) => &input[..i],
+        None => input,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        syntax::error::ParseError,
+        system::{candidates, solve, solve_auto, SolveError, System},
+    };
+    use std::{env, fs, path::Path};
+
+    // CoPL の問題番号と体系の対応
+    fn system_of(n: usize) -> Option<System> {
+        match n {
+            1..=8 => Some(System::Nat),
+            9 | 12 => Some(System::CompareNat1),
+            10 | 13 => Some(System::CompareNat2),
+            11 | 14 => Some(System::CompareNat3),
+            15..=20 => Some(System::EvalNatExp),
+            21..=24 => Some(System::ReduceNatExp),
+            25..=30 => Some(System::EvalML1),
+            31..=33 => Some(System::EvalML1Err),
+            34..=39 => Some(System::EvalML2),
+            _ => None,
+        }
+    }
+
+    fn normalize(s: &str) -> String {
+        s.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn indents(s: &str) -> Vec<usize> {
+        s.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .collect()
+    }
+
+    // COPL_REGENERATE=1 のときは差分のあるファイルを書き直す
+    #[test]
+    fn test_derivations() {
+        let regenerate = env::var("COPL_REGENERATE").is_ok_and(|s| s == "1");
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../derivation");
+        let mut paths = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        paths.sort();
+        let mut failures = vec![];
+        for path in paths {
+            let name = path.file_name().unwrap().to_str().unwrap().to_string();
+            let system = name
+                .trim_start_matches('Q')
+                .parse::<usize>()
+                .ok()
+                .and_then(system_of)
+                .unwrap_or_else(|| panic!("{}: no system for this problem", name));
+            let expected = fs::read_to_string(&path).unwrap();
+            let first = expected.lines().next().unwrap_or_default();
+            let judgment = &first[..first.rfind(" by ").unwrap_or(first.len())];
+            assert!(candidates(judgment).contains(&system), "{}", name);
+            let actual = solve(system, judgment).unwrap_or_else(|e| panic!("{}: {}", name, e));
+            // 問題の判断 (1 行目の by より前) は書き直さない
+            let actual = format!(
+                "{}{}",
+                judgment.trim_start(),
+                &actual[actual.find(" by ").unwrap()..]
+            );
+            let same = normalize(&actual) == normalize(&expected);
+            // 書き直すときは字下げの違い (|- の前の空白など) も差分とみなす
+            if regenerate {
+                if !same || indents(&actual) != indents(&expected) {
+                    fs::write(&path, format!("{}\n", actual)).unwrap();
+                }
+            } else if !same {
+                failures.push(name);
+            }
+        }
+        assert!(
+            failures.is_empty(),
+            "mismatched derivations: {:?}",
+            failures
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            solve(System::EvalML2, "x = 3, y = 2 |- x evalto 3").unwrap(),
+            "x = 3, y = 2 |- x evalto 3 by E-Var2 {
+    x = 3 |- x evalto 3 by E-Var1 {}
+}"
+        );
+        assert_eq!(
+            solve(System::EvalML1, "3 + 5 evalto 9"),
+            Err(SolveError::NoDerivation(
+                "expected `9`, found `8`".to_string()
+            ))
+        );
+        assert_eq!(
+            solve(System::EvalNatExp, "Z + Z evalto S(Z)"),
+            Err(SolveError::NoDerivation(
+                "expected `S(Z)`, found `Z`".to_string()
+            ))
+        );
+        assert_eq!(
+            solve(System::NamelessML3, "x, y |- x ==> #1"),
+            Err(SolveError::NoDerivation(
+                "expected `#1`, found `#2`".to_string()
+            ))
+        );
+        assert_eq!(
+            solve(System::EvalRefML3, "|- let r = ref 2 in !r evalto 2"),
+            Err(SolveError::NoDerivation(
+                "expected `2`, found `2 / @l1 = 2`".to_string()
+            ))
+        );
+        assert_eq!(
+            solve(System::EvalML1, "3 + 5"),
+            Err(SolveError::Syntax(ParseError::new(
+                "3 + 5",
+                5,
+                vec!["`evalto`".to_string()]
+            )))
+        );
+        assert_eq!(
+            solve(System::Nat, "Z plus Z is S(Z)"),
+            Err(SolveError::NoDerivation("Z plus Z is S(Z)".to_string()))
+        );
+        assert_eq!(
+            solve(System::TypingML4, "|- 1 + true : int")
+                .unwrap_err()
+                .to_string(),
+            "no derivation: type error at `true`: expected int, found bool"
+        );
+    }
+
+    #[test]
+    fn test_solve_large_int() {
+        for (system, judgment) in [
+            (System::EvalML1Err, "99999999999999999999 evalto 0"),
+            (System::EvalML3, "|- 99999999999999999999 evalto 0"),
+            (System::EvalML4, "|- 99999999999999999999 evalto 0"),
+            (System::EvalML5, "|- 99999999999999999999 evalto 0"),
+            (System::NamelessML3, "|- 99999999999999999999 ==> 0"),
+            (System::EvalNamelessML3, "|- 99999999999999999999 evalto 0"),
+            (System::EvalRefML3, "|- 99999999999999999999 evalto 0"),
+            (System::EvalContML4, "|- 99999999999999999999 evalto 0"),
+            (System::TypingML4, "|- 99999999999999999999 : int"),
+            (System::PolyTypingML4, "|- 99999999999999999999 : int"),
+        ] {
+            assert!(
+                matches!(
+                    solve(system, judgment),
+                    Err(SolveError::Parse(_) | SolveError::Syntax(_))
+                ),
+                "{:?}",
+                system
+            );
+        }
+        assert!(solve_auto("|- 99999999999999999999 evalto 0").is_err());
+    }
+
+    #[test]
+    fn test_solve_stuck() {
+        let reason = |system, judgment| match solve(system, judgment) {
+            Err(SolveError::NoDerivation(reason)) => reason,
+            result => panic!("{:?}", result),
+        };
+        assert_eq!(
+            reason(System::EvalML1, "1 + true evalto 1"),
+            "cannot evaluate `1 + true`"
+        );
+        assert_eq!(
+            reason(System::EvalML1, "9223372036854775807 + 1 evalto 0"),
+            "integer overflow in `9223372036854775807 + 1`"
+        );
+        assert_eq!(
+            reason(System::EvalML2, "x = 1 |- y evalto 1"),
+            "unbound variable `y`"
+        );
+        assert_eq!(
+            reason(
+                System::EvalML4,
+                "|- match 1 with [] -> 1 | x :: y -> 2 evalto 1"
+            ),
+            "cannot evaluate `match 1 with [] -> 1 | x :: y -> 2`"
+        );
+        assert_eq!(
+            reason(System::NamelessML3, "x |- y ==> #1"),
+            "unbound variable `y`"
+        );
+        assert_eq!(
+            reason(System::EvalContML1, "1 + true evalto 1"),
+            "cannot evaluate `true => {1 + _}`"
+        );
+        assert_eq!(
+            reason(
+                System::EvalML3,
+                "|- let rec f = fun x -> f x in f 1 evalto 1"
+            ),
+            "derivation deeper than 4000 levels"
+        );
+    }
+
+    #[test]
+    fn test_solve_ext() {
+        assert_eq!(
+            solve(System::EvalML1Ext, "7 / 2 > 2 || 1 / 0 = 0 evalto true").unwrap(),
+            "7 / 2 > 2 || 1 / 0 = 0 evalto true by E-OrT {
+    7 / 2 > 2 evalto true by E-Gt {
+        7 / 2 evalto 3 by E-Div {
+            7 evalto 7 by E-Int {};
+            2 evalto 2 by E-Int {};
+            7 div 2 is 3 by B-Div {}
+        };
+        2 evalto 2 by E-Int {};
+        3 greater than 2 is true by B-Gt {}
+    }
+}"
+        );
+        assert!(matches!(
+            solve(System::EvalML2, "x = 1 |- -x evalto -1"),
+            Err(SolveError::Syntax(_))
+        ));
+        assert_eq!(
+            solve(System::EvalML1Ext, "5 mod 0 evalto 0"),
+            Err(SolveError::NoDerivation(
+                "division by zero in `5 mod 0`".to_string()
+            ))
+        );
+        assert_eq!(
+            solve(
+                System::EvalML2Ext,
+                "|- (-9223372036854775807 - 1) / -1 evalto 0"
+            ),
+            Err(SolveError::NoDerivation(
+                "integer overflow in `(-9223372036854775807 - 1) / -1`".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_candidates() {
+        assert_eq!(candidates("S(Z) times Z is Z"), vec![System::Nat]);
+        assert_eq!(candidates("Z + S(Z) -d-> S(Z)"), vec![System::ReduceNatExp]);
+        assert_eq!(candidates("3 + 5 evalto 8"), vec![System::EvalML1]);
+        assert_eq!(
+            candidates("1 + true evalto error"),
+            vec![System::EvalML1Err]
+        );
+        assert_eq!(candidates("x, y |- x ==> #2"), vec![System::NamelessML3]);
+        assert_eq!(
+            candidates("|- let r = ref 2 in !r evalto 2 / @l1 = 2"),
+            vec![System::EvalRefML3]
+        );
+        assert_eq!(
+            candidates("|- 1 + letcc k in k 2 evalto 3"),
+            vec![System::EvalContML4]
+        );
+        assert_eq!(
+            candidates("|- fun x -> x :: [] : int list -> int list"),
+            vec![System::TypingML4, System::PolyTypingML4]
+        );
+        assert_eq!(
+            candidates("|- let plus = 1 in plus evalto 1")[0],
+            System::EvalML2
+        );
+        assert_eq!(
+            candidates("S(Z) is less than S(S(Z))"),
+            vec![
+                System::CompareNat1,
+                System::CompareNat2,
+                System::CompareNat3
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solve_auto() {
+        let auto = |judgment| solve_auto(judgment).map(|(system, _)| system);
+        assert_eq!(auto("x = 3, y = 2 |- x evalto 3"), Ok(System::EvalML2));
+        assert_eq!(
+            auto("|- let f = fun x -> x + 1 in f 2 evalto 3"),
+            Ok(System::EvalML3)
+        );
+        assert_eq!(
+            auto("|- match 1 :: [] with x :: _ -> x | [] -> 0 evalto 1"),
+            Ok(System::EvalML5)
+        );
+        assert_eq!(
+            auto("|- let f = fun x -> x in f f 1 : int"),
+            Ok(System::PolyTypingML4)
+        );
+        assert_eq!(
+            auto("|- 1 + true : int"),
+            Err(SolveError::NoDerivation(
+                "type error at `true`: expected int, found bool".to_string()
+            ))
+        );
+        assert_eq!(
+            auto("3 + 5 5 evalto 8"),
+            Err(SolveError::Syntax(ParseError::new(
+                "3 + 5 5 evalto 8",
+                6,
+                vec!["`evalto`".to_string()]
+            )))
+        );
+    }
+
+    #[test]
+    fn test_system() {
+        assert_eq!("EvalML2".parse::<System>(), Ok(System::EvalML2));
+        assert_eq!("evalml1err".parse::<System>(), Ok(System::EvalML1Err));
+        assert_eq!("EvalML2Ext".parse::<System>(), Ok(System::EvalML2Ext));
+        assert_eq!(System::PolyTypingML4.to_string(), "PolyTypingML4");
+        assert!("EvalML9".parse::<System>().is_err());
+    }
+}
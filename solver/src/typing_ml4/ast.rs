@@ -0,0 +1,298 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Expr {
+    Int(i64),
+    Bool(bool),
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+    Plus(Box<Expr>, Box<Expr>),
+    Minus(Box<Expr>, Box<Expr>),
+    Times(Box<Expr>, Box<Expr>),
+    Lt(Box<Expr>, Box<Expr>),
+    Let(Box<Expr>, Box<Expr>, Box<Expr>),
+    Var(String),
+    Fun(Box<Expr>, Box<Expr>),
+    App(Box<Expr>, Box<Expr>),
+    LetRec(Box<Expr>, Box<Expr>, Box<Expr>, Box<Expr>),
+    Nil,
+    Cons(Box<Expr>, Box<Expr>),
+    Match(Box<Expr>, Box<Expr>, Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    // 結合の強さ (大きいほど強い)
+    fn precedence(&self) -> usize {
+        use crate::typing_ml4::ast::Expr::*;
+
+        match self {
+            If(_, _, _) | Let(_, _, _) | Fun(_, _) | LetRec(_, _, _, _) | Match(_, _, _, _, _) => 0,
+            Lt(_, _) => 1,
+            Cons(_, _) => 2,
+            Plus(_, _) | Minus(_, _) => 3,
+            Times(_, _) => 4,
+            App(_, _) => 5,
+            Int(i) if *i < 0 => 5,
+            _ => 6,
+        }
+    }
+
+    fn paren(&self, paren: bool) -> String {
+        if paren {
+            format!("({})", self)
+        } else {
+            self.to_string()
+        }
+    }
+
+    // 右にできるだけ伸びる式 (右端にあれば括弧がいらない)
+    fn is_open(&self) -> bool {
+        use crate::typing_ml4::ast::Expr::*;
+
+        matches!(
+            self,
+            If(_, _, _) | Let(_, _, _) | Fun(_, _) | LetRec(_, _, _, _) | Match(_, _, _, _, _)
+        )
+    }
+
+    fn ends_with_match(&self) -> bool {
+        use crate::typing_ml4::ast::Expr::*;
+
+        match self {
+            Match(_, _, _, _, _) => true,
+            If(_, _, expr) | Let(_, _, expr) | Fun(_, expr) | LetRec(_, _, _, expr) => {
+                expr.ends_with_match()
+            }
+            _ => false,
+        }
+    }
+
+    // tail は後ろに何も続かないこと
+    fn show(&self, tail: bool) -> String {
+        use crate::typing_ml4::ast::Expr::*;
+
+        let p = self.precedence();
+        let left = |expr: &Expr, paren: bool| {
+            if paren {
+                expr.paren(true)
+            } else {
+                expr.show(false)
+            }
+        };
+        // 右端なら if や let も括弧なしで書ける
+        let right = |expr: &Expr, paren: bool| {
+            if paren && !(tail && expr.is_open()) {
+                expr.paren(true)
+            } else {
+                expr.show(tail)
+            }
+        };
+        match self {
+            Int(i) => i.to_string(),
+            Bool(b) => b.to_string(),
+            If(expr1, expr2, expr3) => {
+                format!("if {} then {} else {}", expr1, expr2, expr3.show(tail))
+            }
+            Plus(expr1, expr2) => format!(
+                "{} + {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Minus(expr1, expr2) => format!(
+                "{} - {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Times(expr1, expr2) => format!(
+                "{} * {}",
+                left(expr1, expr1.precedence() < p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Lt(expr1, expr2) => format!(
+                "{} < {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() <= p)
+            ),
+            Let(expr1, expr2, expr3) => {
+                format!("let {} = {} in {}", expr1, expr2, expr3.show(tail))
+            }
+            Var(s) => s.to_string(),
+            Fun(expr1, expr2) => format!("fun {} -> {}", expr1, expr2.show(tail)),
+            App(expr1, expr2) => format!(
+                "{} {}",
+                left(expr1, expr1.precedence() < p),
+                expr2.paren(expr2.precedence() <= p)
+            ),
+            LetRec(expr1, expr2, expr3, expr4) => {
+                format!(
+                    "let rec {} = fun {} -> {} in {}",
+                    expr1,
+                    expr2,
+                    expr3,
+                    expr4.show(tail)
+                )
+            }
+            Nil => "[]".to_string(),
+            Cons(expr1, expr2) => format!(
+                "{} :: {}",
+                left(expr1, expr1.precedence() <= p),
+                right(expr2, expr2.precedence() < p)
+            ),
+            Match(expr1, expr2, expr3, expr4, expr5) => {
+                // 最初の節の本体が match で終わると後続の節を取り込んでしまう
+                let expr2 = left(expr2, expr2.ends_with_match());
+                format!(
+                    "match {} with [] -> {} | {} :: {} -> {}",
+                    expr1,
+                    expr2,
+                    expr3,
+                    expr4,
+                    expr5.show(tail)
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.show(true))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Type {
+    Int,
+    Bool,
+    List(Box<Type>),
+    Fun(Box<Type>, Box<Type>),
+    Var(usize),
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::typing_ml4::ast::Type::*;
+
+        match self {
+            Int => write!(f, "int"),
+            Bool => write!(f, "bool"),
+            List(ty) => match **ty {
+                Fun(_, _) => write!(f, "({}) list", ty),
+                _ => write!(f, "{} list", ty),
+            },
+            Fun(ty1, ty2) => match **ty1 {
+                Fun(_, _) => write!(f, "({}) -> {}", ty1, ty2),
+                _ => write!(f, "{} -> {}", ty1, ty2),
+            },
+            Var(n) => match n / 26 {
+                0 => write!(f, "'{}", (b'a' + (n % 26) as u8) as char),
+                i => write!(f, "'{}{}", (b'a' + (n % 26) as u8) as char, i),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Env(Vec<(Expr, Type)>);
+
+impl Env {
+    pub fn new() -> Env {
+        Env(vec![])
+    }
+
+    pub fn from(pairs: Vec<(Expr, Type)>) -> Env {
+        Env(pairs)
+    }
+
+    pub fn put(&mut self, expr: Expr, ty: Type) {
+        self.0.push((expr, ty))
+    }
+
+    pub fn get(&self, expr: &Expr) -> Option<Type> {
+        self.0
+            .iter()
+            .rev()
+            .find(|(expr1, _)| expr1 == expr)
+            .map(|(_, ty)| ty.clone())
+    }
+
+    pub fn map<F: Fn(&Type) -> Type>(&self, f: &F) -> Env {
+        let pairs = self
+            .0
+            .iter()
+            .map(|(expr, ty)| (expr.clone(), f(ty)))
+            .collect();
+        Env(pairs)
+    }
+}
+
+impl fmt::Display for Env {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs = self
+            .0
+            .iter()
+            .map(|(expr, ty)| format!("{} : {}", expr, ty))
+            .collect::<Vec<_>>();
+        write!(f, "{}", pairs.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::typing_ml4::ast::{Env, Expr::*, Type};
+
+    #[test]
+    fn test_env() {
+        let mut env = Env::new();
+        env.put(Var("x".to_string()), Type::Int);
+        env.put(Var("y".to_string()), Type::Bool);
+        env.put(Var("x".to_string()), Type::List(Box::new(Type::Int)));
+        assert_eq!("x : int, y : bool, x : int list", env.to_string());
+        assert_eq!(
+            Some(Type::List(Box::new(Type::Int))),
+            env.get(&Var("x".to_string()))
+        );
+        assert_eq!(Some(Type::Bool), env.get(&Var("y".to_string())));
+        assert_eq!(None, env.get(&Var("z".to_string())));
+    }
+
+    #[test]
+    fn test_type() {
+        let ty = Type::Fun(
+            Box::new(Type::Fun(Box::new(Type::Int), Box::new(Type::Bool))),
+            Box::new(Type::Fun(
+                Box::new(Type::List(Box::new(Type::Int))),
+                Box::new(Type::List(Box::new(Type::Fun(
+                    Box::new(Type::Int),
+                    Box::new(Type::Int),
+                )))),
+            )),
+        );
+        assert_eq!(
+            "(int -> bool) -> int list -> (int -> int) list",
+            ty.to_string()
+        );
+        assert_eq!(
+            "'a -> 'b1 list",
+            Type::Fun(
+                Box::new(Type::Var(0)),
+                Box::new(Type::List(Box::new(Type::Var(27))))
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_display1() {
+        assert_eq!(
+            "fun x -> x + 1 :: []",
+            Fun(
+                Box::new(Var("x".to_string())),
+                Box::new(Cons(
+                    Box::new(Plus(Box::new(Var("x".to_string())), Box::new(Int(1)))),
+                    Box::new(Nil)
+                ))
+            )
+            .to_string()
+        );
+    }
+}
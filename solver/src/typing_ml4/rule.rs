@@ -0,0 +1,400 @@
+use crate::{
+    typing_ml4::ast::{Env, Expr, Expr::*, Type},
+    util::{env_prefix, ident},
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Rule {
+    TInt(Env, i64, usize),
+    TBool(Env, bool, usize),
+    TIf(
+        Env,
+        Expr,
+        Expr,
+        Expr,
+        Box<Rule>,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+    TPlus(Env, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    TMinus(Env, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    TTimes(Env, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    TLt(Env, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    TVar(Env, Expr, Type, usize),
+    TLet(Env, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    TFun(Env, Expr, Expr, Type, Box<Rule>, usize),
+    TApp(Env, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    TLetRec(Env, Expr, Expr, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    TNil(Env, Type, usize),
+    TCons(Env, Expr, Expr, Box<Rule>, Box<Rule>, usize),
+    TMatch(
+        Env,
+        Expr,
+        Expr,
+        Expr,
+        Expr,
+        Expr,
+        Box<Rule>,
+        Box<Rule>,
+        Box<Rule>,
+        usize,
+    ),
+}
+
+impl Rule {
+    pub fn typed(&self) -> Type {
+        use crate::typing_ml4::rule::Rule::*;
+
+        match self {
+            TInt(_, _, _) => Type::Int,
+            TBool(_, _, _) => Type::Bool,
+            TIf(_, _, _, _, _, rule2, _, _) => rule2.typed(),
+            TPlus(_, _, _, _, _, _) => Type::Int,
+            TMinus(_, _, _, _, _, _) => Type::Int,
+            TTimes(_, _, _, _, _, _) => Type::Int,
+            TLt(_, _, _, _, _, _) => Type::Bool,
+            TVar(_, _, ty, _) => ty.clone(),
+            TLet(_, _, _, _, _, rule2, _) => rule2.typed(),
+            TFun(_, _, _, ty, rule, _) => Type::Fun(Box::new(ty.clone()), Box::new(rule.typed())),
+            TApp(_, _, _, rule1, _, _) => match rule1.typed() {
+                Type::Fun(_, ty) => *ty,
+                _ => unreachable!(),
+            },
+            TLetRec(_, _, _, _, _, _, rule2, _) => rule2.typed(),
+            TNil(_, ty, _) => ty.clone(),
+            TCons(_, _, _, _, rule2, _) => rule2.typed(),
+            TMatch(_, _, _, _, _, _, _, rule2, _, _) => rule2.typed(),
+        }
+    }
+
+    pub fn map<F: Fn(&Type) -> Type>(&self, f: &F) -> Rule {
+        use crate::typing_ml4::rule::Rule::*;
+
+        let map = |rule: &Rule| Box::new(rule.map(f));
+        match self {
+            TInt(env, i, depth) => TInt(env.map(f), *i, *depth),
+            TBool(env, b, depth) => TBool(env.map(f), *b, *depth),
+            TIf(env, expr1, expr2, expr3, rule1, rule2, rule3, depth) => TIf(
+                env.map(f),
+                expr1.clone(),
+                expr2.clone(),
+                expr3.clone(),
+                map(rule1),
+                map(rule2),
+                map(rule3),
+                *depth,
+            ),
+            TPlus(env, expr1, expr2, rule1, rule2, depth) => TPlus(
+                env.map(f),
+                expr1.clone(),
+                expr2.clone(),
+                map(rule1),
+                map(rule2),
+                *depth,
+            ),
+            TMinus(env, expr1, expr2, rule1, rule2, depth) => TMinus(
+                env.map(f),
+                expr1.clone(),
+                expr2.clone(),
+                map(rule1),
+                map(rule2),
+                *depth,
+            ),
+            TTimes(env, expr1, expr2, rule1, rule2, depth) => TTimes(
+                env.map(f),
+                expr1.clone(),
+                expr2.clone(),
+                map(rule1),
+                map(rule2),
+                *depth,
+            ),
+            TLt(env, expr1, expr2, rule1, rule2, depth) => TLt(
+                env.map(f),
+                expr1.clone(),
+                expr2.clone(),
+                map(rule1),
+                map(rule2),
+                *depth,
+            ),
+            TVar(env, expr, ty, depth) => TVar(env.map(f), expr.clone(), f(ty), *depth),
+            TLet(env, expr1, expr2, expr3, rule1, rule2, depth) => TLet(
+                env.map(f),
+                expr1.clone(),
+                expr2.clone(),
+                expr3.clone(),
+                map(rule1),
+                map(rule2),
+                *depth,
+            ),
+            TFun(env, expr1, expr2, ty, rule, depth) => TFun(
+                env.map(f),
+                expr1.clone(),
+                expr2.clone(),
+                f(ty),
+                map(rule),
+                *depth,
+            ),
+            TApp(env, expr1, expr2, rule1, rule2, depth) => TApp(
+                env.map(f),
+                expr1.clone(),
+                expr2.clone(),
+                map(rule1),
+                map(rule2),
+                *depth,
+            ),
+            TLetRec(env, expr1, expr2, expr3, expr4, rule1, rule2, depth) => TLetRec(
+                env.map(f),
+                expr1.clone(),
+                expr2.clone(),
+                expr3.clone(),
+                expr4.clone(),
+                map(rule1),
+                map(rule2),
+                *depth,
+            ),
+            TNil(env, ty, depth) => TNil(env.map(f), f(ty), *depth),
+            TCons(env, expr1, expr2, rule1, rule2, depth) => TCons(
+                env.map(f),
+                expr1.clone(),
+                expr2.clone(),
+                map(rule1),
+                map(rule2),
+                *depth,
+            ),
+            TMatch(env, expr1, expr2, expr3, expr4, expr5, rule1, rule2, rule3, depth) => TMatch(
+                env.map(f),
+                expr1.clone(),
+                expr2.clone(),
+                expr3.clone(),
+                expr4.clone(),
+                expr5.clone(),
+                map(rule1),
+                map(rule2),
+                map(rule3),
+                *depth,
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::typing_ml4::rule::Rule::*;
+
+        match self {
+            TInt(env, i, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} : int by T-Int {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    i
+                )
+            }
+            TBool(env, b, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} : bool by T-Bool {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    b
+                )
+            }
+            TIf(env, expr1, expr2, expr3, rule1, rule2, rule3, depth) => {
+                let expr = If(
+                    Box::new(expr1.clone()),
+                    Box::new(expr2.clone()),
+                    Box::new(expr3.clone()),
+                );
+                writeln!(
+                    f,
+                    "{}{}|- {} : {} by T-If {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.typed()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TPlus(env, expr1, expr2, rule1, rule2, depth) => {
+                let expr = Plus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} : {} by T-Plus {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.typed()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TMinus(env, expr1, expr2, rule1, rule2, depth) => {
+                let expr = Minus(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} : {} by T-Minus {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.typed()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TTimes(env, expr1, expr2, rule1, rule2, depth) => {
+                let expr = Times(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} : {} by T-Times {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.typed()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TLt(env, expr1, expr2, rule1, rule2, depth) => {
+                let expr = Lt(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} : {} by T-Lt {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.typed()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TVar(env, expr, ty, depth) => {
+                write!(
+                    f,
+                    "{}{}|- {} : {} by T-Var {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    ty
+                )
+            }
+            TLet(env, expr1, expr2, expr3, rule1, rule2, depth) => {
+                let expr = Let(
+                    Box::new(expr1.clone()),
+                    Box::new(expr2.clone()),
+                    Box::new(expr3.clone()),
+                );
+                writeln!(
+                    f,
+                    "{}{}|- {} : {} by T-Let {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.typed()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TFun(env, expr1, expr2, _, rule, depth) => {
+                let expr = Fun(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} : {} by T-Fun {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.typed()
+                )?;
+                writeln!(f, "{}", rule)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TApp(env, expr1, expr2, rule1, rule2, depth) => {
+                let expr = App(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} : {} by T-App {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.typed()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TLetRec(env, expr1, expr2, expr3, expr4, rule1, rule2, depth) => {
+                let expr = LetRec(
+                    Box::new(expr1.clone()),
+                    Box::new(expr2.clone()),
+                    Box::new(expr3.clone()),
+                    Box::new(expr4.clone()),
+                );
+                writeln!(
+                    f,
+                    "{}{}|- {} : {} by T-LetRec {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.typed()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TNil(env, ty, depth) => {
+                write!(
+                    f,
+                    "{}{}|- [] : {} by T-Nil {{}}",
+                    ident(*depth),
+                    env_prefix(env),
+                    ty
+                )
+            }
+            TCons(env, expr1, expr2, rule1, rule2, depth) => {
+                let expr = Cons(Box::new(expr1.clone()), Box::new(expr2.clone()));
+                writeln!(
+                    f,
+                    "{}{}|- {} : {} by T-Cons {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.typed()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{}", rule2)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+            TMatch(env, expr1, expr2, expr3, expr4, expr5, rule1, rule2, rule3, depth) => {
+                let expr = Match(
+                    Box::new(expr1.clone()),
+                    Box::new(expr2.clone()),
+                    Box::new(expr3.clone()),
+                    Box::new(expr4.clone()),
+                    Box::new(expr5.clone()),
+                );
+                writeln!(
+                    f,
+                    "{}{}|- {} : {} by T-Match {{",
+                    ident(*depth),
+                    env_prefix(env),
+                    expr,
+                    self.typed()
+                )?;
+                writeln!(f, "{};", rule1)?;
+                writeln!(f, "{};", rule2)?;
+                writeln!(f, "{}", rule3)?;
+                write!(f, "{}}}", ident(*depth))
+            }
+        }
+    }
+}
@@ -0,0 +1,389 @@
+use crate::typing_ml4::{
+    ast::{Env, Expr, Expr::*, Type},
+    rule::{Rule, Rule::*},
+    unifier::{TypeError, Unifier},
+};
+
+pub fn solve(env: &Env, expr: &Expr, ty: &Type, depth: usize) -> Result<Rule, TypeError> {
+    let mut unifier = Unifier::new();
+    let (rule, ty1) = infer(&mut unifier, env, expr, depth)?;
+    unifier.unify(expr, ty, &ty1)?;
+    Ok(rule.map(&|ty| unifier.default(ty)))
+}
+
+// 型変数を含んだまま導出を組み立て，制約を unifier に集める
+fn infer(
+    unifier: &mut Unifier,
+    env: &Env,
+    expr: &Expr,
+    depth: usize,
+) -> Result<(Rule, Type), TypeError> {
+    match expr {
+        Int(i) => Ok((TInt(env.clone(), *i, depth), Type::Int)),
+        Bool(b) => Ok((TBool(env.clone(), *b, depth), Type::Bool)),
+        If(expr1, expr2, expr3) => {
+            let (rule1, ty1) = infer(unifier, env, expr1, depth + 1)?;
+            unifier.unify(expr1, &Type::Bool, &ty1)?;
+            let (rule2, ty2) = infer(unifier, env, expr2, depth + 1)?;
+            let (rule3, ty3) = infer(unifier, env, expr3, depth + 1)?;
+            unifier.unify(expr3, &ty2, &ty3)?;
+            let rule = TIf(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            );
+            Ok((rule, ty2))
+        }
+        Plus(expr1, expr2) => {
+            let (rule1, ty1) = infer(unifier, env, expr1, depth + 1)?;
+            unifier.unify(expr1, &Type::Int, &ty1)?;
+            let (rule2, ty2) = infer(unifier, env, expr2, depth + 1)?;
+            unifier.unify(expr2, &Type::Int, &ty2)?;
+            let rule = TPlus(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, Type::Int))
+        }
+        Minus(expr1, expr2) => {
+            let (rule1, ty1) = infer(unifier, env, expr1, depth + 1)?;
+            unifier.unify(expr1, &Type::Int, &ty1)?;
+            let (rule2, ty2) = infer(unifier, env, expr2, depth + 1)?;
+            unifier.unify(expr2, &Type::Int, &ty2)?;
+            let rule = TMinus(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, Type::Int))
+        }
+        Times(expr1, expr2) => {
+            let (rule1, ty1) = infer(unifier, env, expr1, depth + 1)?;
+            unifier.unify(expr1, &Type::Int, &ty1)?;
+            let (rule2, ty2) = infer(unifier, env, expr2, depth + 1)?;
+            unifier.unify(expr2, &Type::Int, &ty2)?;
+            let rule = TTimes(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, Type::Int))
+        }
+        Lt(expr1, expr2) => {
+            let (rule1, ty1) = infer(unifier, env, expr1, depth + 1)?;
+            unifier.unify(expr1, &Type::Int, &ty1)?;
+            let (rule2, ty2) = infer(unifier, env, expr2, depth + 1)?;
+            unifier.unify(expr2, &Type::Int, &ty2)?;
+            let rule = TLt(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, Type::Bool))
+        }
+        Var(_) => {
+            let ty = env
+                .get(expr)
+                .ok_or_else(|| TypeError::Unbound(expr.clone()))?;
+            Ok((TVar(env.clone(), expr.clone(), ty.clone(), depth), ty))
+        }
+        Let(expr1, expr2, expr3) => {
+            let (rule1, ty1) = infer(unifier, env, expr2, depth + 1)?;
+            let mut new_env = env.clone();
+            new_env.put(*expr1.clone(), ty1);
+            let (rule2, ty2) = infer(unifier, &new_env, expr3, depth + 1)?;
+            let rule = TLet(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, ty2))
+        }
+        Fun(expr1, expr2) => {
+            let ty1 = unifier.fresh();
+            let mut new_env = env.clone();
+            new_env.put(*expr1.clone(), ty1.clone());
+            let (rule, ty2) = infer(unifier, &new_env, expr2, depth + 1)?;
+            let rule = TFun(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                ty1.clone(),
+                Box::new(rule),
+                depth,
+            );
+            Ok((rule, Type::Fun(Box::new(ty1), Box::new(ty2))))
+        }
+        App(expr1, expr2) => {
+            let (rule1, ty1) = infer(unifier, env, expr1, depth + 1)?;
+            let (rule2, ty2) = infer(unifier, env, expr2, depth + 1)?;
+            let ty3 = unifier.fresh();
+            let ty4 = Type::Fun(Box::new(ty2), Box::new(ty3.clone()));
+            unifier.unify(expr1, &ty4, &ty1)?;
+            let rule = TApp(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, ty3))
+        }
+        LetRec(expr1, expr2, expr3, expr4) => {
+            let ty1 = unifier.fresh();
+            let ty2 = unifier.fresh();
+            let ty3 = Type::Fun(Box::new(ty1.clone()), Box::new(ty2.clone()));
+            let mut new_env1 = env.clone();
+            new_env1.put(*expr1.clone(), ty3.clone());
+            new_env1.put(*expr2.clone(), ty1);
+            let (rule1, ty4) = infer(unifier, &new_env1, expr3, depth + 1)?;
+            unifier.unify(expr3, &ty2, &ty4)?;
+            let mut new_env2 = env.clone();
+            new_env2.put(*expr1.clone(), ty3);
+            let (rule2, ty5) = infer(unifier, &new_env2, expr4, depth + 1)?;
+            let rule = TLetRec(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                *expr4.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, ty5))
+        }
+        Nil => {
+            let ty = Type::List(Box::new(unifier.fresh()));
+            Ok((TNil(env.clone(), ty.clone(), depth), ty))
+        }
+        Cons(expr1, expr2) => {
+            let (rule1, ty1) = infer(unifier, env, expr1, depth + 1)?;
+            let (rule2, ty2) = infer(unifier, env, expr2, depth + 1)?;
+            let ty3 = Type::List(Box::new(ty1));
+            unifier.unify(expr2, &ty3, &ty2)?;
+            let rule = TCons(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                depth,
+            );
+            Ok((rule, ty3))
+        }
+        Match(expr1, expr2, expr3, expr4, expr5) => {
+            let (rule1, ty1) = infer(unifier, env, expr1, depth + 1)?;
+            let ty2 = unifier.fresh();
+            let ty3 = Type::List(Box::new(ty2.clone()));
+            unifier.unify(expr1, &ty3, &ty1)?;
+            let (rule2, ty4) = infer(unifier, env, expr2, depth + 1)?;
+            let mut new_env = env.clone();
+            new_env.put(*expr3.clone(), ty2);
+            new_env.put(*expr4.clone(), ty3);
+            let (rule3, ty5) = infer(unifier, &new_env, expr5, depth + 1)?;
+            unifier.unify(expr5, &ty4, &ty5)?;
+            let rule = TMatch(
+                env.clone(),
+                *expr1.clone(),
+                *expr2.clone(),
+                *expr3.clone(),
+                *expr4.clone(),
+                *expr5.clone(),
+                Box::new(rule1),
+                Box::new(rule2),
+                Box::new(rule3),
+                depth,
+            );
+            Ok((rule, ty4))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::typing_ml4::{
+        ast::{Env, Expr::*, Type},
+        parser::parse,
+        rule::Rule::*,
+        solver::solve,
+    };
+
+    #[test]
+    fn test_solve1() {
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &Plus(Box::new(Int(3)), Box::new(Int(5))),
+                &Type::Int,
+                0
+            ),
+            Ok(TPlus(
+                Env::new(),
+                Int(3),
+                Int(5),
+                Box::new(TInt(Env::new(), 3, 1)),
+                Box::new(TInt(Env::new(), 5, 1)),
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve2() {
+        assert_eq!(
+            solve(
+                &Env::new(),
+                &Plus(Box::new(Int(3)), Box::new(Bool(true))),
+                &Type::Int,
+                0
+            )
+            .unwrap_err()
+            .to_string(),
+            "type error at `true`: expected int, found bool"
+        );
+    }
+
+    #[test]
+    fn test_solve3() {
+        let (env, expr, ty) = parse("|- fun x -> x + 1 : int -> int").unwrap().1;
+        assert_eq!(
+            solve(&env, &expr, &ty, 0),
+            Ok(TFun(
+                Env::new(),
+                Var("x".to_string()),
+                Plus(Box::new(Var("x".to_string())), Box::new(Int(1))),
+                Type::Int,
+                Box::new(TPlus(
+                    Env::from(vec![(Var("x".to_string()), Type::Int)]),
+                    Var("x".to_string()),
+                    Int(1),
+                    Box::new(TVar(
+                        Env::from(vec![(Var("x".to_string()), Type::Int)]),
+                        Var("x".to_string()),
+                        Type::Int,
+                        2
+                    )),
+                    Box::new(TInt(
+                        Env::from(vec![(Var("x".to_string()), Type::Int)]),
+                        1,
+                        2
+                    )),
+                    1
+                )),
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve4() {
+        let (env, expr, ty) =
+            parse("|- fun f -> fun x -> match f x with [] -> x | y :: z -> y : (int -> int list) -> int -> int")
+                .unwrap()
+                .1;
+        assert_eq!(
+            solve(&env, &expr, &ty, 0).unwrap().typed().to_string(),
+            "(int -> int list) -> int -> int"
+        );
+    }
+
+    #[test]
+    fn test_solve5() {
+        let (env, expr, ty) = parse(
+            "|- let rec fact = fun n -> if n < 2 then 1 else n * fact (n - 1) in fact 3 : int",
+        )
+        .unwrap()
+        .1;
+        let rule = solve(&env, &expr, &ty, 0).unwrap();
+        match rule {
+            TLetRec(_, _, _, _, _, rule1, _, _) => {
+                assert_eq!(rule1.to_string().lines().next().unwrap(), "    fact : int -> int, n : int |- if n < 2 then 1 else n * fact (n - 1) : int by T-If {")
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_display1() {
+        let (env, expr, ty) = parse("|- (fun x -> x) 1 :: [] : int list").unwrap().1;
+        assert_eq!(
+            solve(&env, &expr, &ty, 0).unwrap().to_string(),
+            "|- (fun x -> x) 1 :: [] : int list by T-Cons {
+    |- (fun x -> x) 1 : int by T-App {
+        |- fun x -> x : int -> int by T-Fun {
+            x : int |- x : int by T-Var {}
+        };
+        |- 1 : int by T-Int {}
+    };
+    |- [] : int list by T-Nil {}
+}"
+        );
+    }
+
+    #[test]
+    fn test_solve6() {
+        let (env, expr, ty) = parse("|- let k = fun x -> fun y -> x in k 3 true : int")
+            .unwrap()
+            .1;
+        let rule = solve(&env, &expr, &ty, 0).unwrap();
+        assert_eq!(
+            rule.to_string().lines().nth(1).unwrap(),
+            "    |- fun x -> fun y -> x : int -> bool -> int by T-Fun {"
+        );
+    }
+
+    #[test]
+    fn test_solve7() {
+        // 使われない引数の型は int にする
+        let (env, expr, ty) = parse("|- (fun x -> 1) [] : int").unwrap().1;
+        assert_eq!(
+            solve(&env, &expr, &ty, 0).unwrap().to_string(),
+            "|- (fun x -> 1) [] : int by T-App {
+    |- fun x -> 1 : int list -> int by T-Fun {
+        x : int list |- 1 : int by T-Int {}
+    };
+    |- [] : int list by T-Nil {}
+}"
+        );
+    }
+
+    #[test]
+    fn test_solve8() {
+        let (env, expr, ty) = parse("|- fun x -> x x : int").unwrap().1;
+        assert_eq!(
+            solve(&env, &expr, &ty, 0).unwrap_err().to_string(),
+            "type error at `x`: expected 'a -> 'b, found 'a"
+        );
+        let (env, expr, ty) = parse("|- let f = fun x -> x + y in f : int -> int")
+            .unwrap()
+            .1;
+        assert_eq!(
+            solve(&env, &expr, &ty, 0).unwrap_err().to_string(),
+            "unbound variable `y`"
+        );
+    }
+}
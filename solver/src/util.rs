@@ -1,10 +1,11 @@
 use nom::{character::complete::multispace0, error::ParseError, sequence::delimited, IResult};
+use std::fmt;
 
-pub fn ws<'a, F: 'a, O, E: ParseError<&'a str>>(
+pub fn ws<'a, F, O, E: ParseError<&'a str>>(
     inner: F,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
 where
-    F: Fn(&'a str) -> IResult<&'a str, O, E>,
+    F: 'a + Fn(&'a str) -> IResult<&'a str, O, E>,
 {
     delimited(multispace0, inner, multispace0)
 }
@@ -12,3 +13,13 @@ where
 pub fn ident(depth: usize) -> String {
     "    ".repeat(depth)
 }
+
+// 空の環境は |- の前に空白を入れない
+pub fn env_prefix<T: fmt::Display>(env: &T) -> String {
+    let env = env.to_string();
+    if env.is_empty() {
+        env
+    } else {
+        env + " "
+    }
+}